@@ -0,0 +1,55 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use rust_market_data::{model::default_equities, simulator, tick::Tick};
+
+/// One second of ticks at the default 8ms cadence across the whole universe.
+const TICKS_PER_SYMBOL: usize = 125;
+
+fn sample_stream() -> Vec<Tick> {
+    let equities = default_equities();
+    let mut ticks = Vec::with_capacity(equities.len() * TICKS_PER_SYMBOL);
+    for step in 0..TICKS_PER_SYMBOL {
+        for (idx, equity) in equities.iter().enumerate() {
+            ticks.push(Tick {
+                symbol: equity.symbol.clone(),
+                price: 100.0 + (step as f64 * 0.01) + idx as f64 * 0.1,
+                timestamp_ms: 1_716_400_000_000 + (step * equities.len() + idx) as u64,
+                region: equity.region,
+                sector: equity.sector,
+                prev_close: None,
+            });
+        }
+    }
+    ticks
+}
+
+fn bench_gateway_batch(c: &mut Criterion) {
+    let stream = sample_stream();
+    let encoded =
+        simulator::testkit::aggregate_gateway_batch(stream.clone()).expect("encode gateway batch");
+    println!(
+        "Gateway batch: {} ticks in, {} bytes per batch frame",
+        stream.len(),
+        encoded.len()
+    );
+
+    let mut group = c.benchmark_group("gateway_batch");
+    group.throughput(Throughput::Elements(stream.len() as u64));
+
+    group.bench_function("aggregate_and_serialize", |b| {
+        b.iter_batched(
+            || stream.clone(),
+            |ticks| {
+                black_box(
+                    simulator::testkit::aggregate_gateway_batch(ticks)
+                        .expect("encode gateway batch"),
+                )
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_gateway_batch);
+criterion_main!(benches);
@@ -1,8 +1,9 @@
 use std::time::{Duration, Instant};
 
-use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
 use rust_market_data::{
     logging,
+    model::{default_equities, Equity},
     simulator::{self, SimulatorConfig},
 };
 use tokio::runtime::Runtime;
@@ -49,5 +50,33 @@ fn bench_tick_generation(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_tick_generation);
+/// Serial loop versus rayon for a universe far below the serial threshold.
+fn bench_small_universe_step(c: &mut Criterion) {
+    let equities: Vec<Equity> = default_equities().into_iter().take(10).collect();
+    let shocks: Vec<f64> = (0..equities.len())
+        .map(|idx| (idx as f64 - 4.5) * 0.2)
+        .collect();
+
+    let mut group = c.benchmark_group("small_universe_step");
+    group.throughput(Throughput::Elements(equities.len() as u64));
+
+    for (name, parallel) in [("serial", false), ("parallel", true)] {
+        let mut prices = vec![100.0; equities.len()];
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                black_box(simulator::testkit::advance_prices(
+                    &mut prices,
+                    &equities,
+                    &shocks,
+                    0,
+                    parallel,
+                ))
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_tick_generation, bench_small_universe_step);
 criterion_main!(benches);
@@ -0,0 +1,21 @@
+use std::process::Command;
+
+fn main() {
+    let version = env!("CARGO_PKG_VERSION");
+    let git_hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty());
+
+    let build_version = match git_hash {
+        Some(hash) => format!("{version}+{hash}"),
+        None => version.to_string(),
+    };
+    println!("cargo:rustc-env=MARKET_DATA_BUILD_VERSION={build_version}");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/refs/heads");
+}
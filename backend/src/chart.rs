@@ -1,15 +1,21 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use anyhow::{bail, Context, Result};
 use clap::Args;
+use serde_json::json;
 use textplots::{Chart, Plot, Shape};
 use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::net::UnixStream;
 use tokio::time::{self, Instant};
 
+use crate::cli::print_status;
 use crate::constants::SOCKET_PATH;
-use crate::tick::Tick;
+use crate::logging;
+use crate::model::Symbol;
+use crate::reconnect::{self, ReconnectArgs, ReconnectPolicy};
+use crate::replay;
+use crate::tick::{self, Tick, TickValidator};
 
 #[derive(Debug, Args, Clone)]
 pub struct ChartArgs {
@@ -19,7 +25,7 @@ pub struct ChartArgs {
 
     /// Plot only the provided symbol
     #[arg(short, long)]
-    pub symbol: Option<String>,
+    pub symbol: Option<Symbol>,
 
     /// Chart width in characters
     #[arg(long, default_value_t = 120)]
@@ -28,15 +34,56 @@ pub struct ChartArgs {
     /// Chart height in characters
     #[arg(long, default_value_t = 30)]
     pub height: u32,
+
+    /// Suppress informational banners so stdout only carries the chart
+    #[arg(short, long)]
+    pub quiet: bool,
+
+    /// Print the time and price of the high, low, and last sample below the chart
+    #[arg(long)]
+    pub annotate: bool,
+
+    /// Chart a recording (e.g. from `tail --quiet --json`) instead of the live
+    /// socket, reading the whole file at once
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "duration_secs",
+            "reconnect",
+            "reconnect_base_ms",
+            "reconnect_max_ms",
+            "reconnect_max_attempts",
+        ]
+    )]
+    pub file: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub reconnect: ReconnectArgs,
 }
 
 pub async fn run(args: ChartArgs) -> Result<()> {
-    let duration = Duration::from_secs(args.duration_secs);
-    let collected = collect_ticks(duration, args.symbol.clone()).await?;
-
-    if collected.is_empty() {
-        bail!("no ticks collected; ensure the simulator is running and emitting data");
-    }
+    let collected = match &args.file {
+        Some(path) => {
+            let mut series = PriceSeries::default();
+            for tick in replay::read_recording(path)? {
+                series.record(tick, args.symbol.as_ref());
+            }
+            if series.points.is_empty() {
+                bail!("recording {path:?} holds no ticks to chart");
+            }
+            series.points
+        }
+        None => {
+            let duration = Duration::from_secs(args.duration_secs);
+            let policy = args.reconnect.policy()?;
+            let collected =
+                collect_ticks(duration, args.symbol.clone(), args.quiet, policy.as_ref()).await?;
+            if collected.is_empty() {
+                bail!("no ticks collected; ensure the simulator is running and emitting data");
+            }
+            collected
+        }
+    };
 
     let (symbol, points) = if let Some(symbol) = &args.symbol {
         let Some(points) = collected.get(symbol) else {
@@ -54,33 +101,60 @@ pub async fn run(args: ChartArgs) -> Result<()> {
         bail!("not enough data points to render a chart");
     }
 
-    render_chart(&symbol, points, duration, args.width, args.height);
+    let model = build_chart_model(symbol, points).context("no data points to chart")?;
+    let span = match args.file {
+        Some(_) => Duration::from_secs_f64(model.max_time),
+        None => Duration::from_secs(args.duration_secs),
+    };
+    render_chart(&model, span, &args);
     Ok(())
 }
 
+/// Per-symbol `(seconds since the first tick, price)` samples.
+#[derive(Debug, Default)]
+struct PriceSeries {
+    reference_ms: Option<u64>,
+    points: HashMap<Symbol, Vec<(f64, f64)>>,
+}
+
+impl PriceSeries {
+    /// Add `tick` unless `filter` names a different symbol.
+    fn record(&mut self, tick: Tick, filter: Option<&Symbol>) {
+        if filter.is_some_and(|filter| filter != &tick.symbol) {
+            return;
+        }
+        let base = *self.reference_ms.get_or_insert(tick.timestamp_ms);
+        let elapsed = tick.timestamp_ms.saturating_sub(base) as f64 / 1000.0;
+        self.points
+            .entry(tick.symbol)
+            .or_default()
+            .push((elapsed, tick.price));
+    }
+}
+
 async fn collect_ticks(
     duration: Duration,
-    symbol_filter: Option<String>,
-) -> Result<HashMap<String, Vec<(f64, f64)>>> {
-    let stream = UnixStream::connect(SOCKET_PATH).await.with_context(|| {
-        format!(
-            "failed to connect to socket {:?}; run `cargo run -- run` first",
-            SOCKET_PATH
-        )
-    })?;
+    symbol_filter: Option<Symbol>,
+    quiet: bool,
+    policy: Option<&ReconnectPolicy>,
+) -> Result<HashMap<Symbol, Vec<(f64, f64)>>> {
+    let stream = reconnect::connect_unix(Path::new(SOCKET_PATH), policy).await?;
 
     let mut lines = BufReader::new(stream).lines();
+    let mut validator = TickValidator::default();
     let deadline = Instant::now() + duration;
-    let mut reference_timestamp: Option<u128> = None;
-    let mut data: HashMap<String, Vec<(f64, f64)>> = HashMap::new();
+    let mut series = PriceSeries::default();
 
-    println!(
-        "Collecting ticks for {}s{}...",
-        duration.as_secs(),
-        symbol_filter
-            .as_ref()
-            .map(|s| format!(" (filtering for {s})"))
-            .unwrap_or_default()
+    print_status(
+        quiet,
+        &format!(
+            "Collecting ticks for {}s{}...",
+            duration.as_secs(),
+            symbol_filter
+                .as_ref()
+                .map(|s| format!(" (filtering for {s})"))
+                .unwrap_or_default()
+        ),
     );
 
     loop {
@@ -93,67 +167,228 @@ async fn collect_ticks(
         }
 
         match time::timeout(remaining, lines.next_line()).await {
-            Ok(line_result) => match line_result? {
-                Some(line) => {
-                    let tick: Tick = serde_json::from_str(&line)?;
-                    if let Some(ref filter) = symbol_filter {
-                        if filter != &tick.symbol {
+            Ok(line_result) => match line_result {
+                Ok(Some(line)) => {
+                    let tick: Tick = match serde_json::from_str(&line) {
+                        Ok(tick) => tick,
+                        Err(err) => {
+                            logging::warn(
+                                "chart.invalid_tick",
+                                "Skipping malformed tick",
+                                json!({ "error": err.to_string() }),
+                            );
                             continue;
                         }
+                    };
+                    if let Err(err) = validator.check(&tick, tick::now_ms()) {
+                        logging::warn(
+                            "chart.invalid_tick",
+                            "Skipping malformed tick",
+                            json!({ "symbol": tick.symbol, "error": err.to_string() }),
+                        );
+                        continue;
+                    }
+                    series.record(tick, symbol_filter.as_ref());
+                }
+                Ok(None) | Err(_) if policy.is_some() => {
+                    if let Err(err) = line_result {
+                        logging::warn(
+                            "chart.stream_error",
+                            "Socket read failed; reconnecting",
+                            json!({ "error": err.to_string() }),
+                        );
+                    }
+                    print_status(quiet, "Stream closed; reconnecting...");
+                    let stream = time::timeout(
+                        remaining,
+                        reconnect::connect_unix(Path::new(SOCKET_PATH), policy),
+                    )
+                    .await;
+                    match stream {
+                        Ok(stream) => {
+                            lines = BufReader::new(stream?).lines();
+                            validator = TickValidator::default();
+                        }
+                        Err(_) => break,
                     }
-
-                    let base = reference_timestamp.get_or_insert(tick.timestamp_ms);
-                    let elapsed = ((tick.timestamp_ms - *base) as f64) / 1000.0;
-                    data.entry(tick.symbol.clone())
-                        .or_default()
-                        .push((elapsed, tick.price));
                 }
-                None => break,
+                Ok(None) => break,
+                Err(err) => return Err(err.into()),
             },
             Err(_) => break,
         }
     }
 
-    Ok(data)
+    Ok(series.points)
 }
 
-fn render_chart(
-    symbol: &str,
-    mut points: Vec<(f64, f64)>,
-    duration: Duration,
-    width: u32,
-    height: u32,
-) {
+/// A sample on the chart: seconds since the first collected tick and the price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ChartPoint {
+    time: f64,
+    price: f64,
+}
+
+/// One symbol's price path, ordered by time and with its axis bounds resolved.
+#[derive(Debug, Clone, PartialEq)]
+struct ChartModel {
+    symbol: Symbol,
+    /// `(seconds since the first tick, price)` pairs in time order.
+    samples: Vec<(f32, f32)>,
+    min_price: f64,
+    max_price: f64,
+    /// Right edge of the time axis, never below 1ms so the axis is not empty.
+    max_time: f64,
+    /// Earliest sample at `max_price`.
+    high: ChartPoint,
+    /// Earliest sample at `min_price`.
+    low: ChartPoint,
+    last: ChartPoint,
+}
+
+impl ChartModel {
+    /// Lines describing the high, low, and last sample, printed under the plot.
+    fn annotations(&self) -> [String; 3] {
+        let line = |label: &str, point: ChartPoint| {
+            format!("{label:<5} {:.4} at +{:.2}s", point.price, point.time)
+        };
+        [
+            line("High:", self.high),
+            line("Low:", self.low),
+            line("Last:", self.last),
+        ]
+    }
+}
+
+/// Build the chart model, or `None` when there are no points.
+fn build_chart_model(symbol: Symbol, mut points: Vec<(f64, f64)>) -> Option<ChartModel> {
     points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
 
-    println!(
-        "Rendering chart for {symbol} ({} samples) collected over ~{}s",
-        points.len(),
-        duration.as_secs()
-    );
+    let point = |&(time, price): &(f64, f64)| ChartPoint { time, price };
+    let last = point(points.last()?);
+    let mut high = point(points.first()?);
+    let mut low = high;
+    for sample in points.iter().map(point) {
+        if sample.price > high.price {
+            high = sample;
+        }
+        if sample.price < low.price {
+            low = sample;
+        }
+    }
+
+    let max_time = last.time.max(1e-3);
+    let (min_price, max_price) = (low.price, high.price);
 
-    let max_time = points
-        .last()
-        .map(|(t, _)| *t)
-        .unwrap_or(duration.as_secs_f64())
-        .max(1e-3);
-    let min_price = points.iter().map(|(_, p)| *p).fold(f64::INFINITY, f64::min);
-    let max_price = points
-        .iter()
-        .map(|(_, p)| *p)
-        .fold(f64::NEG_INFINITY, f64::max);
-    println!("Price range: {:.4} → {:.4}", min_price, max_price);
-
-    let samples: Vec<(f32, f32)> = points
+    let samples = points
         .into_iter()
         .map(|(t, p)| (t as f32, p as f32))
         .collect();
 
-    let plot_width = width.max(40);
-    let plot_height = height.max(10);
+    Some(ChartModel {
+        symbol,
+        samples,
+        min_price,
+        max_price,
+        max_time,
+        high,
+        low,
+        last,
+    })
+}
 
-    Chart::new(plot_width, plot_height, 0.0, max_time as f32)
-        .lineplot(&Shape::Lines(&samples))
+fn render_chart(model: &ChartModel, duration: Duration, args: &ChartArgs) {
+    print_status(
+        args.quiet,
+        &format!(
+            "Rendering chart for {} ({} samples) collected over ~{}s",
+            model.symbol,
+            model.samples.len(),
+            duration.as_secs()
+        ),
+    );
+    println!(
+        "Price range: {:.4} → {:.4}",
+        model.min_price, model.max_price
+    );
+
+    let plot_width = args.width.max(40);
+    let plot_height = args.height.max(10);
+
+    Chart::new(plot_width, plot_height, 0.0, model.max_time as f32)
+        .lineplot(&Shape::Lines(&model.samples))
         .display();
+    if args.annotate {
+        for line in model.annotations() {
+            println!("{line}");
+        }
+    }
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol() -> Symbol {
+        "NATECH007".parse().unwrap()
+    }
+
+    #[test]
+    fn model_orders_samples_and_resolves_bounds() {
+        let points = vec![(2.0, 101.5), (0.0, 100.0), (1.0, 99.25), (3.5, 100.75)];
+        let model = build_chart_model(symbol(), points).expect("model");
+
+        assert_eq!(
+            model.samples,
+            vec![(0.0, 100.0), (1.0, 99.25), (2.0, 101.5), (3.5, 100.75)]
+        );
+        assert_eq!(model.min_price, 99.25);
+        assert_eq!(model.max_price, 101.5);
+        assert_eq!(model.max_time, 3.5);
+        assert_eq!(model.symbol, "NATECH007");
+    }
+
+    #[test]
+    fn model_time_axis_is_never_empty() {
+        let model = build_chart_model(symbol(), vec![(0.0, 10.0), (0.0, 10.5)]).expect("model");
+        assert_eq!(model.max_time, 1e-3);
+        assert!(build_chart_model(symbol(), Vec::new()).is_none());
+    }
+
+    #[test]
+    fn annotations_report_extrema_and_last_sample() {
+        let points = vec![
+            (0.0, 100.0),
+            (1.5, 103.25),
+            (3.0, 97.5),
+            (4.0, 103.25),
+            (6.25, 99.0),
+        ];
+        let model = build_chart_model(symbol(), points).expect("model");
+
+        assert_eq!(
+            model.high,
+            ChartPoint {
+                time: 1.5,
+                price: 103.25
+            },
+            "ties keep the first high"
+        );
+        assert_eq!(
+            model.low,
+            ChartPoint {
+                time: 3.0,
+                price: 97.5
+            }
+        );
+        assert_eq!(
+            model.annotations(),
+            [
+                "High: 103.2500 at +1.50s",
+                "Low:  97.5000 at +3.00s",
+                "Last: 99.0000 at +6.25s",
+            ]
+        );
+    }
+}
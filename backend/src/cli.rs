@@ -1,6 +1,14 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Args, Parser, Subcommand};
 
 use crate::chart::ChartArgs;
+use crate::export::ExportArgs;
+use crate::logging::LogFormat;
+use crate::probe::ProbeArgs;
+use crate::replay::ReplayArgs;
+use crate::simulator::SimulatorConfig;
 use crate::tail::TailArgs;
 
 #[derive(Debug, Parser)]
@@ -8,21 +16,117 @@ use crate::tail::TailArgs;
 pub struct Cli {
     #[command(subcommand)]
     command: Option<Command>,
+
+    /// Log output format
+    #[arg(long, value_enum, global = true, default_value_t = LogFormat::Json)]
+    log_format: LogFormat,
 }
 
 impl Cli {
+    pub fn log_format(&self) -> LogFormat {
+        self.log_format
+    }
+
     pub fn command(self) -> Command {
         self.command.unwrap_or_default()
     }
 }
 
-#[derive(Debug, Subcommand, Default)]
+#[derive(Debug, Subcommand)]
 pub enum Command {
     /// Run the tick generator and socket publisher
-    #[default]
-    Run,
+    Run(RunArgs),
     /// Subscribe to the unix socket and print incoming ticks
     Tail(TailArgs),
     /// Collect ticks and render an ASCII price chart
     Chart(ChartArgs),
+    /// Write the universe, correlation matrix, and seed for a reproducible run
+    Export(ExportArgs),
+    /// Measure end-to-end latency and batch jitter against a running gateway
+    Probe(ProbeArgs),
+    /// Play back a recorded tick file at its original pace (or faster)
+    Replay(ReplayArgs),
+}
+
+impl Default for Command {
+    fn default() -> Self {
+        Command::Run(RunArgs::default())
+    }
+}
+
+#[derive(Debug, Args, Clone, Default)]
+pub struct RunArgs {
+    /// Load settings from a TOML config file; flags given alongside it take precedence
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// Seed every random stream for a reproducible run
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Derive the correlation structure from a scenario id, independent of --seed
+    #[arg(long)]
+    pub scenario_id: Option<u64>,
+
+    /// Load the equity universe from a JSON file written by `export`, or a `symbol,region,sector` CSV
+    #[arg(long)]
+    pub universe_path: Option<PathBuf>,
+
+    /// Load the correlation matrix from a CSV file written by `export`
+    #[arg(long)]
+    pub correlation_path: Option<PathBuf>,
+
+    /// Separate the region, sector, and number in default symbols (e.g. `_` for NA_TECH_007);
+    /// letters, digits, and whitespace are rejected
+    #[arg(long)]
+    pub symbol_separator: Option<char>,
+
+    /// Pin the number of threads used for per-tick price updates (defaults to all cores)
+    #[arg(long)]
+    pub rayon_threads: Option<usize>,
+
+    /// Replay each symbol's last N ticks to new socket subscribers before live ticks
+    #[arg(long)]
+    pub tail_n: Option<usize>,
+}
+
+impl RunArgs {
+    /// The config file's settings (or the defaults) with every given flag applied on top.
+    pub fn into_config(self) -> Result<SimulatorConfig> {
+        let mut config = match &self.config {
+            Some(path) => SimulatorConfig::from_toml_file(path)?,
+            None => SimulatorConfig::default(),
+        };
+        if let Some(seed) = self.seed {
+            config.seed = Some(seed);
+        }
+        if let Some(scenario_id) = self.scenario_id {
+            config.scenario_id = Some(scenario_id);
+        }
+        if let Some(path) = self.universe_path {
+            config.universe_path = Some(path);
+        }
+        if let Some(path) = self.correlation_path {
+            config.correlation_path = Some(path);
+        }
+        if let Some(separator) = self.symbol_separator {
+            config.symbol_separator = Some(separator);
+        }
+        if let Some(threads) = self.rayon_threads {
+            config.rayon_threads = Some(threads);
+        }
+        if let Some(tail_n) = self.tail_n {
+            config.socket_backlog = tail_n;
+        }
+        Ok(config)
+    }
+}
+
+/// Print an informational status line unless quiet mode is enabled.
+///
+/// Status lines go to stderr so stdout stays reserved for tick output.
+pub fn print_status(quiet: bool, message: &str) {
+    if !quiet {
+        eprintln!("{message}");
+    }
 }
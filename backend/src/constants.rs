@@ -1,7 +1,33 @@
 pub const SOCKET_PATH: &str = "market_ticks.sock";
+pub const STATE_DUMP_DIR: &str = "dumps";
+pub const REDIS_CHANNEL: &str = "market_ticks";
+pub const REDIS_RETRY_MS: u64 = 1_000;
 pub const TICK_INTERVAL_MS: u64 = 8;
 pub const CORRELATION_REFRESH_SECS: u64 = 30;
+pub const SUBSCRIBER_REPORT_SECS: u64 = 1;
 pub const GATEWAY_BIND_ADDR: &str = "127.0.0.1:9001";
 pub const GATEWAY_THROTTLE_MS: u64 = 1_000;
 pub const GATEWAY_QUEUE_DEPTH: usize = 8;
-pub const TICK_BATCH_VERSION: u32 = 1;
+pub const GATEWAY_DEBUG_INTERVAL_MS: u64 = 1_000;
+pub const HEARTBEAT_INTERVAL_MS: u64 = 5_000;
+pub const GATEWAY_MAX_INBOUND_MESSAGE_BYTES: usize = 4 * 1024;
+pub const GATEWAY_MAX_CLIENT_SYMBOLS: usize = 100;
+pub const GATEWAY_CLIENT_SEND_TIMEOUT_MS: u64 = 5_000;
+pub const HEALTH_DEGRADED_LOSS_PER_SEC: f64 = 1.0;
+pub const HEALTH_UNHEALTHY_LOSS_PER_SEC: f64 = 20.0;
+pub const GATEWAY_BIND_ATTEMPTS: u32 = 10;
+pub const GATEWAY_BIND_BACKOFF_MS: u64 = 100;
+pub const GATEWAY_BIND_MAX_BACKOFF_MS: u64 = 2_000;
+pub const GATEWAY_LAG_ALERT_TICKS: usize = 4_096;
+pub const GATEWAY_LAG_ALERT_WINDOW_MS: u64 = 10_000;
+pub const TICK_BATCH_VERSION: u32 = 4;
+pub const SOCKET_FLUSH_INTERVAL_MS: u64 = 10;
+pub const SOCKET_BUFFER_BYTES: usize = 64 * 1024;
+pub const SERIAL_UNIVERSE_THRESHOLD: usize = 64;
+pub const COMPUTE_BUDGET_WINDOW: usize = 128;
+pub const BASE_SPREAD_BPS: f64 = 4.0;
+pub const STRESS_SPREAD_MULTIPLIER: f64 = 3.0;
+pub const JUMP_SCALE: f64 = 4.0;
+pub const CORRELATED_INIT_BASE_PRICE: f64 = 115.0;
+pub const CORRELATED_INIT_DISPERSION: f64 = 0.15;
+pub const PRICE_CHANGE_EPSILON: f64 = 1e-9;
@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Args;
+use serde_json::json;
+
+use crate::cli::RunArgs;
+use crate::logging;
+use crate::simulator;
+
+#[derive(Debug, Args, Clone)]
+pub struct ExportArgs {
+    /// Directory receiving universe.json, correlation.csv, and seed.txt
+    #[arg(long, default_value = "scenario")]
+    pub out_dir: PathBuf,
+
+    #[command(flatten)]
+    pub scenario: RunArgs,
+}
+
+pub fn run(args: ExportArgs) -> Result<()> {
+    let config = args.scenario.into_config()?;
+    let bundle = simulator::export_bundle(&config, &args.out_dir)?;
+
+    logging::info(
+        "export.complete",
+        "Scenario bundle written",
+        json!({
+            "seed": bundle.seed,
+            "universe": bundle.universe_path.display().to_string(),
+            "correlation": bundle.correlation_path.display().to_string(),
+        }),
+    );
+    println!(
+        "Reproduce with: cargo run -p rust-market-data -- run --seed {} --universe-path {} --correlation-path {}",
+        bundle.seed,
+        bundle.universe_path.display(),
+        bundle.correlation_path.display()
+    );
+    Ok(())
+}
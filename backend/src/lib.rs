@@ -1,8 +1,13 @@
 pub mod chart;
 pub mod cli;
 pub mod constants;
+pub mod export;
 pub mod logging;
 pub mod model;
+pub mod probe;
+pub mod reconnect;
+pub mod replay;
 pub mod simulator;
 pub mod tail;
 pub mod tick;
+pub mod version;
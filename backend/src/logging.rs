@@ -1,23 +1,49 @@
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use clap::ValueEnum;
 use serde::Serialize;
 use serde_json::{json, Value};
-use std::sync::atomic::{AtomicBool, Ordering};
 
 #[derive(Serialize)]
 struct LogEvent<'a> {
     level: &'a str,
     event: &'a str,
     message: &'a str,
-    timestamp_ms: u128,
+    timestamp_ms: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     metadata: Option<Value>,
 }
 
+/// Output format for log lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// One JSON object per line (see `schemas/log.schema.json`)
+    #[default]
+    Json,
+    /// Human-readable `timestamp LEVEL [event] message {metadata}` lines
+    Text,
+}
+
 static SILENT: AtomicBool = AtomicBool::new(false);
+static FORMAT: AtomicU8 = AtomicU8::new(LogFormat::Json as u8);
 
 pub fn set_silent(value: bool) {
     SILENT.store(value, Ordering::Relaxed);
 }
 
+pub fn set_format(format: LogFormat) {
+    FORMAT.store(format as u8, Ordering::Relaxed);
+}
+
+fn current_format() -> LogFormat {
+    if FORMAT.load(Ordering::Relaxed) == LogFormat::Text as u8 {
+        LogFormat::Text
+    } else {
+        LogFormat::Json
+    }
+}
+
 fn emit(level: &str, event: &str, message: &str, metadata: Option<Value>) {
     if SILENT.load(Ordering::Relaxed) && level != "error" {
         return;
@@ -31,18 +57,70 @@ fn emit(level: &str, event: &str, message: &str, metadata: Option<Value>) {
         metadata,
     };
 
-    match serde_json::to_string(&entry) {
-        Ok(payload) => {
-            if level == "error" {
-                eprintln!("{payload}");
-            } else {
-                println!("{payload}");
+    let format = current_format();
+    let result = if level == "error" {
+        write_entry(&mut std::io::stderr().lock(), format, &entry)
+    } else {
+        write_entry(&mut std::io::stdout().lock(), format, &entry)
+    };
+
+    if let Err(err) = result {
+        eprintln!(
+            "{{\"level\":\"error\",\"event\":\"logging_failure\",\"message\":\"failed to serialise log\",\"error\":\"{err}\"}}"
+        );
+    }
+}
+
+fn write_entry<W: Write>(
+    sink: &mut W,
+    format: LogFormat,
+    entry: &LogEvent<'_>,
+) -> std::io::Result<()> {
+    match format {
+        LogFormat::Json => {
+            serde_json::to_writer(&mut *sink, entry)?;
+        }
+        LogFormat::Text => {
+            write!(
+                sink,
+                "{} {:<5} [{}] {}",
+                format_utc_timestamp(entry.timestamp_ms),
+                entry.level.to_uppercase(),
+                entry.event,
+                entry.message
+            )?;
+            if let Some(metadata) = &entry.metadata {
+                write!(sink, " {metadata}")?;
             }
         }
-        Err(err) => eprintln!(
-            "{{\"level\":\"error\",\"event\":\"logging_failure\",\"message\":\"failed to serialise log\",\"error\":\"{err}\"}}"
-        ),
     }
+    sink.write_all(b"\n")
+}
+
+/// Render epoch milliseconds as an ISO-8601 UTC timestamp.
+fn format_utc_timestamp(timestamp_ms: u64) -> String {
+    let total_secs = (timestamp_ms / 1000) as i64;
+    let millis = (timestamp_ms % 1000) as u32;
+    let days = total_secs.div_euclid(86_400);
+    let secs_of_day = total_secs.rem_euclid(86_400);
+
+    // civil-from-days conversion (proleptic Gregorian calendar)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{millis:03}Z",
+        secs_of_day / 3_600,
+        (secs_of_day % 3_600) / 60,
+        secs_of_day % 60
+    )
 }
 
 pub fn info(event: &str, message: &str, metadata: Value) {
@@ -69,13 +147,13 @@ pub fn error_simple(event: &str, message: &str) {
     emit("error", event, message, None);
 }
 
-fn current_timestamp_ms() -> u128 {
+fn current_timestamp_ms() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    SystemTime::now()
+    let elapsed = SystemTime::now()
         .duration_since(UNIX_EPOCH)
-        .expect("time went backwards")
-        .as_millis()
+        .expect("time went backwards");
+    u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
 }
 
 pub fn metadata_from_pairs(pairs: &[(&str, Value)]) -> Value {
@@ -89,3 +167,57 @@ pub fn metadata_from_pairs(pairs: &[(&str, Value)]) -> Value {
 pub fn metadata_object() -> Value {
     json!({})
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_entry(metadata: Option<Value>) -> LogEvent<'static> {
+        LogEvent {
+            level: "info",
+            event: "socket.bind",
+            message: "Listening for tick subscribers",
+            timestamp_ms: 1_716_400_001_000,
+            metadata,
+        }
+    }
+
+    fn render(format: LogFormat, entry: &LogEvent<'_>) -> String {
+        let mut sink = Vec::new();
+        write_entry(&mut sink, format, entry).expect("write log entry");
+        String::from_utf8(sink).expect("utf8 log line")
+    }
+
+    #[test]
+    fn json_format_matches_schema_fields() {
+        let line = render(
+            LogFormat::Json,
+            &sample_entry(Some(json!({ "path": "market_ticks.sock" }))),
+        );
+        assert!(line.ends_with('\n'));
+
+        let parsed: Value = serde_json::from_str(line.trim_end()).expect("json log line");
+        assert_eq!(parsed["level"], "info");
+        assert_eq!(parsed["event"], "socket.bind");
+        assert_eq!(parsed["timestamp_ms"], 1_716_400_001_000u64);
+        assert_eq!(parsed["metadata"]["path"], "market_ticks.sock");
+    }
+
+    #[test]
+    fn text_format_is_single_readable_line() {
+        let line = render(
+            LogFormat::Text,
+            &sample_entry(Some(json!({ "path": "market_ticks.sock" }))),
+        );
+        assert_eq!(
+            line,
+            "2024-05-22T17:46:41.000Z INFO  [socket.bind] Listening for tick subscribers {\"path\":\"market_ticks.sock\"}\n"
+        );
+
+        let bare = render(LogFormat::Text, &sample_entry(None));
+        assert_eq!(
+            bare,
+            "2024-05-22T17:46:41.000Z INFO  [socket.bind] Listening for tick subscribers\n"
+        );
+    }
+}
@@ -1,15 +1,29 @@
+use std::process::ExitCode;
+
 use anyhow::Result;
 use clap::Parser;
 use rust_market_data::chart;
 use rust_market_data::cli::{self, Cli};
+use rust_market_data::export;
+use rust_market_data::logging;
+use rust_market_data::probe;
+use rust_market_data::replay;
 use rust_market_data::simulator;
 use rust_market_data::tail;
 
 #[tokio::main(flavor = "multi_thread", worker_threads = 6)]
-async fn main() -> Result<()> {
-    match Cli::parse().command() {
-        cli::Command::Run => simulator::run().await,
-        cli::Command::Tail(args) => tail::run(args).await,
-        cli::Command::Chart(args) => chart::run(args).await,
+async fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+    logging::set_format(cli.log_format());
+
+    match cli.command() {
+        cli::Command::Run(args) => simulator::run_with_config(args.into_config()?)
+            .await
+            .map(|reason| ExitCode::from(reason.exit_code())),
+        cli::Command::Tail(args) => tail::run(args).await.map(|()| ExitCode::SUCCESS),
+        cli::Command::Chart(args) => chart::run(args).await.map(|()| ExitCode::SUCCESS),
+        cli::Command::Export(args) => export::run(args).map(|()| ExitCode::SUCCESS),
+        cli::Command::Probe(args) => probe::run(args).await.map(|()| ExitCode::SUCCESS),
+        cli::Command::Replay(args) => replay::run(args).await.map(|()| ExitCode::SUCCESS),
     }
 }
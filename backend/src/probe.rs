@@ -0,0 +1,191 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use futures_util::StreamExt;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::time::{self, Instant};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::cli::print_status;
+use crate::constants::GATEWAY_BIND_ADDR;
+use crate::logging;
+use crate::tick::Tick;
+
+const REPORTED_QUANTILES: [f64; 3] = [0.5, 0.9, 0.99];
+
+#[derive(Debug, Args, Clone)]
+pub struct ProbeArgs {
+    /// Websocket URL of a running gateway (defaults to the local gateway)
+    #[arg(long)]
+    pub url: Option<String>,
+
+    /// Number of seconds to sample batches before reporting
+    #[arg(short, long, default_value_t = 10)]
+    pub duration_secs: u64,
+
+    /// Suppress informational banners so stdout only carries the report
+    #[arg(short, long)]
+    pub quiet: bool,
+}
+
+/// Only batch frames carry ticks; the handshake frame is skipped.
+#[derive(Deserialize)]
+struct Frame {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    ticks: Vec<Tick>,
+}
+
+pub async fn run(args: ProbeArgs) -> Result<()> {
+    let url = args
+        .url
+        .clone()
+        .unwrap_or_else(|| format!("ws://{GATEWAY_BIND_ADDR}/ws"));
+    let (mut socket, _) = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .with_context(|| format!("failed to connect to gateway {url}; is `run` active?"))?;
+    print_status(
+        args.quiet,
+        &format!(
+            "Connected to {url}; sampling for {}s...",
+            args.duration_secs
+        ),
+    );
+
+    let deadline = Instant::now() + Duration::from_secs(args.duration_secs);
+    let mut latencies_ms = Vec::new();
+    let mut intervals_ms = Vec::new();
+    let mut last_batch: Option<Instant> = None;
+
+    loop {
+        let message = match time::timeout_at(deadline, socket.next()).await {
+            Err(_) | Ok(None) => break,
+            Ok(Some(message)) => message.context("gateway stream failed")?,
+        };
+        let received_at = Instant::now();
+        let received_ms = unix_time_ms();
+
+        let Message::Text(text) = message else {
+            continue;
+        };
+        let frame: Frame = match serde_json::from_str(&text) {
+            Ok(frame) => frame,
+            Err(err) => {
+                logging::warn(
+                    "probe.invalid_frame",
+                    "Skipping unparseable gateway frame",
+                    json!({ "error": err.to_string() }),
+                );
+                continue;
+            }
+        };
+        if frame.kind != "batch" {
+            continue;
+        }
+        let Some(newest) = frame.ticks.iter().map(|tick| tick.timestamp_ms).max() else {
+            continue;
+        };
+
+        latencies_ms.push(received_ms.saturating_sub(newest) as f64);
+        if let Some(previous) = last_batch.replace(received_at) {
+            intervals_ms.push(received_at.duration_since(previous).as_secs_f64() * 1_000.0);
+        }
+    }
+
+    let _ = socket.close(None).await;
+
+    let Some(latency) = percentiles(&latencies_ms, &REPORTED_QUANTILES) else {
+        bail!(
+            "no batches received from {url} within {}s",
+            args.duration_secs
+        );
+    };
+    println!("batches received: {}", latencies_ms.len());
+    println!(
+        "latency ms (receive - newest tick): p50 {:.1} | p90 {:.1} | p99 {:.1}",
+        latency[0], latency[1], latency[2]
+    );
+    if let Some(interval) = percentiles(&intervals_ms, &REPORTED_QUANTILES) {
+        println!(
+            "batch interval ms: p50 {:.1} | p90 {:.1} | p99 {:.1} | jitter (stddev) {:.1}",
+            interval[0],
+            interval[1],
+            interval[2],
+            std_dev(&intervals_ms)
+        );
+    }
+    Ok(())
+}
+
+/// Nearest-rank percentiles of `samples` for each quantile in `quantiles` (each in `0.0..=1.0`).
+///
+/// Returns `None` when there are no samples.
+pub fn percentiles(samples: &[f64], quantiles: &[f64]) -> Option<Vec<f64>> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let last = sorted.len() - 1;
+    Some(
+        quantiles
+            .iter()
+            .map(|&quantile| {
+                let rank = (quantile.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+                sorted[rank.saturating_sub(1).min(last)]
+            })
+            .collect(),
+    )
+}
+
+fn std_dev(samples: &[f64]) -> f64 {
+    let count = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / count;
+    (samples
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / count)
+        .sqrt()
+}
+
+fn unix_time_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let samples: Vec<f64> = (1..=100).rev().map(f64::from).collect();
+        assert_eq!(
+            percentiles(&samples, &[0.5, 0.9, 0.99]),
+            Some(vec![50.0, 90.0, 99.0])
+        );
+        assert_eq!(percentiles(&samples, &[0.0, 1.0]), Some(vec![1.0, 100.0]));
+    }
+
+    #[test]
+    fn percentiles_handle_small_and_empty_inputs() {
+        assert_eq!(percentiles(&[], &[0.5]), None);
+        assert_eq!(percentiles(&[7.0], &[0.5, 0.99]), Some(vec![7.0, 7.0]));
+        assert_eq!(
+            percentiles(&[3.0, 1.0, 2.0, 4.0], &[0.5, 0.9]),
+            Some(vec![2.0, 4.0])
+        );
+    }
+
+    #[test]
+    fn std_dev_measures_interval_spread() {
+        assert_eq!(std_dev(&[10.0, 10.0, 10.0]), 0.0);
+        assert_eq!(std_dev(&[8.0, 12.0]), 2.0);
+    }
+}
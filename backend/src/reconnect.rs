@@ -0,0 +1,168 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use market_core::next_backoff;
+use serde_json::json;
+use tokio::net::UnixStream;
+use tokio::time;
+
+use crate::logging;
+
+/// Reconnect flags shared by the `tail` and `chart` subcommands.
+#[derive(Debug, Args, Clone)]
+pub struct ReconnectArgs {
+    /// Reconnect with exponential backoff when the socket is unavailable or closes
+    #[arg(long)]
+    pub reconnect: bool,
+
+    /// Initial delay between reconnect attempts, in milliseconds
+    #[arg(long, default_value_t = 250)]
+    pub reconnect_base_ms: u64,
+
+    /// Upper bound for the delay between reconnect attempts, in milliseconds
+    #[arg(long, default_value_t = 5_000)]
+    pub reconnect_max_ms: u64,
+
+    /// Give up after this many consecutive failed attempts (unlimited when omitted)
+    #[arg(long)]
+    pub reconnect_max_attempts: Option<u32>,
+}
+
+impl ReconnectArgs {
+    /// Validate the flags and return the policy to use, or `None` when reconnecting is disabled.
+    pub fn policy(&self) -> Result<Option<ReconnectPolicy>> {
+        let policy = ReconnectPolicy::new(
+            Duration::from_millis(self.reconnect_base_ms),
+            Duration::from_millis(self.reconnect_max_ms),
+            self.reconnect_max_attempts,
+        )?;
+        Ok(self.reconnect.then_some(policy))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    pub base: Duration,
+    pub max: Duration,
+    pub max_attempts: Option<u32>,
+}
+
+impl ReconnectPolicy {
+    pub fn new(base: Duration, max: Duration, max_attempts: Option<u32>) -> Result<Self> {
+        if base.is_zero() {
+            bail!("--reconnect-base-ms must be greater than zero");
+        }
+        if base > max {
+            bail!(
+                "--reconnect-base-ms ({}) must not exceed --reconnect-max-ms ({})",
+                base.as_millis(),
+                max.as_millis()
+            );
+        }
+        Ok(Self {
+            base,
+            max,
+            max_attempts,
+        })
+    }
+
+    fn exhausted(&self, attempts: u32) -> bool {
+        matches!(self.max_attempts, Some(limit) if attempts >= limit)
+    }
+
+    /// Delay to wait after `current`, on the schedule the dashboard also uses.
+    pub fn next_delay(&self, current: Duration) -> Duration {
+        next_backoff(current, self.base, self.max)
+    }
+}
+
+/// Connect to the simulator socket, retrying according to `policy` when one is provided.
+pub async fn connect_unix(path: &Path, policy: Option<&ReconnectPolicy>) -> Result<UnixStream> {
+    let mut attempts = 0u32;
+    let mut delay = policy.map(|policy| policy.base).unwrap_or_default();
+
+    loop {
+        let err = match UnixStream::connect(path).await {
+            Ok(stream) => return Ok(stream),
+            Err(err) => err,
+        };
+        attempts += 1;
+
+        let Some(policy) = policy.filter(|policy| !policy.exhausted(attempts)) else {
+            return Err(err).with_context(|| {
+                format!(
+                    "failed to connect to socket {:?}; run `cargo run -- run` first",
+                    path
+                )
+            });
+        };
+
+        logging::warn(
+            "reconnect.retry",
+            "Socket unavailable; retrying",
+            json!({
+                "path": path.display().to_string(),
+                "attempt": attempts,
+                "delay_ms": delay.as_millis() as u64,
+                "error": err.to_string(),
+            }),
+        );
+        time::sleep(delay).await;
+        delay = policy.next_delay(delay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(base_ms: u64, max_ms: u64) -> ReconnectPolicy {
+        ReconnectPolicy::new(
+            Duration::from_millis(base_ms),
+            Duration::from_millis(max_ms),
+            None,
+        )
+        .expect("valid policy")
+    }
+
+    #[test]
+    fn delays_follow_the_configured_bounds() {
+        let policy = policy(100, 750);
+        let mut delay = policy.base;
+        let mut schedule = vec![delay.as_millis()];
+        for _ in 0..5 {
+            delay = policy.next_delay(delay);
+            schedule.push(delay.as_millis());
+        }
+        assert_eq!(schedule, vec![100, 200, 400, 750, 750, 750]);
+
+        let constant = self::policy(300, 300);
+        assert_eq!(
+            constant.next_delay(constant.base),
+            Duration::from_millis(300)
+        );
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        assert!(ReconnectPolicy::new(Duration::ZERO, Duration::from_secs(1), None).is_err());
+        assert!(
+            ReconnectPolicy::new(Duration::from_secs(2), Duration::from_secs(1), None).is_err()
+        );
+    }
+
+    #[test]
+    fn attempts_are_bounded_when_configured() {
+        let policy = ReconnectPolicy::new(
+            Duration::from_millis(10),
+            Duration::from_millis(20),
+            Some(3),
+        )
+        .unwrap();
+        assert!(!policy.exhausted(2));
+        assert!(policy.exhausted(3));
+        assert!(!self::policy(10, 20).exhausted(u32::MAX));
+    }
+}
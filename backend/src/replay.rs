@@ -0,0 +1,251 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use serde_json::json;
+use tokio::time::{self, Instant};
+
+use crate::cli::print_status;
+use crate::logging;
+use crate::tick::{self, Tick, TickValidator};
+
+#[derive(Debug, Args, Clone)]
+pub struct ReplayArgs {
+    /// Newline-delimited tick JSON, e.g. captured with `tail --quiet --json > ticks.jsonl`
+    pub file: PathBuf,
+
+    /// Playback speed relative to the recorded timestamps
+    #[arg(long, default_value_t = 1.0)]
+    pub speed: f64,
+
+    /// Start this many seconds into the recording, skipping earlier ticks
+    #[arg(long, default_value_t = 0.0)]
+    pub seek: f64,
+
+    /// Accelerate gradually from 1x to `--speed` over the replayed span
+    #[arg(long)]
+    pub ramp: bool,
+
+    /// Suppress informational banners so stdout only carries ticks
+    #[arg(short, long)]
+    pub quiet: bool,
+}
+
+pub async fn run(args: ReplayArgs) -> Result<()> {
+    if !(args.speed.is_finite() && args.speed > 0.0) {
+        bail!("--speed must be a positive number");
+    }
+    if !(args.seek.is_finite() && args.seek >= 0.0) {
+        bail!("--seek must be a non-negative number of seconds");
+    }
+
+    let ticks = read_recording(&args.file)?;
+    let Some(first) = ticks.first() else {
+        bail!("recording {:?} contains no ticks", args.file);
+    };
+
+    let start_ms = first
+        .timestamp_ms
+        .saturating_add((args.seek * 1_000.0) as u64);
+    let ticks = &ticks[seek_index(&ticks, start_ms)..];
+    let span_ms = ticks
+        .iter()
+        .map(|tick| tick.timestamp_ms.saturating_sub(start_ms))
+        .max()
+        .unwrap_or_default();
+    let clock = ReplayClock::new(start_ms, args.speed, args.ramp.then_some(span_ms as f64));
+
+    print_status(
+        args.quiet,
+        &format!(
+            "Replaying {} ticks from {:?} at {}x{}...",
+            ticks.len(),
+            args.file,
+            args.speed,
+            if args.ramp { " (ramped)" } else { "" }
+        ),
+    );
+
+    let started = Instant::now();
+    for tick in ticks {
+        // ticks already due at high speeds go out without a timer round-trip
+        if !clock.should_emit(tick.timestamp_ms, started.elapsed()) {
+            time::sleep_until(started + clock.wall_delay(tick.timestamp_ms)).await;
+        }
+        println!("{}", serde_json::to_string(tick)?);
+    }
+    Ok(())
+}
+
+/// Read a newline-delimited tick recording, skipping malformed lines.
+pub(crate) fn read_recording(path: &Path) -> Result<Vec<Tick>> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read recording {path:?}"))?;
+    Ok(parse_recording(&raw))
+}
+
+/// Parse newline-delimited ticks, skipping blank, unparseable, or malformed
+/// lines and ticks older than their symbol's previous one.
+fn parse_recording(raw: &str) -> Vec<Tick> {
+    let now_ms = tick::now_ms();
+    let mut validator = TickValidator::default();
+    raw.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str::<Tick>(line) {
+            Ok(tick) if validator.check(&tick, now_ms).is_ok() => Some(tick),
+            Ok(_) | Err(_) => {
+                logging::warn(
+                    "replay.invalid_tick",
+                    "Skipping malformed recorded tick",
+                    json!({ "line": line }),
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+/// Index of the first tick at or after `start_ms`; ticks are assumed to be in recorded order.
+pub fn seek_index(ticks: &[Tick], start_ms: u64) -> usize {
+    ticks.partition_point(|tick| tick.timestamp_ms < start_ms)
+}
+
+/// Maps wall-clock time since playback began onto the recorded timeline.
+///
+/// Without a ramp the recording advances at a constant `speed`. With a ramp
+/// the instantaneous speed grows linearly with recorded progress, from 1x at
+/// the seek point to `speed` after `ramp_span_ms` of recording.
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayClock {
+    start_ms: u64,
+    speed: f64,
+    /// Growth rate of the speed per recorded millisecond when ramping.
+    ramp_rate: Option<f64>,
+}
+
+impl ReplayClock {
+    pub fn new(start_ms: u64, speed: f64, ramp_span_ms: Option<f64>) -> Self {
+        let ramp_rate = ramp_span_ms
+            .filter(|span| *span > 0.0)
+            .map(|span| (speed - 1.0) / span)
+            .filter(|rate| *rate != 0.0);
+        Self {
+            start_ms,
+            speed,
+            ramp_rate,
+        }
+    }
+
+    /// Recorded timestamp reached after `wall` of playback.
+    pub fn virtual_now(&self, wall: Duration) -> f64 {
+        let wall_ms = wall.as_secs_f64() * 1_000.0;
+        let elapsed = match self.ramp_rate {
+            // speed(v) = 1 + rate·v integrates to v(t) = (e^(rate·t) − 1) / rate
+            Some(rate) => (rate * wall_ms).exp_m1() / rate,
+            None => wall_ms * self.speed,
+        };
+        self.start_ms as f64 + elapsed
+    }
+
+    /// Whether a tick recorded at `timestamp_ms` is due after `wall` of playback.
+    pub(crate) fn should_emit(&self, timestamp_ms: u64, wall: Duration) -> bool {
+        timestamp_ms as f64 <= self.virtual_now(wall)
+    }
+
+    /// Wall-clock delay from the start of playback until `timestamp_ms` is due.
+    ///
+    /// A ramp that slows below 1x only covers its own span, so timestamps
+    /// should not lie more than `ramp_span_ms` past the start.
+    pub fn wall_delay(&self, timestamp_ms: u64) -> Duration {
+        let elapsed = timestamp_ms.saturating_sub(self.start_ms) as f64;
+        let wall_ms = match self.ramp_rate {
+            Some(rate) => (rate * elapsed).ln_1p() / rate,
+            None => elapsed / self.speed,
+        };
+        Duration::from_secs_f64(wall_ms.max(0.0) / 1_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Region, Sector};
+
+    fn tick(timestamp_ms: u64) -> Tick {
+        Tick {
+            symbol: "NATECH000".parse().unwrap(),
+            price: 100.0,
+            timestamp_ms,
+            region: Region::NorthAmerica,
+            sector: Sector::Technology,
+            prev_close: None,
+        }
+    }
+
+    #[test]
+    fn seek_skips_ticks_before_the_offset() {
+        let ticks: Vec<Tick> = [1_000, 1_500, 2_000, 2_000, 3_000]
+            .into_iter()
+            .map(tick)
+            .collect();
+        assert_eq!(seek_index(&ticks, 1_000), 0);
+        assert_eq!(seek_index(&ticks, 2_000), 2);
+        assert_eq!(seek_index(&ticks, 2_001), 4);
+        assert_eq!(seek_index(&ticks, 9_000), ticks.len());
+    }
+
+    #[test]
+    fn constant_speed_scales_recorded_time() {
+        let clock = ReplayClock::new(10_000, 4.0, None);
+        assert_eq!(clock.virtual_now(Duration::ZERO), 10_000.0);
+        assert_eq!(clock.virtual_now(Duration::from_secs(1)), 14_000.0);
+        assert!(clock.should_emit(14_000, Duration::from_secs(1)));
+        assert!(!clock.should_emit(14_001, Duration::from_secs(1)));
+        assert_eq!(clock.wall_delay(14_000), Duration::from_secs(1));
+        assert_eq!(clock.wall_delay(5_000), Duration::ZERO);
+    }
+
+    #[test]
+    fn virtual_clock_advances_monotonically() {
+        for clock in [
+            ReplayClock::new(0, 2.0, None),
+            ReplayClock::new(0, 10.0, Some(60_000.0)),
+            ReplayClock::new(0, 0.5, Some(60_000.0)),
+        ] {
+            let mut previous = clock.virtual_now(Duration::ZERO);
+            for step in 1..=500 {
+                let now = clock.virtual_now(Duration::from_millis(step * 100));
+                assert!(now > previous, "clock {clock:?} stalled at step {step}");
+                previous = now;
+            }
+        }
+    }
+
+    #[test]
+    fn ramp_accelerates_from_one_to_target_speed() {
+        let span = 60_000.0;
+        let clock = ReplayClock::new(0, 10.0, Some(span));
+        let speed_at = |wall_ms: u64| {
+            let now = clock.virtual_now(Duration::from_millis(wall_ms));
+            clock.virtual_now(Duration::from_millis(wall_ms + 1)) - now
+        };
+        assert!((speed_at(0) - 1.0).abs() < 0.01);
+
+        let end = clock.wall_delay(span as u64);
+        let end_speed = speed_at(end.as_millis() as u64);
+        assert!((end_speed - 10.0).abs() < 0.1, "end speed {end_speed}");
+        // ramping reaches the end sooner than 1x but later than the full target speed
+        assert!(end > Duration::from_secs(6) && end < Duration::from_secs(60));
+    }
+
+    #[test]
+    fn wall_delay_inverts_the_virtual_clock() {
+        let clock = ReplayClock::new(5_000, 3.0, Some(30_000.0));
+        for timestamp in [5_000, 6_000, 20_000, 35_000] {
+            let wall = clock.wall_delay(timestamp);
+            assert!((clock.virtual_now(wall) - timestamp as f64).abs() < 1.0);
+            assert!(clock.should_emit(timestamp, wall + Duration::from_millis(1)));
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::model::Symbol;
+use crate::tick::Tick;
+
+/// Last few ticks per symbol, replayed to new socket subscribers before the
+/// live stream so `tail` starts with recent history.
+pub(super) struct TickBacklog {
+    per_symbol: usize,
+    next_seq: u64,
+    ticks: HashMap<Symbol, VecDeque<(u64, Tick)>>,
+}
+
+impl TickBacklog {
+    pub(super) fn new(per_symbol: usize) -> Self {
+        Self {
+            per_symbol,
+            next_seq: 0,
+            ticks: HashMap::new(),
+        }
+    }
+
+    pub(super) fn record(&mut self, tick: Tick) {
+        if self.per_symbol == 0 {
+            return;
+        }
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let ring = self.ticks.entry(tick.symbol.clone()).or_default();
+        if ring.len() == self.per_symbol {
+            ring.pop_front();
+        }
+        ring.push_back((seq, tick));
+    }
+
+    /// Retained ticks across every symbol, in the order they were recorded.
+    pub(super) fn replay(&self) -> Vec<Tick> {
+        let mut retained: Vec<&(u64, Tick)> = self.ticks.values().flatten().collect();
+        retained.sort_unstable_by_key(|(seq, _)| *seq);
+        retained.into_iter().map(|(_, tick)| tick.clone()).collect()
+    }
+}
+
+/// Ticks drained into the backlog after a client subscribed. The client's
+/// receiver saw some of them too, so its live stream opens with ticks the
+/// replay already sent.
+pub(super) struct ReplayOverlap {
+    ticks: VecDeque<Tick>,
+}
+
+impl ReplayOverlap {
+    pub(super) fn new(drained: Vec<Tick>) -> Self {
+        Self {
+            ticks: drained.into(),
+        }
+    }
+
+    /// Whether the replay already sent this live tick. The live stream joins
+    /// the drained ticks somewhere and then follows them one for one, so the
+    /// first tick that was not drained ends the overlap.
+    pub(super) fn covers(&mut self, tick: &Tick) -> bool {
+        match self.ticks.iter().position(|drained| drained == tick) {
+            Some(idx) => {
+                self.ticks.drain(..=idx);
+                true
+            }
+            None => {
+                self.ticks.clear();
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Region, Sector};
+
+    fn tick(symbol: &str, timestamp_ms: u64) -> Tick {
+        Tick {
+            symbol: symbol.parse().unwrap(),
+            price: 100.0,
+            timestamp_ms,
+            region: Region::Europe,
+            sector: Sector::Energy,
+            prev_close: None,
+        }
+    }
+
+    #[test]
+    fn keeps_the_last_ticks_per_symbol_in_arrival_order() {
+        let mut backlog = TickBacklog::new(2);
+        for (symbol, ts) in [("AAA", 1), ("BBB", 2), ("AAA", 3), ("AAA", 4), ("BBB", 5)] {
+            backlog.record(tick(symbol, ts));
+        }
+
+        let replay: Vec<(String, u64)> = backlog
+            .replay()
+            .into_iter()
+            .map(|tick| (tick.symbol.as_str().to_string(), tick.timestamp_ms))
+            .collect();
+        assert_eq!(
+            replay,
+            vec![
+                ("BBB".to_string(), 2),
+                ("AAA".to_string(), 3),
+                ("AAA".to_string(), 4),
+                ("BBB".to_string(), 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn zero_capacity_keeps_nothing() {
+        let mut backlog = TickBacklog::new(0);
+        backlog.record(tick("AAA", 1));
+        assert!(backlog.replay().is_empty());
+    }
+
+    #[test]
+    fn overlap_skips_live_ticks_up_to_the_end_of_the_drain() {
+        let drained: Vec<Tick> = (1..=4).map(|ts| tick("AAA", ts)).collect();
+        let mut overlap = ReplayOverlap::new(drained);
+
+        // subscribed after the first two drained ticks were sent
+        assert!(overlap.covers(&tick("AAA", 3)));
+        assert!(overlap.covers(&tick("AAA", 4)));
+        assert!(!overlap.covers(&tick("AAA", 5)));
+        assert!(!overlap.covers(&tick("AAA", 3)));
+    }
+
+    #[test]
+    fn overlap_is_empty_when_the_drain_preceded_every_live_tick() {
+        let mut overlap = ReplayOverlap::new(vec![tick("AAA", 1)]);
+        assert!(!overlap.covers(&tick("AAA", 2)));
+        assert!(!overlap.covers(&tick("AAA", 1)));
+    }
+}
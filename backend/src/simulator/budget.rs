@@ -0,0 +1,128 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Whether generator iterations averaging `avg_iteration` overrun `interval`,
+/// so the ticker skips beats instead of keeping its cadence.
+pub(super) fn over_budget(avg_iteration: Duration, interval: Duration) -> bool {
+    avg_iteration > interval
+}
+
+/// Rolling average of generator iteration wall time over a fixed window.
+pub(super) struct ComputeBudget {
+    interval: Duration,
+    window: usize,
+    samples: VecDeque<Duration>,
+    total: Duration,
+    warned: bool,
+}
+
+impl ComputeBudget {
+    /// A `window` of 0 disables the check.
+    pub(super) fn new(interval: Duration, window: usize) -> Self {
+        Self {
+            interval,
+            window,
+            samples: VecDeque::with_capacity(window),
+            total: Duration::ZERO,
+            warned: false,
+        }
+    }
+
+    /// Measure later iterations against `interval`, as when a tick ramp
+    /// reschedules the ticker.
+    pub(super) fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Record one iteration, returning the rolling average the first time a
+    /// full window runs over budget. Warns again only after the average has
+    /// dropped back under the interval.
+    pub(super) fn record(&mut self, elapsed: Duration) -> Option<Duration> {
+        if self.window == 0 {
+            return None;
+        }
+        if self.samples.len() == self.window {
+            if let Some(oldest) = self.samples.pop_front() {
+                self.total = self.total.saturating_sub(oldest);
+            }
+        }
+        self.samples.push_back(elapsed);
+        self.total = self.total.saturating_add(elapsed);
+        if self.samples.len() < self.window {
+            return None;
+        }
+
+        let average = self.total / self.window as u32;
+        match (over_budget(average, self.interval), self.warned) {
+            (true, false) => {
+                self.warned = true;
+                Some(average)
+            }
+            (false, true) => {
+                self.warned = false;
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_millis(8);
+
+    #[test]
+    fn over_budget_compares_against_the_interval() {
+        assert!(!over_budget(Duration::from_millis(5), INTERVAL));
+        assert!(!over_budget(INTERVAL, INTERVAL));
+        assert!(over_budget(Duration::from_millis(9), INTERVAL));
+    }
+
+    #[test]
+    fn warns_once_per_sustained_overrun() {
+        let mut budget = ComputeBudget::new(INTERVAL, 4);
+        let fast = Duration::from_millis(2);
+        let slow = Duration::from_millis(20);
+
+        // a single slow iteration does not move the average past the budget
+        for elapsed in [fast, fast, fast, slow] {
+            assert_eq!(budget.record(elapsed), None);
+        }
+
+        let warnings: Vec<_> = [slow, slow, slow, slow, slow]
+            .into_iter()
+            .filter_map(|elapsed| budget.record(elapsed))
+            .collect();
+        assert_eq!(warnings, vec![Duration::from_millis(11)]);
+
+        // recovering re-arms the warning
+        for _ in 0..4 {
+            assert_eq!(budget.record(fast), None);
+        }
+        let rearmed = [slow, slow, slow, slow]
+            .into_iter()
+            .filter_map(|elapsed| budget.record(elapsed))
+            .count();
+        assert_eq!(rearmed, 1);
+    }
+
+    #[test]
+    fn a_longer_interval_absorbs_the_same_iterations() {
+        let mut budget = ComputeBudget::new(INTERVAL, 2);
+        budget.set_interval(INTERVAL * 4);
+        let slow = Duration::from_millis(20);
+        assert_eq!(budget.record(slow), None);
+        assert_eq!(budget.record(slow), None);
+
+        budget.set_interval(INTERVAL);
+        assert_eq!(budget.record(slow), Some(slow));
+    }
+
+    #[test]
+    fn zero_window_disables_the_check() {
+        let mut budget = ComputeBudget::new(INTERVAL, 0);
+        assert_eq!(budget.record(Duration::from_secs(1)), None);
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+
+use crate::model::{Equity, Region, Symbol};
+use crate::tick::Tick;
+
+pub(super) const MINUTES_PER_DAY: u64 = 24 * 60;
+
+/// Minute of the UTC day (`0..1440`) that a millisecond timestamp falls in.
+pub(super) fn minute_of_day(timestamp_ms: u64) -> u32 {
+    ((timestamp_ms / 60_000) % MINUTES_PER_DAY) as u32
+}
+
+/// Whether `region` trades at `minute` of the day.
+///
+/// Sessions are `(open, close)` minutes of the UTC day with the close exclusive;
+/// an open after the close wraps past midnight. Regions without a session always trade.
+pub(super) fn is_open(sessions: &HashMap<Region, (u32, u32)>, region: Region, minute: u32) -> bool {
+    match sessions.get(&region) {
+        None => true,
+        Some(&(open, close)) if open <= close => (open..close).contains(&minute),
+        Some(&(open, close)) => minute >= open || minute < close,
+    }
+}
+
+/// Reject sessions whose minutes fall outside the day or that open and close
+/// at the same minute, which would be ambiguous between never and always open.
+pub(super) fn validate_sessions(sessions: &HashMap<Region, (u32, u32)>) -> Result<()> {
+    for (region, &(open, close)) in sessions {
+        if u64::from(open) >= MINUTES_PER_DAY || u64::from(close) >= MINUTES_PER_DAY {
+            bail!(
+                "region_sessions for {region}: minutes must be below {MINUTES_PER_DAY}, got ({open}, {close})"
+            );
+        }
+        if open == close {
+            bail!(
+                "region_sessions for {region}: open and close are both {open}; omit the region to trade around the clock"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Fill `mask` with per-equity open flags at `timestamp_ms`, in universe order,
+/// reusing its allocation across ticks.
+///
+/// The `market_index` follows the global factor rather than its tagged region,
+/// so it is always open.
+pub(super) fn open_mask(
+    mask: &mut Vec<bool>,
+    sessions: &HashMap<Region, (u32, u32)>,
+    equities: &[Equity],
+    market_index: Option<&Symbol>,
+    timestamp_ms: u64,
+) {
+    let minute = minute_of_day(timestamp_ms);
+    mask.clear();
+    mask.extend(equities.iter().map(|equity| {
+        market_index == Some(&equity.symbol) || is_open(sessions, equity.region, minute)
+    }));
+}
+
+/// Previous session close per symbol, captured when its session opens.
+///
+/// Without calendars every symbol trades in one endless session, so the close
+/// is the price it had before its first generated tick.
+#[derive(Default)]
+pub(super) struct PrevCloses {
+    closes: HashMap<Symbol, SessionClose>,
+}
+
+struct SessionClose {
+    price: f64,
+    open: bool,
+}
+
+impl PrevCloses {
+    /// Track session state from the prices held before this iteration's update;
+    /// `open` is the per-equity mask, or `None` when no calendars are configured.
+    pub(super) fn observe(&mut self, equities: &[Equity], prices: &[f64], open: Option<&[bool]>) {
+        for (idx, (equity, &price)) in equities.iter().zip(prices).enumerate() {
+            let open = match open {
+                Some(mask) => mask[idx],
+                None => true,
+            };
+            match self.closes.get_mut(&equity.symbol) {
+                Some(close) => {
+                    // closed regions hold their price, so it is the last session's close
+                    if open && !close.open {
+                        close.price = price;
+                    }
+                    close.open = open;
+                }
+                None => {
+                    self.closes
+                        .insert(equity.symbol.clone(), SessionClose { price, open });
+                }
+            }
+        }
+    }
+
+    pub(super) fn stamp(&self, ticks: &mut [Tick]) {
+        for tick in ticks {
+            tick.prev_close = self.closes.get(&tick.symbol).map(|close| close.price);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{default_equities, Sector};
+
+    const HOUR_MS: u64 = 60 * 60_000;
+
+    fn disjoint_sessions() -> HashMap<Region, (u32, u32)> {
+        HashMap::from([
+            (Region::AsiaPacific, (0, 4 * 60)),
+            (Region::MiddleEastAfrica, (4 * 60, 8 * 60)),
+            (Region::Europe, (8 * 60, 14 * 60)),
+            (Region::NorthAmerica, (14 * 60, 20 * 60)),
+            // closing at midnight wraps to minute 0
+            (Region::SouthAmerica, (20 * 60, 0)),
+        ])
+    }
+
+    #[test]
+    fn sessions_include_open_and_exclude_close() {
+        let sessions = disjoint_sessions();
+        assert!(is_open(&sessions, Region::Europe, 8 * 60));
+        assert!(!is_open(&sessions, Region::Europe, 14 * 60));
+        assert!(is_open(&sessions, Region::NorthAmerica, 14 * 60));
+    }
+
+    #[test]
+    fn sessions_can_wrap_past_midnight() {
+        let sessions = HashMap::from([(Region::AsiaPacific, (22 * 60, 6 * 60))]);
+        assert!(is_open(&sessions, Region::AsiaPacific, 23 * 60));
+        assert!(is_open(&sessions, Region::AsiaPacific, 60));
+        assert!(!is_open(&sessions, Region::AsiaPacific, 12 * 60));
+        assert!(is_open(&sessions, Region::Europe, 12 * 60));
+    }
+
+    #[test]
+    fn only_the_open_region_trades_at_a_given_time() {
+        let sessions = disjoint_sessions();
+        let equities = default_equities();
+        // 10:30 UTC on some later day
+        let timestamp_ms = 20_000 * 24 * HOUR_MS + 10 * HOUR_MS + 30 * 60_000;
+
+        let mut mask = Vec::new();
+        open_mask(&mut mask, &sessions, &equities, None, timestamp_ms);
+        for (equity, &open) in equities.iter().zip(&mask) {
+            assert_eq!(open, equity.region == Region::Europe, "{}", equity.symbol);
+        }
+    }
+
+    #[test]
+    fn the_market_index_ignores_its_region_session() {
+        let sessions = disjoint_sessions();
+        let index = Symbol::new("MKT").unwrap();
+        let equities = [Equity {
+            symbol: index.clone(),
+            region: Region::NorthAmerica,
+            sector: Sector::Financials,
+        }];
+        let timestamp_ms = 20_000 * 24 * HOUR_MS + 10 * HOUR_MS + 30 * 60_000;
+
+        let mut mask = Vec::new();
+        open_mask(&mut mask, &sessions, &equities, None, timestamp_ms);
+        assert_eq!(mask, [false]);
+        open_mask(&mut mask, &sessions, &equities, Some(&index), timestamp_ms);
+        assert_eq!(mask, [true]);
+    }
+
+    #[test]
+    fn sessions_must_lie_within_the_day_and_not_be_empty() {
+        assert!(validate_sessions(&disjoint_sessions()).is_ok());
+        let wrapping = HashMap::from([(Region::AsiaPacific, (22 * 60, 6 * 60))]);
+        assert!(validate_sessions(&wrapping).is_ok());
+        for session in [(0, 1440), (1440, 60), (600, 600)] {
+            let sessions = HashMap::from([(Region::Europe, session)]);
+            assert!(validate_sessions(&sessions).is_err(), "{session:?}");
+        }
+    }
+
+    #[test]
+    fn prev_close_is_stable_within_a_session_and_rolls_at_the_next_open() {
+        let equities = [Equity {
+            symbol: Symbol::new("EUENG001").unwrap(),
+            region: Region::Europe,
+            sector: Sector::Energy,
+        }];
+        let stamped = |closes: &PrevCloses| {
+            let mut ticks = [Tick {
+                symbol: equities[0].symbol.clone(),
+                price: 1.0,
+                timestamp_ms: 1,
+                region: Region::Europe,
+                sector: Sector::Energy,
+                prev_close: None,
+            }];
+            closes.stamp(&mut ticks);
+            ticks[0].prev_close
+        };
+        let mut closes = PrevCloses::default();
+
+        // first session opens at 100 and trades around
+        for price in [100.0, 101.5, 99.0, 102.0] {
+            closes.observe(&equities, &[price], Some(&[true]));
+            assert_eq!(stamped(&closes), Some(100.0));
+        }
+
+        // the market closes holding 102, then reopens for a new session
+        closes.observe(&equities, &[102.0], Some(&[false]));
+        assert_eq!(stamped(&closes), Some(100.0));
+        for price in [102.0, 104.0, 103.0] {
+            closes.observe(&equities, &[price], Some(&[true]));
+            assert_eq!(stamped(&closes), Some(102.0));
+        }
+    }
+
+    #[test]
+    fn prev_close_without_calendars_is_the_first_price() {
+        let equities = default_equities();
+        let mut closes = PrevCloses::default();
+        let first = vec![50.0; equities.len()];
+        closes.observe(&equities, &first, None);
+        closes.observe(&equities, &vec![55.0; equities.len()], None);
+
+        let mut ticks = [Tick {
+            symbol: equities[3].symbol.clone(),
+            price: 55.0,
+            timestamp_ms: 1,
+            region: equities[3].region,
+            sector: equities[3].sector,
+            prev_close: None,
+        }];
+        closes.stamp(&mut ticks);
+        assert_eq!(ticks[0].prev_close, Some(50.0));
+    }
+}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Fault injection for the `/ws` gateway, to exercise client reconnect and
+/// backoff paths without killing the server. Only built with the `chaos`
+/// feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChaosConfig {
+    /// Close every connection with a "service restart" code this long after
+    /// it opens; clients that reconnect are dropped again on the same cadence.
+    #[serde(with = "humantime_serde")]
+    pub drop_every: Option<Duration>,
+    /// Hold every websocket handshake this long before completing it.
+    #[serde(with = "humantime_serde")]
+    pub delay: Option<Duration>,
+}
@@ -0,0 +1,193 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use nalgebra::DMatrix;
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::{broadcast, watch, RwLock};
+
+use crate::logging;
+use crate::model::Symbol;
+use crate::tick::Tick;
+
+use super::gateway::{GatewayBatch, LatestSnapshot};
+use super::universe::StockUniverse;
+use super::{current_timestamp_ms, ShutdownSignal, SimulatorConfig};
+
+/// Point-in-time view of a running simulator, written on SIGUSR2.
+#[derive(Debug, Serialize)]
+pub(super) struct StateDump {
+    written_at_ms: u64,
+    uptime_secs: f64,
+    subscribers: SubscriberCounts,
+    correlation: CorrelationSummary,
+    /// Latest price per symbol as of the most recent gateway batch.
+    prices: Vec<PricePoint>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(super) struct SubscriberCounts {
+    pub(super) source: usize,
+    pub(super) gateway: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct CorrelationSummary {
+    size: usize,
+    mean_off_diagonal: Option<f64>,
+    min_off_diagonal: Option<f64>,
+    max_off_diagonal: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct PricePoint {
+    symbol: Symbol,
+    price: f64,
+    timestamp_ms: u64,
+}
+
+impl CorrelationSummary {
+    fn new(correlation: &DMatrix<f64>) -> Self {
+        let off_diagonal: Vec<f64> = (0..correlation.nrows())
+            .flat_map(|row| (0..correlation.ncols()).map(move |col| (row, col)))
+            .filter(|(row, col)| row != col)
+            .map(|index| correlation[index])
+            .collect();
+        let count = off_diagonal.len();
+        Self {
+            size: correlation.nrows(),
+            mean_off_diagonal: (count > 0).then(|| off_diagonal.iter().sum::<f64>() / count as f64),
+            min_off_diagonal: off_diagonal.iter().copied().reduce(f64::min),
+            max_off_diagonal: off_diagonal.iter().copied().reduce(f64::max),
+        }
+    }
+}
+
+pub(super) fn build_state_dump(
+    prices: &[Tick],
+    correlation: &DMatrix<f64>,
+    subscribers: SubscriberCounts,
+    uptime: Duration,
+    now_ms: u64,
+) -> StateDump {
+    StateDump {
+        written_at_ms: now_ms,
+        uptime_secs: uptime.as_secs_f64(),
+        subscribers,
+        correlation: CorrelationSummary::new(correlation),
+        prices: prices
+            .iter()
+            .map(|tick| PricePoint {
+                symbol: tick.symbol.clone(),
+                price: tick.price,
+                timestamp_ms: tick.timestamp_ms,
+            })
+            .collect(),
+    }
+}
+
+/// Write `dump` as `state-<written_at_ms>.json` inside `dir`, returning the path.
+pub(super) fn write_state_dump(dir: &Path, dump: &StateDump) -> Result<PathBuf> {
+    fs::create_dir_all(dir).with_context(|| format!("failed to create dump directory {dir:?}"))?;
+    let path = dir.join(format!("state-{}.json", dump.written_at_ms));
+    let body = serde_json::to_string_pretty(dump).context("serialize state dump")?;
+    fs::write(&path, body).with_context(|| format!("failed to write state dump {path:?}"))?;
+    Ok(path)
+}
+
+/// Write a state dump whenever one is requested, without touching the tick stream.
+pub(super) async fn run_state_dumps(
+    config: Arc<SimulatorConfig>,
+    universe: Arc<RwLock<StockUniverse>>,
+    snapshot: LatestSnapshot,
+    source: broadcast::Sender<Tick>,
+    gateway: broadcast::Sender<GatewayBatch>,
+    mut requests: broadcast::Receiver<()>,
+    mut shutdown: watch::Receiver<ShutdownSignal>,
+) -> Result<()> {
+    let started = Instant::now();
+    loop {
+        tokio::select! {
+            request = requests.recv() => {
+                if matches!(request, Err(broadcast::error::RecvError::Closed)) {
+                    break;
+                }
+                let dump = build_state_dump(
+                    &snapshot.ticks(),
+                    universe.read().await.correlation_matrix(),
+                    SubscriberCounts {
+                        source: source.receiver_count(),
+                        gateway: gateway.receiver_count(),
+                    },
+                    started.elapsed(),
+                    current_timestamp_ms(),
+                );
+                match write_state_dump(&config.dump_dir, &dump) {
+                    Ok(path) => logging::info(
+                        "state_dump.written",
+                        "State snapshot written",
+                        json!({ "path": path.display().to_string() }),
+                    ),
+                    Err(err) => logging::error(
+                        "state_dump.failed",
+                        "Failed to write state snapshot",
+                        json!({ "error": format!("{err:#}") }),
+                    ),
+                }
+            }
+            changed = shutdown.changed() => {
+                if changed.is_err() || !matches!(*shutdown.borrow(), ShutdownSignal::None) {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Region, Sector};
+
+    #[test]
+    fn dump_is_written_as_valid_json() {
+        let prices = vec![Tick {
+            symbol: "EUENRG000".parse().unwrap(),
+            price: 101.5,
+            timestamp_ms: 1_716_400_000_000,
+            region: Region::Europe,
+            sector: Sector::Energy,
+            prev_close: None,
+        }];
+        let correlation =
+            DMatrix::from_row_slice(3, 3, &[1.0, 0.2, 0.4, 0.2, 1.0, 0.6, 0.4, 0.6, 1.0]);
+        let dump = build_state_dump(
+            &prices,
+            &correlation,
+            SubscriberCounts {
+                source: 2,
+                gateway: 1,
+            },
+            Duration::from_secs(90),
+            1_716_400_000_500,
+        );
+
+        let dir = std::env::temp_dir().join(format!("rmd-dump-{}", std::process::id()));
+        let path = write_state_dump(&dir, &dump).expect("write dump");
+        assert_eq!(path.file_name().unwrap(), "state-1716400000500.json");
+
+        let raw = fs::read_to_string(&path).expect("read dump");
+        let _ = fs::remove_dir_all(&dir);
+        let value: serde_json::Value = serde_json::from_str(&raw).expect("valid json");
+        assert_eq!(value["uptime_secs"], 90.0);
+        assert_eq!(value["subscribers"]["source"], 2);
+        assert_eq!(value["correlation"]["size"], 3);
+        assert_eq!(value["correlation"]["min_off_diagonal"], 0.2);
+        assert_eq!(value["correlation"]["max_off_diagonal"], 0.6);
+        assert_eq!(value["prices"][0]["symbol"], "EUENRG000");
+    }
+}
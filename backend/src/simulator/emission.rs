@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::PRICE_CHANGE_EPSILON;
+use crate::model::Symbol;
+use crate::tick::Tick;
+
+/// Order in which one batch of generated ticks is sent to the broadcast.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmissionOrder {
+    /// Universe order (region-major, sector-minor for the default universe).
+    #[default]
+    Natural,
+    /// A fresh permutation every batch, drawn from a stream seeded once.
+    Shuffled(u64),
+    /// Consecutive runs per region, keeping universe order within each region.
+    ByRegion,
+}
+
+/// Applies an [`EmissionOrder`] batch after batch.
+pub(super) struct Emitter {
+    order: EmissionOrder,
+    rng: Option<StdRng>,
+}
+
+impl Emitter {
+    pub(super) fn new(order: EmissionOrder) -> Self {
+        let rng = match order {
+            EmissionOrder::Shuffled(seed) => Some(StdRng::seed_from_u64(seed)),
+            EmissionOrder::Natural | EmissionOrder::ByRegion => None,
+        };
+        Self { order, rng }
+    }
+
+    pub(super) fn arrange(&mut self, ticks: &mut [Tick]) {
+        match self.order {
+            EmissionOrder::Natural => {}
+            EmissionOrder::Shuffled(_) => {
+                if let Some(rng) = &mut self.rng {
+                    ticks.shuffle(rng);
+                }
+            }
+            EmissionOrder::ByRegion => ticks.sort_by_key(|tick| tick.region.index()),
+        }
+    }
+}
+
+/// Drops ticks whose price has not moved since that symbol was last emitted.
+#[derive(Default)]
+pub(super) struct ChangeFilter {
+    last_emitted: HashMap<Symbol, f64>,
+}
+
+impl ChangeFilter {
+    pub(super) fn retain_changed(&mut self, ticks: &mut Vec<Tick>) {
+        ticks.retain(|tick| {
+            let changed = match self.last_emitted.get(&tick.symbol) {
+                Some(last) => (tick.price - last).abs() > PRICE_CHANGE_EPSILON,
+                None => true,
+            };
+            if changed {
+                self.last_emitted.insert(tick.symbol.clone(), tick.price);
+            }
+            changed
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Region, Sector};
+
+    fn batch() -> Vec<Tick> {
+        let regions = [
+            Region::Europe,
+            Region::NorthAmerica,
+            Region::Europe,
+            Region::AsiaPacific,
+            Region::NorthAmerica,
+            Region::Europe,
+        ];
+        regions
+            .into_iter()
+            .enumerate()
+            .map(|(idx, region)| Tick {
+                symbol: format!("S{idx}").parse().unwrap(),
+                price: 100.0,
+                timestamp_ms: idx as u64,
+                region,
+                sector: Sector::Technology,
+                prev_close: None,
+            })
+            .collect()
+    }
+
+    fn symbols(ticks: &[Tick]) -> Vec<&str> {
+        ticks.iter().map(|tick| tick.symbol.as_str()).collect()
+    }
+
+    #[test]
+    fn by_region_groups_consecutive_ticks() {
+        let mut ticks = batch();
+        Emitter::new(EmissionOrder::ByRegion).arrange(&mut ticks);
+
+        let mut seen = Vec::new();
+        for tick in &ticks {
+            if seen.last() != Some(&tick.region) {
+                assert!(!seen.contains(&tick.region), "{:?} split", tick.region);
+                seen.push(tick.region);
+            }
+        }
+        assert_eq!(seen.len(), 3);
+        // universe order is kept within a region
+        let europe: Vec<&str> = ticks
+            .iter()
+            .filter(|tick| tick.region == Region::Europe)
+            .map(|tick| tick.symbol.as_str())
+            .collect();
+        assert_eq!(europe, ["S0", "S2", "S5"]);
+    }
+
+    fn step(filter: &mut ChangeFilter, prices: &[f64]) -> usize {
+        let mut ticks: Vec<Tick> = batch().into_iter().take(prices.len()).collect();
+        for (tick, &price) in ticks.iter_mut().zip(prices) {
+            tick.price = price;
+        }
+        filter.retain_changed(&mut ticks);
+        ticks.len()
+    }
+
+    #[test]
+    fn unchanged_prices_are_emitted_once() {
+        let mut filter = ChangeFilter::default();
+        assert_eq!(step(&mut filter, &[100.0, 50.0]), 2);
+        for _ in 0..5 {
+            assert_eq!(step(&mut filter, &[100.0, 50.0]), 0);
+        }
+        assert_eq!(
+            step(&mut filter, &[100.0, 50.0 + PRICE_CHANGE_EPSILON / 2.0]),
+            0
+        );
+    }
+
+    #[test]
+    fn moving_prices_keep_flowing() {
+        let mut filter = ChangeFilter::default();
+        for step_idx in 0..5 {
+            let drift = step_idx as f64 * 0.01;
+            assert_eq!(step(&mut filter, &[100.0 + drift, 50.0 - drift]), 2);
+        }
+        // only the symbol that moved is emitted
+        assert_eq!(step(&mut filter, &[100.04, 50.0]), 1);
+    }
+
+    #[test]
+    fn natural_keeps_order_and_shuffle_is_seeded() {
+        let mut natural = batch();
+        Emitter::new(EmissionOrder::Natural).arrange(&mut natural);
+        assert_eq!(symbols(&natural), symbols(&batch()));
+
+        let shuffled = |seed| {
+            let mut emitter = Emitter::new(EmissionOrder::Shuffled(seed));
+            let mut ticks = batch();
+            emitter.arrange(&mut ticks);
+            symbols(&ticks).join(",")
+        };
+        assert_eq!(shuffled(9), shuffled(9));
+        let mut all = batch();
+        Emitter::new(EmissionOrder::Shuffled(9)).arrange(&mut all);
+        let mut sorted = symbols(&all);
+        sorted.sort_unstable();
+        assert_eq!(sorted, symbols(&batch()));
+    }
+}
@@ -1,71 +1,675 @@
-use std::collections::hash_map::HashMap;
-use std::net::SocketAddr;
-use std::time::{Duration, Instant};
+use std::collections::hash_map::{Entry, HashMap};
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use axum::{
-    extract::ws::{Message, WebSocket, WebSocketUpgrade},
-    response::Response,
+    extract::{
+        rejection::QueryRejection,
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        ConnectInfo, Query,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
     routing::get,
-    Router,
+    Json, Router,
 };
-use futures_util::{SinkExt, StreamExt};
-use serde::Serialize;
+use futures_util::{stream::SplitStream, Sink, SinkExt, StreamExt};
+use market_core::{
+    BatchMessage, DebugMessage, GatewayMessage, HeartbeatMessage, MetaMessage, TradesMessage,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+use socket2::{Domain, Protocol, Socket, Type};
 use tokio::net::TcpListener;
 use tokio::sync::{broadcast, mpsc, watch};
-use tokio::time::{interval, MissedTickBehavior};
+use tokio::time::{interval, interval_at, Interval, MissedTickBehavior};
+use tokio_tungstenite::tungstenite::error::{CapacityError, Error as WsError};
 
-use crate::{constants::TICK_BATCH_VERSION, logging, tick::Tick};
+use crate::{
+    constants::{GATEWAY_DEBUG_INTERVAL_MS, TICK_BATCH_VERSION},
+    logging,
+    model::{Equity, Region, Sector, Symbol},
+    tick::{CompactTick, Tick},
+};
 
 use super::{
-    metrics::{MetricsEvent, MetricsTx},
-    ShutdownSignal,
+    calendar, current_timestamp_ms,
+    heatmap::{clustered_heatmap, CorrelationHeatmap},
+    metrics::{GatewayStats, Health, MetricsEvent, MetricsTx},
+    subscription::{Subscription, SymbolOverflow, TooManySymbols},
+    trades::TradeBatch,
+    universe::StockUniverse,
+    Regime, ShutdownSignal, SimulatorConfig,
 };
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn debug_flag_requires_explicit_opt_in() {
+        let params = |debug: Option<&str>| ClientParams {
+            debug: debug.map(str::to_string),
+            symbols: None,
+        };
+        assert!(params(Some("1")).debug());
+        assert!(params(Some("true")).debug());
+        assert!(!params(Some("0")).debug());
+        assert!(!params(None).debug());
+
+        let frame = serde_json::to_value(GatewayMessage::<Tick>::Debug(DebugMessage::from(
+            GatewayStats {
+                batch_count: 4,
+                avg_symbols: 2.5,
+                health: Health::Degraded,
+            },
+        )))
+        .unwrap();
+        assert_eq!(
+            frame,
+            json!({ "type": "debug", "batch_count": 4, "avg_symbols": 2.5 })
+        );
+    }
+
+    #[test]
+    fn readiness_fails_only_when_unhealthy() {
+        let (status, Json(body)) = readiness(Health::Healthy);
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!({ "status": "healthy" }));
+
+        let (status, Json(body)) = readiness(Health::Degraded);
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "degraded");
+        assert!(body["warning"].is_string());
+
+        let (status, Json(body)) = readiness(Health::Unhealthy);
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "unhealthy");
+    }
+
+    #[test]
+    fn snapshot_filters_accept_wire_names_and_prefixes() {
+        let params = |region: &str, sector: &str| SnapshotParams {
+            region: Some(region.to_string()),
+            sector: Some(sector.to_string()),
+        };
+        let parsed = params("north_america", "REA");
+        assert_eq!(parsed.region().unwrap(), Some(Region::NorthAmerica));
+        assert_eq!(parsed.sector().unwrap(), Some(Sector::RealEstate));
+        assert_eq!(params("eu", "x").region().unwrap(), Some(Region::Europe));
+        assert_eq!(SnapshotParams::default().region().unwrap(), None);
+
+        let err = params("mars", "x").region().unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert!(err.message.contains("\"mars\""), "{}", err.message);
+        assert!(err.message.contains("north_america"), "{}", err.message);
+        assert!(params("eu", "x").sector().is_err());
+    }
+
+    #[test]
+    fn heartbeat_counts_open_regions_only_with_calendars() {
+        let (regime_tx, regime) = watch::channel(Regime::Calm);
+        let heartbeat = |region_sessions| Heartbeat {
+            interval: Duration::from_secs(1),
+            started: Instant::now(),
+            region_sessions: Arc::new(region_sessions),
+            regime: regime.clone(),
+        };
+        // 09:00 UTC
+        let timestamp_ms = 9 * 60 * 60_000;
+
+        let frame = serde_json::to_value(GatewayMessage::<Tick>::Heartbeat(
+            heartbeat(HashMap::new()).payload(timestamp_ms),
+        ))
+        .unwrap();
+        assert_eq!(frame["type"], "heartbeat");
+        assert_eq!(frame["version"], TICK_BATCH_VERSION);
+        assert_eq!(frame["regime"], "calm");
+        assert!(frame["open_regions"].is_null());
+        assert!(frame["uptime_secs"].is_f64());
+
+        let sessions = HashMap::from([
+            (Region::Europe, (8 * 60, 16 * 60)),
+            (Region::NorthAmerica, (14 * 60, 21 * 60)),
+        ]);
+        let heartbeat = heartbeat(sessions);
+        let payload = heartbeat.payload(timestamp_ms);
+        // regions without a session always trade
+        assert_eq!(payload.open_regions, Some(4));
+
+        regime_tx.send_replace(Regime::Stressed);
+        assert_eq!(heartbeat.payload(timestamp_ms).regime, Regime::Stressed);
+    }
+
+    #[test]
+    fn bind_backoff_doubles_within_jitter_and_caps() {
+        use rand::SeedableRng;
+
+        let retry = BindRetry {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1_000),
+        };
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        for (attempt, ceiling_ms) in [
+            (1, 100),
+            (2, 200),
+            (3, 400),
+            (4, 800),
+            (5, 1_000),
+            (40, 1_000),
+        ] {
+            let ceiling = Duration::from_millis(ceiling_ms);
+            for _ in 0..50 {
+                let delay = bind_backoff(&retry, attempt, &mut rng);
+                assert!(
+                    delay >= ceiling / 2 && delay <= ceiling,
+                    "attempt {attempt}: {delay:?} outside {ceiling:?}"
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn bind_gives_up_after_the_last_attempt() {
+        let occupant = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = occupant.local_addr().unwrap();
+        let retry = BindRetry {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+        };
+        let err = bind_with_retry(addr, retry).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AddrInUse);
+    }
+
+    #[test]
+    fn next_boundary_rounds_up_to_period_multiples() {
+        let second = Duration::from_secs(1);
+        assert_eq!(
+            until_next_boundary(Duration::from_millis(12_250), second),
+            Duration::from_millis(750)
+        );
+        assert_eq!(
+            until_next_boundary(Duration::from_secs(12), second),
+            Duration::ZERO
+        );
+        assert_eq!(
+            until_next_boundary(Duration::from_millis(1_030), Duration::from_millis(250)),
+            Duration::from_millis(220)
+        );
+        assert_eq!(
+            until_next_boundary(Duration::from_millis(5), Duration::ZERO),
+            Duration::ZERO
+        );
+    }
+
     #[test]
     fn accumulator_snapshot_sorts_symbols() {
         let mut accumulator = BatchAccumulator::default();
         accumulator.ingest(Tick {
-            symbol: "B".into(),
+            symbol: "B".parse().unwrap(),
             price: 1.0,
             timestamp_ms: 1,
             region: crate::model::Region::Europe,
             sector: crate::model::Sector::Technology,
+            prev_close: None,
         });
         accumulator.ingest(Tick {
-            symbol: "A".into(),
+            symbol: "A".parse().unwrap(),
             price: 1.0,
             timestamp_ms: 2,
             region: crate::model::Region::Europe,
             sector: crate::model::Sector::Technology,
+            prev_close: None,
         });
 
         let snapshot = accumulator.snapshot();
-        let symbols: Vec<_> = snapshot.iter().map(|tick| tick.symbol.as_str()).collect();
+        let symbols: Vec<_> = snapshot
+            .iter()
+            .map(|candle| candle.tick.symbol.as_str())
+            .collect();
         assert_eq!(symbols, vec!["A", "B"]);
     }
+
+    #[test]
+    fn accumulator_tracks_ohlc_within_window() {
+        let tick = |price: f64, timestamp_ms: u64| Tick {
+            symbol: "A".parse().unwrap(),
+            price,
+            timestamp_ms,
+            region: Region::Europe,
+            sector: Sector::Technology,
+            prev_close: None,
+        };
+        let mut accumulator = BatchAccumulator::default();
+        for (i, price) in [10.0, 12.0, 9.0, 11.0].into_iter().enumerate() {
+            accumulator.ingest(tick(price, i as u64));
+        }
+
+        let candle = accumulator.snapshot().remove(0);
+        assert_eq!(
+            (candle.open, candle.high, candle.low, candle.tick.price),
+            (10.0, 12.0, 9.0, 11.0)
+        );
+
+        accumulator.roll_window();
+        accumulator.ingest(tick(13.0, 5));
+        let candle = accumulator.snapshot().remove(0);
+        assert_eq!(
+            (candle.open, candle.high, candle.low, candle.tick.price),
+            (11.0, 13.0, 11.0, 13.0),
+            "a new window opens at the previous close"
+        );
+    }
+
+    #[test]
+    fn retain_symbols_drops_delisted_keys() {
+        let tick = |symbol: &str| Tick {
+            symbol: symbol.parse().unwrap(),
+            price: 10.0,
+            timestamp_ms: 1,
+            region: Region::Europe,
+            sector: Sector::Technology,
+            prev_close: None,
+        };
+        let mut accumulator = BatchAccumulator::default().with_aggregation(Aggregation::Mean);
+        accumulator.ingest(tick("A"));
+        accumulator.ingest(tick("B"));
+
+        let live: HashSet<Symbol> = ["B".parse().unwrap()].into_iter().collect();
+        accumulator.retain_symbols(&live);
+        let symbols: Vec<_> = accumulator
+            .snapshot()
+            .iter()
+            .map(|candle| candle.tick.symbol.to_string())
+            .collect();
+        assert_eq!(symbols, vec!["B"]);
+        assert_eq!(accumulator.window.len(), 1);
+    }
+
+    #[test]
+    fn aggregation_prices_the_window_as_last_mean_or_twap() {
+        let tick = |price: f64, timestamp_ms: u64| Tick {
+            symbol: "A".parse().unwrap(),
+            price,
+            timestamp_ms,
+            region: Region::Europe,
+            sector: Sector::Technology,
+            prev_close: None,
+        };
+        // 10 stands for 100ms, 20 for 300ms, and 30 for the last 600ms
+        let window = [tick(10.0, 0), tick(20.0, 100), tick(30.0, 400)];
+        let expected = [
+            (Aggregation::Last, 30.0),
+            (Aggregation::Mean, 20.0),
+            (Aggregation::Twap, 25.0),
+        ];
+
+        for (aggregation, price) in expected {
+            let mut accumulator = BatchAccumulator::default().with_aggregation(aggregation);
+            for tick in window.clone() {
+                accumulator.ingest(tick);
+            }
+            let candle = accumulator.snapshot_at(1_000).remove(0);
+            assert!(
+                (candle.tick.price - price).abs() < 1e-9,
+                "{aggregation:?} gave {}",
+                candle.tick.price
+            );
+            assert_eq!((candle.open, candle.high, candle.low), (10.0, 30.0, 10.0));
+
+            // the next window opens at the actual close, not the aggregate
+            accumulator.roll_window();
+            accumulator.ingest(tick(40.0, 1_200));
+            let candle = accumulator.snapshot_at(1_500).remove(0);
+            assert_eq!(
+                (candle.open, candle.tick.price),
+                (30.0, 40.0),
+                "{aggregation:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn composite_key_keeps_symbol_per_region() {
+        let mut accumulator =
+            BatchAccumulator::with_key(|tick: &Tick| (tick.symbol.clone(), tick.region));
+        for (region, price) in [
+            (Region::Europe, 1.0),
+            (Region::AsiaPacific, 2.0),
+            (Region::Europe, 3.0),
+        ] {
+            accumulator.ingest(Tick {
+                symbol: "A".parse().unwrap(),
+                price,
+                timestamp_ms: 1,
+                region,
+                sector: Sector::Technology,
+                prev_close: None,
+            });
+        }
+
+        let snapshot = accumulator.snapshot();
+        assert_eq!(snapshot.len(), 2, "same symbol in two regions both survive");
+        let europe = snapshot
+            .iter()
+            .find(|candle| candle.tick.region == Region::Europe)
+            .unwrap();
+        assert_eq!(europe.tick.price, 3.0);
+    }
+
+    #[test]
+    fn connection_limiter_rejects_after_limit_per_ip() {
+        let mut limiter = ConnectionLimiter::new(ConnectionRateLimit {
+            max_connections: 2,
+            window: Duration::from_secs(60),
+        });
+        let first: IpAddr = "10.0.0.1".parse().unwrap();
+        let second: IpAddr = "10.0.0.2".parse().unwrap();
+
+        assert!(limiter.admit(first));
+        assert!(limiter.admit(first));
+        assert!(!limiter.admit(first));
+        assert!(limiter.admit(second), "limits are tracked per address");
+    }
+
+    #[test]
+    fn channel_capacities_reject_a_zero_queue_depth() {
+        let err = channel_capacities(0).unwrap_err();
+        assert!(err.to_string().contains("gateway_queue_depth"), "{err}");
+        assert!(channel_capacities(usize::MAX).is_err());
+        // fits the broadcast but not the mpsc queue
+        assert!(channel_capacities(usize::MAX >> 2).is_err());
+        assert!(channel_capacities(usize::MAX >> 3).is_ok());
+        assert_eq!(
+            channel_capacities(8).unwrap(),
+            ChannelCapacities {
+                queue: 8,
+                broadcast: 16
+            }
+        );
+    }
+
+    #[test]
+    fn sustained_lag_alerts_once_per_window_past_the_threshold() {
+        let window = Duration::from_secs(10);
+        let mut lag = SustainedLag::new(LagAlert {
+            max_skipped: 100,
+            window,
+        });
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        assert_eq!(lag.record(at(0), 60), None);
+        assert_eq!(lag.record(at(4), 30), None);
+        assert_eq!(lag.record(at(8), 40), Some(130));
+        assert_eq!(lag.record(at(9), 500), None, "already alerted this window");
+
+        // a new window starts from zero
+        assert_eq!(lag.record(at(10), 99), None);
+        assert_eq!(
+            lag.record(at(21), 99),
+            None,
+            "scattered lag is not sustained"
+        );
+        assert_eq!(lag.record(at(22), 1), Some(100));
+
+        let mut disabled = SustainedLag::new(LagAlert {
+            max_skipped: 0,
+            window,
+        });
+        assert_eq!(disabled.record(at(0), usize::MAX), None);
+    }
+
+    #[test]
+    fn frames_are_tagged_with_type() {
+        let meta = serde_json::to_value(GatewayMessage::<Tick>::Meta(meta_message(
+            vec![Equity {
+                symbol: "A".parse().unwrap(),
+                region: crate::model::Region::Europe,
+                sector: crate::model::Sector::Energy,
+            }],
+            Duration::from_millis(250),
+            false,
+        )))
+        .unwrap();
+        assert_eq!(meta["type"], "meta");
+        assert_eq!(meta["throttle_ms"], 250);
+        assert!(meta["build_version"]
+            .as_str()
+            .unwrap()
+            .starts_with(env!("CARGO_PKG_VERSION")));
+        assert_eq!(meta["symbols"][0]["symbol"], "A");
+        assert_eq!(meta["regions"], serde_json::json!(["europe"]));
+        assert_eq!(meta["sectors"], serde_json::json!(["energy"]));
+
+        let batch = serde_json::to_value(GatewayMessage::Batch(BatchMessage::<Tick> {
+            version: TICK_BATCH_VERSION,
+            ticks: Vec::new(),
+        }))
+        .unwrap();
+        assert_eq!(batch["type"], "batch");
+        assert_eq!(batch["version"], TICK_BATCH_VERSION);
+    }
+
+    #[test]
+    fn compact_batches_use_short_keys() {
+        let ticks = vec![OhlcTick::from(Tick {
+            symbol: "A".parse().unwrap(),
+            price: 1.5,
+            timestamp_ms: 7,
+            region: crate::model::Region::Europe,
+            sector: crate::model::Sector::Energy,
+            prev_close: None,
+        })];
+
+        let full = encode_batch(ticks.clone(), false, None).unwrap();
+        let compact = encode_batch(ticks, true, None).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&compact).unwrap();
+        assert_eq!(parsed["type"], "batch");
+        assert_eq!(parsed["ticks"][0]["s"], "A");
+        assert_eq!(parsed["ticks"][0]["high"], 1.5);
+        assert!(compact.len() < full.len());
+    }
+
+    #[test]
+    fn encoded_batches_deserialize_as_frontend_ticks() {
+        let ticks: Vec<Tick> = [1_716_400_005_123, u64::MAX]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, timestamp_ms)| Tick {
+                symbol: format!("A{idx}").parse().unwrap(),
+                price: 1.5,
+                timestamp_ms,
+                region: Region::Europe,
+                sector: Sector::Energy,
+                prev_close: None,
+            })
+            .collect();
+        let batch: GatewayBatch = ticks.iter().cloned().map(OhlcTick::from).collect();
+
+        for compact_keys in [false, true] {
+            let encoded = encode_batch(batch.clone(), compact_keys, None).unwrap();
+            let decoded: market_core::WireMessage = serde_json::from_str(&encoded).unwrap();
+            let GatewayMessage::Batch(decoded) = decoded.into() else {
+                panic!("expected a batch frame");
+            };
+            assert_eq!(decoded.ticks, ticks, "compact_keys={compact_keys}");
+        }
+    }
+
+    #[test]
+    fn wire_decimals_trim_prices_within_tolerance() {
+        const DECIMALS: u32 = 4;
+        let prices = [101.234_567_891_2, 0.1 + 0.2, 99_999.999_96, 1e-7, 7.0];
+        let batch: GatewayBatch = prices
+            .iter()
+            .enumerate()
+            .map(|(idx, &price)| {
+                let mut candle = OhlcTick::from(Tick {
+                    symbol: format!("A{idx}").parse().unwrap(),
+                    price,
+                    timestamp_ms: 7,
+                    region: Region::Europe,
+                    sector: Sector::Energy,
+                    prev_close: Some(price * 0.987_654_321),
+                });
+                candle.update(Tick {
+                    price: price * 1.000_123_456,
+                    ..candle.tick.clone()
+                });
+                candle
+            })
+            .collect();
+
+        for compact_keys in [false, true] {
+            let full = encode_batch(batch.clone(), compact_keys, None).unwrap();
+            let encoded = encode_batch(batch.clone(), compact_keys, Some(DECIMALS)).unwrap();
+            assert!(encoded.len() < full.len(), "compact_keys={compact_keys}");
+
+            let parsed: serde_json::Value = serde_json::from_str(&encoded).unwrap();
+            for tick in parsed["ticks"].as_array().unwrap() {
+                let price_key = if compact_keys { "p" } else { "price" };
+                let close_key = if compact_keys { "pc" } else { "prev_close" };
+                for key in [price_key, close_key, "open", "high", "low"] {
+                    let text = tick[key].to_string();
+                    let decimals = text.split_once('.').map_or(0, |(_, frac)| frac.len());
+                    assert!(
+                        decimals <= DECIMALS as usize,
+                        "{key}={text} has more than {DECIMALS} decimals"
+                    );
+                }
+            }
+
+            let decoded: market_core::WireMessage = serde_json::from_str(&encoded).unwrap();
+            let GatewayMessage::Batch(decoded) = decoded.into() else {
+                panic!("expected a batch frame");
+            };
+            let tolerance = 0.5 * 10f64.powi(-(DECIMALS as i32)) + 1e-12;
+            for (decoded, original) in decoded.ticks.iter().zip(&batch) {
+                assert!((decoded.price - original.tick.price).abs() <= tolerance);
+                let close = decoded.prev_close.unwrap();
+                assert!((close - original.tick.prev_close.unwrap()).abs() <= tolerance);
+            }
+        }
+    }
+}
+
+/// Per-IP limit on websocket connection attempts within a fixed window.
+///
+/// Each address's window starts at its first attempt and resets once
+/// `window` has elapsed, so a client can make up to twice `max_connections`
+/// attempts across a window boundary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectionRateLimit {
+    pub max_connections: usize,
+    #[serde(with = "humantime_serde")]
+    pub window: Duration,
+}
+
+/// Ticks the gateway aggregator may miss within `window` before the lag
+/// counts as sustained and is reported at error level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LagAlert {
+    /// Skipped ticks within one window that raise the alert; 0 disables it.
+    pub max_skipped: usize,
+    #[serde(with = "humantime_serde")]
+    pub window: Duration,
+}
+
+/// Bounded retry for binding the gateway listener while a previous instance
+/// still holds the port (e.g. right after a restart).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BindRetry {
+    /// Total bind attempts, including the first; 1 disables retrying.
+    pub max_attempts: u32,
+    #[serde(with = "humantime_serde")]
+    pub base_delay: Duration,
+    #[serde(with = "humantime_serde")]
+    pub max_delay: Duration,
+}
+
+/// How the gateway treats a websocket client that falls behind the batch broadcast.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientLagPolicy {
+    /// Log the lag and keep streaming; the client silently misses the skipped batches.
+    #[default]
+    Skip,
+    /// Send a close frame so the client reconnects and resyncs from scratch.
+    CloseOnLag,
+    /// Send a full snapshot of the latest tick per symbol before resuming.
+    SnapshotResync,
+}
+
+/// Price reported for a symbol that ticked several times within one throttle window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Aggregation {
+    /// The window's last price.
+    #[default]
+    Last,
+    /// Simple average of every price in the window.
+    Mean,
+    /// Time-weighted average: each price counts for as long as it stood, the
+    /// last one until the batch is flushed.
+    Twap,
+}
+
+/// Capacities of the aggregator-to-dispatcher queue and of the batch
+/// broadcast that clients and the Redis sink read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct ChannelCapacities {
+    pub queue: usize,
+    pub broadcast: usize,
+}
+
+/// Channel capacities for `queue_depth`: the queue holds `queue_depth`
+/// batches and the broadcast twice that. Tokio panics on zero-capacity
+/// channels and on capacities past their semaphore limit (`usize::MAX >> 3`
+/// for mpsc, which also keeps the doubled broadcast under its
+/// `usize::MAX >> 1`), so those depths are rejected here as configuration
+/// errors.
+pub(super) fn channel_capacities(queue_depth: usize) -> Result<ChannelCapacities> {
+    if queue_depth == 0 {
+        bail!("gateway_queue_depth must be at least 1, got 0");
+    }
+    if queue_depth > usize::MAX >> 3 {
+        bail!("gateway_queue_depth {queue_depth} is too large");
+    }
+    Ok(ChannelCapacities {
+        queue: queue_depth,
+        broadcast: queue_depth * 2,
+    })
 }
 
 pub(super) async fn run_gateway(
-    addr: SocketAddr,
-    throttle: Duration,
-    queue_depth: usize,
-    source_sender: broadcast::Sender<Tick>,
+    config: Arc<SimulatorConfig>,
+    feeds: GatewayFeeds,
+    gateway_sender: broadcast::Sender<GatewayBatch>,
     metrics: MetricsTx,
+    stats: watch::Receiver<GatewayStats>,
     shutdowns: GatewayShutdown,
 ) -> Result<()> {
-    let (gateway_sender, _) = broadcast::channel::<Vec<Tick>>(queue_depth * 2);
-    let (queue_tx, queue_rx) = mpsc::channel::<Vec<Tick>>(queue_depth);
+    let snapshot = feeds.snapshot;
+    let (queue_tx, queue_rx) = mpsc::channel::<GatewayBatch>(config.gateway_queue_depth);
+    let resync_snapshot =
+        (config.client_lag_policy == ClientLagPolicy::SnapshotResync).then(|| snapshot.clone());
 
     tokio::try_join!(
         run_gateway_aggregator(
-            throttle,
-            source_sender.subscribe(),
+            Arc::clone(&config),
+            feeds.ticks.subscribe(),
+            LiveSymbols::new(Arc::clone(&feeds.universe)).await,
             queue_tx,
             metrics.clone(),
             shutdowns.aggregator,
@@ -73,36 +677,84 @@ pub(super) async fn run_gateway(
         run_gateway_dispatcher(
             queue_rx,
             gateway_sender.clone(),
+            snapshot.clone(),
             metrics.clone(),
             shutdowns.dispatcher,
         ),
-        run_gateway_server(addr, gateway_sender, metrics, shutdowns.server),
+        run_gateway_server(
+            config.gateway_addr,
+            ClientContext {
+                gateway_sender,
+                trade_sender: feeds.trades,
+                throttle: config.gateway_throttle,
+                compact_keys: config.compact_keys,
+                wire_decimals: config.wire_decimals,
+                lag_policy: config.client_lag_policy,
+                universe: Arc::clone(&feeds.universe),
+                max_symbols: config.max_client_symbols,
+                symbol_overflow: config.client_symbol_overflow,
+                max_inbound_message_bytes: config.max_inbound_message_bytes,
+                send_timeout: config.client_send_timeout,
+                snapshot: resync_snapshot,
+                metrics,
+                stats,
+                heartbeat: config.heartbeat_enabled.then(|| Heartbeat {
+                    interval: config.heartbeat_interval,
+                    started: Instant::now(),
+                    region_sessions: Arc::new(config.region_sessions.clone()),
+                    regime: feeds.regime,
+                }),
+                #[cfg(feature = "chaos")]
+                chaos: config.chaos,
+            },
+            snapshot,
+            config.correlation_endpoint.then_some(feeds.universe),
+            config.gateway_connection_limit,
+            config.gateway_bind_retry,
+            shutdowns.server
+        ),
     )?;
 
     Ok(())
 }
 
 async fn run_gateway_aggregator(
-    throttle: Duration,
+    config: Arc<SimulatorConfig>,
     mut source: broadcast::Receiver<Tick>,
-    queue_sender: mpsc::Sender<Vec<Tick>>,
+    mut live_symbols: LiveSymbols,
+    queue_sender: mpsc::Sender<GatewayBatch>,
     metrics: MetricsTx,
     mut shutdown: watch::Receiver<ShutdownSignal>,
 ) -> Result<()> {
     logging::info_simple("gateway.aggregator.start", "Gateway aggregator started");
 
-    let mut accumulator = BatchAccumulator::default();
-    let mut ticker = interval(throttle);
+    let throttle = config.gateway_throttle;
+    let mut accumulator = BatchAccumulator::default().with_aggregation(config.gateway_aggregation);
+    let mut ticker = if config.align_batches_to_clock {
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let first_flush = tokio::time::Instant::now() + until_next_boundary(since_epoch, throttle);
+        interval_at(first_flush, throttle)
+    } else {
+        let mut ticker = interval(throttle);
+        ticker.reset();
+        ticker
+    };
     ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
-    ticker.reset();
     let mut lag_tracker = RateTracker::new(Duration::from_secs(1));
     let mut drop_tracker = RateTracker::new(Duration::from_secs(1));
+    let mut sustained_lag = SustainedLag::new(config.aggregator_lag_alert);
 
     loop {
         tokio::select! {
             _ = ticker.tick() => {
+                if let Some(live) = live_symbols.refresh().await {
+                    accumulator.retain_symbols(live);
+                }
                 if !accumulator.is_empty() {
                     let snapshot = accumulator.snapshot();
+                    accumulator.roll_window();
                     if !snapshot.is_empty() {
                         match queue_sender.try_send(snapshot) {
                             Ok(_) => {}
@@ -140,6 +792,17 @@ async fn run_gateway_aggregator(
                                 json!({ "skipped_total": total, "max_skipped": max })
                             );
                         }
+                        if let Some(skipped) = sustained_lag.record(Instant::now(), skipped as usize) {
+                            logging::error(
+                                "gateway.aggregator.sustained_lag",
+                                "Gateway aggregator keeps falling behind source ticks; batches are missing updates",
+                                json!({
+                                    "skipped": skipped,
+                                    "window_ms": config.aggregator_lag_alert.window.as_millis() as u64,
+                                    "threshold": config.aggregator_lag_alert.max_skipped,
+                                })
+                            );
+                        }
                     }
                     Err(broadcast::error::RecvError::Closed) => {
                         break;
@@ -174,16 +837,123 @@ async fn run_gateway_aggregator(
     Ok(())
 }
 
+/// Time from `since_epoch` until the next wall-clock multiple of `period`
+/// (e.g. the next whole second for a one-second period).
+fn until_next_boundary(since_epoch: Duration, period: Duration) -> Duration {
+    let period_nanos = period.as_nanos();
+    if period_nanos == 0 {
+        return Duration::ZERO;
+    }
+    match since_epoch.as_nanos() % period_nanos {
+        0 => Duration::ZERO,
+        elapsed => Duration::from_nanos((period_nanos - elapsed) as u64),
+    }
+}
+
+/// Simulator state the gateway serves: every tick, trade prints for
+/// `/ws/trades`, the universe behind `/correlation`, the latest-tick
+/// snapshot shared with embedders, and the live regime for heartbeats.
+pub(super) struct GatewayFeeds {
+    pub ticks: broadcast::Sender<Tick>,
+    pub trades: broadcast::Sender<TradeBatch>,
+    pub universe: SharedUniverse,
+    pub snapshot: LatestSnapshot,
+    pub regime: watch::Receiver<Regime>,
+}
+
+type SharedUniverse = Arc<tokio::sync::RwLock<StockUniverse>>;
+
+/// Symbols the universe lists after its latest reload, so the aggregator can
+/// drop candles for delisted names.
+struct LiveSymbols {
+    universe: SharedUniverse,
+    generation: u64,
+    /// `None` until the universe first changes: every symbol seen is live.
+    symbols: Option<HashSet<Symbol>>,
+}
+
+impl LiveSymbols {
+    async fn new(universe: SharedUniverse) -> Self {
+        let generation = universe.read().await.generation();
+        Self {
+            universe,
+            generation,
+            symbols: None,
+        }
+    }
+
+    /// The live symbol set, re-read whenever the universe generation moved.
+    /// Ticks generated before a reload can still be in flight, so callers
+    /// prune against it on every flush rather than only once.
+    async fn refresh(&mut self) -> Option<&HashSet<Symbol>> {
+        let universe = self.universe.read().await;
+        if universe.generation() != self.generation {
+            self.generation = universe.generation();
+            self.symbols = Some(
+                universe
+                    .equities()
+                    .iter()
+                    .map(|equity| equity.symbol.clone())
+                    .collect(),
+            );
+        }
+        self.symbols.as_ref()
+    }
+}
+
 pub(super) struct GatewayShutdown {
     pub aggregator: watch::Receiver<ShutdownSignal>,
     pub dispatcher: watch::Receiver<ShutdownSignal>,
     pub server: watch::Receiver<ShutdownSignal>,
 }
 
-#[derive(Serialize)]
-struct TickBatchPayload {
-    version: u32,
-    ticks: Vec<Tick>,
+impl From<GatewayStats> for DebugMessage {
+    fn from(stats: GatewayStats) -> Self {
+        Self {
+            batch_count: stats.batch_count,
+            avg_symbols: stats.avg_symbols,
+        }
+    }
+}
+
+/// Query parameters accepted on the websocket route.
+#[derive(Debug, Default, Deserialize)]
+struct ClientParams {
+    debug: Option<String>,
+    /// Comma-separated symbols to stream instead of the whole universe.
+    symbols: Option<String>,
+}
+
+impl ClientParams {
+    fn debug(&self) -> bool {
+        matches!(self.debug.as_deref(), Some("1" | "true"))
+    }
+}
+
+/// One-time handshake frame for `symbols`, listing only the regions and
+/// sectors they cover so clients never offer a filter that matches nothing.
+pub(super) fn meta_message(
+    symbols: Vec<Equity>,
+    throttle: Duration,
+    compact_keys: bool,
+) -> MetaMessage {
+    let regions = Region::ALL
+        .into_iter()
+        .filter(|region| symbols.iter().any(|equity| equity.region == *region))
+        .collect();
+    let sectors = Sector::ALL
+        .into_iter()
+        .filter(|sector| symbols.iter().any(|equity| equity.sector == *sector))
+        .collect();
+    MetaMessage {
+        version: TICK_BATCH_VERSION,
+        build_version: crate::version::version().to_string(),
+        throttle_ms: throttle.as_millis() as u64,
+        compact_keys,
+        symbols,
+        regions,
+        sectors,
+    }
 }
 
 struct RateTracker {
@@ -234,9 +1004,50 @@ impl RateTracker {
     }
 }
 
+/// Counts ticks the aggregator skipped per fixed window and decides when the
+/// lag has become sustained.
+struct SustainedLag {
+    alert: LagAlert,
+    window_start: Option<Instant>,
+    skipped: usize,
+    alerted: bool,
+}
+
+impl SustainedLag {
+    fn new(alert: LagAlert) -> Self {
+        Self {
+            alert,
+            window_start: None,
+            skipped: 0,
+            alerted: false,
+        }
+    }
+
+    /// Record `skipped` ticks lost at `now`, returning the window's total the
+    /// first time it reaches `alert.max_skipped`. Fires at most once per window.
+    fn record(&mut self, now: Instant, skipped: usize) -> Option<usize> {
+        let expired = match self.window_start {
+            Some(start) => now.duration_since(start) >= self.alert.window,
+            None => true,
+        };
+        if expired {
+            self.window_start = Some(now);
+            self.skipped = 0;
+            self.alerted = false;
+        }
+        self.skipped = self.skipped.saturating_add(skipped);
+        if self.alerted || self.alert.max_skipped == 0 || self.skipped < self.alert.max_skipped {
+            return None;
+        }
+        self.alerted = true;
+        Some(self.skipped)
+    }
+}
+
 async fn run_gateway_dispatcher(
-    mut queue: mpsc::Receiver<Vec<Tick>>,
-    gateway_sender: broadcast::Sender<Vec<Tick>>,
+    mut queue: mpsc::Receiver<GatewayBatch>,
+    gateway_sender: broadcast::Sender<GatewayBatch>,
+    snapshot: LatestSnapshot,
     metrics: MetricsTx,
     mut shutdown: watch::Receiver<ShutdownSignal>,
 ) -> Result<()> {
@@ -248,6 +1059,7 @@ async fn run_gateway_dispatcher(
                 match batch {
                     Some(batch) => {
                         metrics.report(MetricsEvent::GatewayBatch { symbols: batch.len() });
+                        snapshot.replace(&batch);
                         let _ = gateway_sender.send(batch);
                     }
                     None => break,
@@ -265,20 +1077,310 @@ async fn run_gateway_dispatcher(
     Ok(())
 }
 
-#[derive(Default)]
-struct BatchAccumulator {
-    latest: HashMap<String, Tick>,
+struct ConnectionLimiter {
+    limit: ConnectionRateLimit,
+    windows: HashMap<IpAddr, ConnectionWindow>,
+}
+
+struct ConnectionWindow {
+    started: Instant,
+    count: usize,
+}
+
+impl ConnectionLimiter {
+    fn new(limit: ConnectionRateLimit) -> Self {
+        Self {
+            limit,
+            windows: HashMap::new(),
+        }
+    }
+
+    fn admit(&mut self, ip: IpAddr) -> bool {
+        let now = Instant::now();
+        let window = self.limit.window;
+        self.windows
+            .retain(|_, entry| now.duration_since(entry.started) < window);
+
+        let entry = self.windows.entry(ip).or_insert(ConnectionWindow {
+            started: now,
+            count: 0,
+        });
+        if entry.count >= self.limit.max_connections {
+            return false;
+        }
+        entry.count += 1;
+        true
+    }
+}
+
+pub(super) fn encode_batch(
+    mut ticks: GatewayBatch,
+    compact_keys: bool,
+    wire_decimals: Option<u32>,
+) -> Result<String> {
+    if let Some(decimals) = wire_decimals {
+        for candle in &mut ticks {
+            candle.round_prices(decimals);
+        }
+    }
+    let payload = if compact_keys {
+        serde_json::to_string(&GatewayMessage::Batch(BatchMessage {
+            version: TICK_BATCH_VERSION,
+            ticks: ticks.into_iter().map(OhlcTick::compact).collect(),
+        }))
+    } else {
+        serde_json::to_string(&GatewayMessage::Batch(BatchMessage {
+            version: TICK_BATCH_VERSION,
+            ticks,
+        }))
+    };
+    payload.context("serialize tick payload")
+}
+
+/// Run ticks through the batching path and encode the resulting frame, as done once per throttle interval.
+pub(super) fn aggregate_and_encode<I>(ticks: I, compact_keys: bool) -> Result<String>
+where
+    I: IntoIterator<Item = Tick>,
+{
+    let mut accumulator = BatchAccumulator::default();
+    for tick in ticks {
+        accumulator.ingest(tick);
+    }
+    encode_batch(accumulator.snapshot(), compact_keys, None)
+}
+
+/// Batches broadcast to websocket clients, one candle per symbol.
+pub(super) type GatewayBatch = Vec<OhlcTick>;
+
+/// Latest tick for a symbol plus the open/high/low seen during the current throttle window.
+///
+/// The tick's `price` is the window's last price, or its [`Aggregation`] once
+/// the accumulator takes a snapshot.
+#[derive(Clone, Debug, Serialize)]
+pub(super) struct OhlcTick<T = Tick> {
+    #[serde(flatten)]
+    tick: T,
+    open: f64,
+    high: f64,
+    low: f64,
+}
+
+impl From<Tick> for OhlcTick {
+    fn from(tick: Tick) -> Self {
+        Self {
+            open: tick.price,
+            high: tick.price,
+            low: tick.price,
+            tick,
+        }
+    }
+}
+
+impl OhlcTick {
+    fn update(&mut self, tick: Tick) {
+        self.high = self.high.max(tick.price);
+        self.low = self.low.min(tick.price);
+        self.tick = tick;
+    }
+
+    /// Start a new window that opens at the previous close.
+    fn roll(&mut self) {
+        self.open = self.tick.price;
+        self.high = self.tick.price;
+        self.low = self.tick.price;
+    }
+
+    /// Round every price in the candle to `decimals` places so the JSON
+    /// encoding carries no noise digits.
+    fn round_prices(&mut self, decimals: u32) {
+        let round = |value: f64| round_to_decimals(value, decimals);
+        self.tick.price = round(self.tick.price);
+        self.tick.prev_close = self.tick.prev_close.map(round);
+        self.open = round(self.open);
+        self.high = round(self.high);
+        self.low = round(self.low);
+    }
+
+    fn compact(self) -> OhlcTick<CompactTick> {
+        OhlcTick {
+            tick: CompactTick::from(self.tick),
+            open: self.open,
+            high: self.high,
+            low: self.low,
+        }
+    }
+}
+
+/// Round `value` to `decimals` places. Dividing the rounded integer by an
+/// exact power of ten yields the closest `f64` to the decimal, which
+/// `serde_json` prints with no more than `decimals` fractional digits. Values
+/// too large to scale exactly are returned unchanged.
+fn round_to_decimals(value: f64, decimals: u32) -> f64 {
+    const EXACT_INTEGER_LIMIT: f64 = 9_007_199_254_740_992.0;
+    let scale = 10f64.powi(decimals.min(22) as i32);
+    let scaled = (value * scale).round();
+    if scaled.is_finite() && scaled.abs() < EXACT_INTEGER_LIMIT {
+        scaled / scale
+    } else {
+        value
+    }
+}
+
+/// Running price statistics for one key over the current throttle window.
+#[derive(Clone, Copy, Debug)]
+struct WindowStats {
+    sum: f64,
+    count: u32,
+    /// Sum of each superseded price times the milliseconds it stood.
+    weighted_sum: f64,
+    weighted_ms: u64,
+    last_price: f64,
+    last_ms: u64,
+}
+
+impl WindowStats {
+    fn new(tick: &Tick) -> Self {
+        Self {
+            sum: tick.price,
+            count: 1,
+            weighted_sum: 0.0,
+            weighted_ms: 0,
+            last_price: tick.price,
+            last_ms: tick.timestamp_ms,
+        }
+    }
+
+    fn record(&mut self, tick: &Tick) {
+        let held = tick.timestamp_ms.saturating_sub(self.last_ms);
+        self.weighted_sum += self.last_price * held as f64;
+        self.weighted_ms += held;
+        self.sum += tick.price;
+        self.count += 1;
+        self.last_price = tick.price;
+        self.last_ms = tick.timestamp_ms;
+    }
+
+    /// Aggregate price as of `now_ms`, when the window is flushed.
+    fn price(&self, aggregation: Aggregation, now_ms: u64) -> f64 {
+        match aggregation {
+            Aggregation::Last => self.last_price,
+            Aggregation::Mean => self.sum / f64::from(self.count),
+            Aggregation::Twap => {
+                let held = now_ms.saturating_sub(self.last_ms);
+                match self.weighted_ms + held {
+                    0 => self.last_price,
+                    total => (self.weighted_sum + self.last_price * held as f64) / total as f64,
+                }
+            }
+        }
+    }
+}
+
+/// Keeps the latest tick and its window range per dedup key between flushes.
+///
+/// Keys default to the symbol; multi-venue setups can supply a composite key
+/// such as `(symbol, region)` via [`BatchAccumulator::with_key`].
+struct BatchAccumulator<K = Symbol, F = fn(&Tick) -> Symbol> {
+    key: F,
+    latest: HashMap<K, OhlcTick>,
+    aggregation: Aggregation,
+    /// Per-key statistics for the current window; unused for [`Aggregation::Last`].
+    window: HashMap<K, WindowStats>,
 }
 
-impl BatchAccumulator {
+impl Default for BatchAccumulator {
+    fn default() -> Self {
+        Self::with_key(symbol_key)
+    }
+}
+
+fn symbol_key(tick: &Tick) -> Symbol {
+    tick.symbol.clone()
+}
+
+impl<K, F> BatchAccumulator<K, F>
+where
+    K: Eq + Hash + Clone,
+    F: Fn(&Tick) -> K,
+{
+    fn with_key(key: F) -> Self {
+        Self {
+            key,
+            latest: HashMap::new(),
+            aggregation: Aggregation::Last,
+            window: HashMap::new(),
+        }
+    }
+
+    /// Report `aggregation` of each window's prices instead of the last one.
+    fn with_aggregation(mut self, aggregation: Aggregation) -> Self {
+        self.aggregation = aggregation;
+        self
+    }
+
     fn ingest(&mut self, tick: Tick) {
-        self.latest.insert(tick.symbol.clone(), tick);
+        let key = (self.key)(&tick);
+        if self.aggregation != Aggregation::Last {
+            match self.window.entry(key.clone()) {
+                Entry::Occupied(mut entry) => entry.get_mut().record(&tick),
+                Entry::Vacant(entry) => {
+                    entry.insert(WindowStats::new(&tick));
+                }
+            }
+        }
+        match self.latest.entry(key) {
+            Entry::Occupied(mut entry) => entry.get_mut().update(tick),
+            Entry::Vacant(entry) => {
+                entry.insert(OhlcTick::from(tick));
+            }
+        }
     }
 
-    fn snapshot(&self) -> Vec<Tick> {
-        let mut ticks: Vec<Tick> = self.latest.values().cloned().collect();
-        ticks.sort_by(|a, b| a.symbol.cmp(&b.symbol));
-        ticks
+    /// Replace the entry with an already-aggregated candle.
+    fn ingest_candle(&mut self, candle: OhlcTick) {
+        let key = (self.key)(&candle.tick);
+        self.window.remove(&key);
+        self.latest.insert(key, candle);
+    }
+
+    /// Drop keys whose symbol is no longer in `live`, e.g. after a universe reload.
+    fn retain_symbols(&mut self, live: &HashSet<Symbol>) {
+        self.latest
+            .retain(|_, candle| live.contains(&candle.tick.symbol));
+        let latest = &self.latest;
+        self.window.retain(|key, _| latest.contains_key(key));
+    }
+
+    fn roll_window(&mut self) {
+        self.latest.values_mut().for_each(OhlcTick::roll);
+        self.window.clear();
+    }
+
+    fn snapshot(&self) -> GatewayBatch {
+        self.snapshot_at(current_timestamp_ms())
+    }
+
+    /// Candles as of `now_ms`, priced with the configured aggregation for
+    /// keys that ticked during the current window.
+    fn snapshot_at(&self, now_ms: u64) -> GatewayBatch {
+        let mut candles: GatewayBatch = self
+            .latest
+            .iter()
+            .map(|(key, candle)| {
+                let mut candle = candle.clone();
+                if let Some(stats) = self.window.get(key) {
+                    candle.tick.price = stats.price(self.aggregation, now_ms);
+                }
+                candle
+            })
+            .collect();
+        candles.sort_by(|a, b| {
+            a.tick
+                .symbol
+                .cmp(&b.tick.symbol)
+                .then_with(|| (a.tick.region as u8).cmp(&(b.tick.region as u8)))
+        });
+        candles
     }
 
     fn is_empty(&self) -> bool {
@@ -286,13 +1388,166 @@ impl BatchAccumulator {
     }
 }
 
+/// Latest candle per symbol as of the most recently dispatched batch, shared
+/// between the dispatcher, lagging clients, and embedders holding a
+/// [`super::SimulatorHandle`].
+#[derive(Clone, Default)]
+pub(super) struct LatestSnapshot(Arc<RwLock<BatchAccumulator>>);
+
+impl LatestSnapshot {
+    /// Replace the snapshot with `batch`. Every batch carries each live
+    /// symbol, so symbols a universe reload removed drop out here too.
+    fn replace(&self, batch: &GatewayBatch) {
+        let mut latest = self.0.write().expect("gateway snapshot poisoned");
+        *latest = BatchAccumulator::default();
+        for candle in batch {
+            latest.ingest_candle(candle.clone());
+        }
+    }
+
+    fn candles(&self) -> GatewayBatch {
+        self.0.read().expect("gateway snapshot poisoned").snapshot()
+    }
+
+    /// Latest tick per symbol, sorted by symbol.
+    pub(super) fn ticks(&self) -> Vec<Tick> {
+        self.candles()
+            .into_iter()
+            .map(|candle| candle.tick)
+            .collect()
+    }
+}
+
+/// Resolves at an optional deadline, or never when it is absent.
+async fn sleep_until_optional(deadline: Option<tokio::time::Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Resolves on the next tick of an optional interval, or never when it is absent.
+async fn next_optional_tick(ticker: &mut Option<Interval>) {
+    match ticker {
+        Some(ticker) => {
+            ticker.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Shared state handed to every websocket client task.
+#[derive(Clone)]
+struct ClientContext {
+    gateway_sender: broadcast::Sender<GatewayBatch>,
+    trade_sender: broadcast::Sender<TradeBatch>,
+    throttle: Duration,
+    compact_keys: bool,
+    wire_decimals: Option<u32>,
+    lag_policy: ClientLagPolicy,
+    /// Universe that `?symbols=` subscriptions and the meta frame are built from.
+    universe: SharedUniverse,
+    max_symbols: Option<usize>,
+    symbol_overflow: SymbolOverflow,
+    max_inbound_message_bytes: usize,
+    send_timeout: Option<Duration>,
+    /// The dispatcher's latest-candle snapshot, also served on `/snapshot`;
+    /// set only under [`ClientLagPolicy::SnapshotResync`], which resends it
+    /// to a client that lagged.
+    snapshot: Option<LatestSnapshot>,
+    metrics: MetricsTx,
+    stats: watch::Receiver<GatewayStats>,
+    heartbeat: Option<Heartbeat>,
+    #[cfg(feature = "chaos")]
+    chaos: Option<super::ChaosConfig>,
+}
+
+impl ClientContext {
+    /// Resolve a `?symbols=` list against the current universe; `None` streams
+    /// every symbol.
+    async fn subscribe(
+        &self,
+        requested: Option<&str>,
+    ) -> Result<Option<Subscription>, TooManySymbols> {
+        let Some(requested) = requested else {
+            return Ok(None);
+        };
+        let universe = self.universe.read().await;
+        Subscription::resolve(
+            requested,
+            universe.equities(),
+            self.max_symbols,
+            self.symbol_overflow,
+        )
+        .map(Some)
+    }
+
+    /// Handshake frame for the universe as it stands now, so clients that
+    /// connect after a reload see its current symbols.
+    async fn meta_frame(&self) -> Result<String> {
+        let symbols = self.universe.read().await.equities().to_vec();
+        let meta = meta_message(symbols, self.throttle, self.compact_keys);
+        serde_json::to_string(&GatewayMessage::<Tick>::Meta(meta))
+            .context("serialize gateway meta frame")
+    }
+
+    /// How long chaos testing holds the websocket handshake, if at all.
+    fn chaos_delay(&self) -> Option<Duration> {
+        #[cfg(feature = "chaos")]
+        return self.chaos.and_then(|chaos| chaos.delay);
+        #[cfg(not(feature = "chaos"))]
+        None
+    }
+
+    /// When chaos testing drops a connection opened now, if ever.
+    fn chaos_drop_deadline(&self) -> Option<tokio::time::Instant> {
+        #[cfg(feature = "chaos")]
+        return self
+            .chaos
+            .and_then(|chaos| chaos.drop_every)
+            .map(|every| tokio::time::Instant::now() + every);
+        #[cfg(not(feature = "chaos"))]
+        None
+    }
+}
+
+/// Source of the market-state heartbeat frames sent to every client.
+#[derive(Clone)]
+struct Heartbeat {
+    interval: Duration,
+    started: Instant,
+    region_sessions: Arc<HashMap<Region, (u32, u32)>>,
+    regime: watch::Receiver<Regime>,
+}
+
+impl Heartbeat {
+    fn payload(&self, timestamp_ms: u64) -> HeartbeatMessage {
+        let minute = calendar::minute_of_day(timestamp_ms);
+        let open_regions = (!self.region_sessions.is_empty()).then(|| {
+            Region::ALL
+                .into_iter()
+                .filter(|&region| calendar::is_open(&self.region_sessions, region, minute))
+                .count()
+        });
+        HeartbeatMessage {
+            version: TICK_BATCH_VERSION,
+            regime: *self.regime.borrow(),
+            open_regions,
+            uptime_secs: self.started.elapsed().as_secs_f64(),
+        }
+    }
+}
+
 async fn run_gateway_server(
     addr: SocketAddr,
-    gateway_sender: broadcast::Sender<Vec<Tick>>,
-    metrics: MetricsTx,
+    client: ClientContext,
+    snapshot: LatestSnapshot,
+    correlation: Option<SharedUniverse>,
+    connection_limit: Option<ConnectionRateLimit>,
+    bind_retry: BindRetry,
     mut shutdown: watch::Receiver<ShutdownSignal>,
 ) -> Result<()> {
-    let listener = TcpListener::bind(addr)
+    let listener = bind_with_retry(addr, bind_retry)
         .await
         .with_context(|| format!("failed to bind gateway websocket at {addr}"))?;
 
@@ -302,16 +1557,94 @@ async fn run_gateway_server(
         json!({ "addr": addr.to_string() }),
     );
 
-    let app = Router::new().route(
-        "/ws",
-        get({
-            let gateway_sender = gateway_sender.clone();
-            let metrics = metrics.clone();
-            move |ws: WebSocketUpgrade| {
-                websocket_upgrade(ws, gateway_sender.clone(), metrics.clone())
-            }
-        }),
-    );
+    let limiter = connection_limit.map(|limit| Arc::new(Mutex::new(ConnectionLimiter::new(limit))));
+
+    let admit = move |peer: SocketAddr| match &limiter {
+        Some(limiter) => limiter
+            .lock()
+            .expect("connection limiter poisoned")
+            .admit(peer.ip()),
+        None => true,
+    };
+    let admit_trades = admit.clone();
+    let trades_client = client.clone();
+    let health = client.stats.clone();
+    let mut app = Router::new()
+        .route(
+            "/ws",
+            get(
+                move |ws: WebSocketUpgrade,
+                      ConnectInfo(peer): ConnectInfo<SocketAddr>,
+                      params: Result<Query<ClientParams>, QueryRejection>| {
+                    let admit = admit.clone();
+                    let client = client.clone();
+                    async move {
+                        let params = match params {
+                            Ok(Query(params)) => params,
+                            Err(rejection) => {
+                                return ApiError::bad_request(rejection.body_text())
+                                    .into_response();
+                            }
+                        };
+                        let subscription = match client.subscribe(params.symbols.as_deref()).await {
+                            Ok(subscription) => subscription.map(Arc::new),
+                            Err(err) => {
+                                logging::warn(
+                                    "gateway.client.subscription_rejected",
+                                    "Rejected websocket client subscribing to too many symbols",
+                                    json!({ "requested": err.requested, "limit": err.limit }),
+                                );
+                                return ApiError::bad_request(err.to_string()).into_response();
+                            }
+                        };
+                        let feed = ClientFeed::Ticks {
+                            debug: params.debug(),
+                            subscription,
+                        };
+                        // only well-formed requests count against the rate limit
+                        let admitted = admit(peer);
+                        websocket_upgrade(ws, peer, admitted, feed, client).await
+                    }
+                },
+            ),
+        )
+        .route(
+            "/ws/trades",
+            get(
+                move |ws: WebSocketUpgrade, ConnectInfo(peer): ConnectInfo<SocketAddr>| {
+                    let admitted = admit_trades(peer);
+                    websocket_upgrade(
+                        ws,
+                        peer,
+                        admitted,
+                        ClientFeed::Trades,
+                        trades_client.clone(),
+                    )
+                },
+            ),
+        )
+        .route(
+            "/readyz",
+            get(move || {
+                let response = readiness(health.borrow().health);
+                async move { response }
+            }),
+        )
+        .route(
+            "/snapshot",
+            get(
+                move |params: Result<Query<SnapshotParams>, QueryRejection>| {
+                    let response = latest_snapshot(&snapshot, params);
+                    async move { response }
+                },
+            ),
+        );
+    if let Some(universe) = correlation {
+        app = app.route(
+            "/correlation",
+            get(move || correlation_heatmap(Arc::clone(&universe))),
+        );
+    }
 
     let shutdown_signal = async move {
         while shutdown.changed().await.is_ok() {
@@ -321,24 +1654,297 @@ async fn run_gateway_server(
         }
     };
 
-    axum::serve(listener, app.into_make_service())
-        .with_graceful_shutdown(shutdown_signal)
-        .await
-        .context("gateway server terminated with error")?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal)
+    .await
+    .context("gateway server terminated with error")?;
 
     logging::info_simple("gateway.server.stop", "Gateway websocket server stopped");
     Ok(())
 }
 
+/// `/readyz` response: degraded still counts as ready but carries a warning,
+/// unhealthy answers 503 so load balancers stop routing new clients here.
+fn readiness(health: Health) -> (StatusCode, Json<serde_json::Value>) {
+    match health {
+        Health::Healthy => (StatusCode::OK, Json(json!({ "status": health.as_str() }))),
+        Health::Degraded => (
+            StatusCode::OK,
+            Json(json!({
+                "status": health.as_str(),
+                "warning": "gateway clients are lagging or batches are being dropped",
+            })),
+        ),
+        Health::Unhealthy => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "status": health.as_str(),
+                "error": "gateway is losing batches faster than the unhealthy threshold",
+            })),
+        ),
+    }
+}
+
+/// JSON error body shared by the gateway's HTTP routes:
+/// `{"error":"<message>","code":<status>}`.
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = json!({ "error": self.message, "code": self.status.as_u16() });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+/// Filters accepted by `/snapshot`, as wire names (`north_america`) or
+/// symbol prefixes (`NA`).
+#[derive(Debug, Default, Deserialize)]
+struct SnapshotParams {
+    region: Option<String>,
+    sector: Option<String>,
+}
+
+impl SnapshotParams {
+    fn region(&self) -> Result<Option<Region>, ApiError> {
+        parse_filter(
+            self.region.as_deref(),
+            "region",
+            Region::from_prefix,
+            || Region::ALL.iter().map(wire_name).collect(),
+        )
+    }
+
+    fn sector(&self) -> Result<Option<Sector>, ApiError> {
+        parse_filter(
+            self.sector.as_deref(),
+            "sector",
+            Sector::from_prefix,
+            || Sector::ALL.iter().map(wire_name).collect(),
+        )
+    }
+}
+
+fn parse_filter<T: serde::de::DeserializeOwned>(
+    raw: Option<&str>,
+    name: &str,
+    from_prefix: fn(&str) -> Option<T>,
+    expected: impl FnOnce() -> Vec<String>,
+) -> Result<Option<T>, ApiError> {
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+    serde_json::from_value(json!(raw.to_ascii_lowercase()))
+        .ok()
+        .or_else(|| from_prefix(&raw.to_ascii_uppercase()))
+        .map(Some)
+        .ok_or_else(|| {
+            ApiError::bad_request(format!(
+                "unknown {name} {raw:?}; expected one of {}",
+                expected().join(", ")
+            ))
+        })
+}
+
+fn wire_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Latest tick per symbol, optionally filtered by region and sector. Answers
+/// 503 until the first gateway batch has been dispatched.
+fn latest_snapshot(
+    snapshot: &LatestSnapshot,
+    params: Result<Query<SnapshotParams>, QueryRejection>,
+) -> Response {
+    let result = params
+        .map_err(|rejection| ApiError::bad_request(rejection.body_text()))
+        .and_then(|Query(params)| Ok((params.region()?, params.sector()?)))
+        .and_then(|(region, sector)| {
+            let ticks = snapshot.ticks();
+            if ticks.is_empty() {
+                return Err(ApiError::new(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "no gateway batch has been published yet",
+                ));
+            }
+            Ok(ticks
+                .into_iter()
+                .filter(|tick| match region {
+                    Some(region) => tick.region == region,
+                    None => true,
+                })
+                .filter(|tick| match sector {
+                    Some(sector) => tick.sector == sector,
+                    None => true,
+                })
+                .collect::<Vec<_>>())
+        });
+    match result {
+        Ok(ticks) => Json(ticks).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+/// Current correlation matrix in clustered heatmap order.
+async fn correlation_heatmap(universe: SharedUniverse) -> Response {
+    let (correlation, symbols) = {
+        let universe = universe.read().await;
+        let symbols: Vec<Symbol> = universe
+            .equities()
+            .iter()
+            .map(|equity| equity.symbol.clone())
+            .collect();
+        (universe.correlation_matrix().clone(), symbols)
+    };
+    // clustering is cubic in the universe size, so keep it off the async workers
+    let heatmap = tokio::task::spawn_blocking(move || -> CorrelationHeatmap {
+        let labels: Vec<&str> = symbols.iter().map(|symbol| symbol.as_str()).collect();
+        clustered_heatmap(&correlation, &labels)
+    })
+    .await;
+    match heatmap {
+        Ok(heatmap) => Json(heatmap).into_response(),
+        Err(err) => {
+            logging::error(
+                "gateway.correlation.failed",
+                "Failed to build the correlation heatmap",
+                json!({ "error": err.to_string() }),
+            );
+            ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to build the correlation heatmap",
+            )
+            .into_response()
+        }
+    }
+}
+
+/// Bind `addr`, retrying with jittered exponential backoff while the address is still in use.
+async fn bind_with_retry(addr: SocketAddr, retry: BindRetry) -> std::io::Result<TcpListener> {
+    let mut attempt = 1;
+    loop {
+        match bind_reusable(addr) {
+            Ok(listener) => return Ok(listener),
+            Err(err) if is_transient_bind_error(&err) && attempt < retry.max_attempts => {
+                let delay = bind_backoff(&retry, attempt, &mut rand::thread_rng());
+                logging::warn(
+                    "gateway.bind.retry",
+                    "Gateway address unavailable, retrying bind",
+                    json!({
+                        "addr": addr.to_string(),
+                        "attempt": attempt,
+                        "max_attempts": retry.max_attempts,
+                        "delay_ms": delay.as_millis() as u64,
+                        "error": err.to_string(),
+                    }),
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Bind with `SO_REUSEADDR` so sockets from a previous run lingering in `TIME_WAIT` do not block us.
+fn bind_reusable(addr: SocketAddr) -> std::io::Result<TcpListener> {
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(1024)?;
+    TcpListener::from_std(socket.into())
+}
+
+fn is_transient_bind_error(err: &std::io::Error) -> bool {
+    matches!(
+        err.kind(),
+        std::io::ErrorKind::AddrInUse | std::io::ErrorKind::AddrNotAvailable
+    )
+}
+
+/// Delay after failed attempt `attempt` (1-based): doubling from the base delay,
+/// capped at the maximum, then scaled by a random factor in `0.5..=1.0` so
+/// restarting replicas do not retry in lockstep.
+fn bind_backoff(retry: &BindRetry, attempt: u32, rng: &mut impl Rng) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16);
+    let backoff = retry
+        .base_delay
+        .saturating_mul(1 << exponent)
+        .min(retry.max_delay);
+    backoff.mul_f64(rng.gen_range(0.5..=1.0))
+}
+
+/// Stream a websocket client subscribes to, chosen by its route.
+enum ClientFeed {
+    /// `/ws`: the meta frame, then tick batches (plus debug frames when
+    /// requested), limited to the subscribed symbols when there is a subscription.
+    Ticks {
+        debug: bool,
+        subscription: Option<Arc<Subscription>>,
+    },
+    /// `/ws/trades`: time-and-sales prints only.
+    Trades,
+}
+
 async fn websocket_upgrade(
     ws: WebSocketUpgrade,
-    gateway_sender: broadcast::Sender<Vec<Tick>>,
-    metrics: MetricsTx,
+    peer: SocketAddr,
+    admitted: bool,
+    feed: ClientFeed,
+    client: ClientContext,
 ) -> Response {
+    if !admitted {
+        logging::warn(
+            "gateway.client.rate_limited",
+            "Rejected websocket client exceeding per-IP connection limit",
+            json!({ "ip": peer.ip().to_string() }),
+        );
+        return ApiError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "too many connection attempts",
+        )
+        .into_response();
+    }
+    if let Some(delay) = client.chaos_delay() {
+        tokio::time::sleep(delay).await;
+    }
+
+    // refuse oversized input while decoding rather than after buffering it
+    let ws = ws
+        .max_message_size(client.max_inbound_message_bytes)
+        .max_frame_size(client.max_inbound_message_bytes);
     ws.on_upgrade(move |socket| async move {
-        if let Err(err) =
-            forward_ticks_to_client(socket, gateway_sender.clone(), metrics.clone()).await
-        {
+        let result = match feed {
+            ClientFeed::Ticks {
+                debug,
+                subscription,
+            } => forward_ticks_to_client(socket, client, debug, subscription).await,
+            ClientFeed::Trades => forward_trades_to_client(socket, client).await,
+        };
+        if let Err(err) = result {
             logging::warn(
                 "gateway.client_error",
                 "Gateway websocket client ended with error",
@@ -350,43 +1956,111 @@ async fn websocket_upgrade(
 
 async fn forward_ticks_to_client(
     socket: WebSocket,
-    gateway_sender: broadcast::Sender<Vec<Tick>>,
-    metrics: MetricsTx,
+    client: ClientContext,
+    debug: bool,
+    subscription: Option<Arc<Subscription>>,
 ) -> Result<()> {
-    logging::info_simple(
-        "gateway.client.connected",
-        "Gateway websocket client connected",
-    );
+    match &subscription {
+        Some(subscription) => logging::info(
+            "gateway.client.connected",
+            "Gateway websocket client connected",
+            json!({ "subscribed_symbols": subscription.len() }),
+        ),
+        None => logging::info_simple(
+            "gateway.client.connected",
+            "Gateway websocket client connected",
+        ),
+    }
 
-    let (mut ws_sender, mut ws_receiver) = socket.split();
-    let mut receiver = gateway_sender.subscribe();
+    let (mut ws_sender, ws_receiver) = socket.split();
+    let mut receiver = client.gateway_sender.subscribe();
+    let chaos_drop = client.chaos_drop_deadline();
+    let meta = client.meta_frame().await?;
+    ws_sender
+        .send(Message::Text(meta))
+        .await
+        .context("send gateway meta frame")?;
+    let notices = subscription
+        .iter()
+        .flat_map(|subscription| subscription.notices());
+    for notice in notices {
+        let payload = serde_json::to_string(&GatewayMessage::<Tick>::Notice(notice.clone()))
+            .context("serialize gateway notice frame")?;
+        ws_sender
+            .send(Message::Text(payload))
+            .await
+            .context("send gateway notice frame")?;
+    }
     let mut lag_tracker = RateTracker::new(Duration::from_secs(1));
-
-    let reader = tokio::spawn(async move {
-        while let Some(Ok(message)) = ws_receiver.next().await {
-            if matches!(message, Message::Close(_)) {
-                break;
-            }
-        }
+    let mut debug_ticker = debug.then(|| {
+        let mut ticker = interval(Duration::from_millis(GATEWAY_DEBUG_INTERVAL_MS));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        ticker
+    });
+    let mut heartbeat_ticker = client.heartbeat.as_ref().map(|heartbeat| {
+        let mut ticker = interval(heartbeat.interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        ticker
     });
 
+    let mut inbound = tokio::spawn(watch_inbound(ws_receiver));
+
     loop {
-        match receiver.recv().await {
+        let recv = tokio::select! {
+            recv = receiver.recv() => recv,
+            _ = next_optional_tick(&mut debug_ticker) => {
+                let stats = *client.stats.borrow();
+                let payload = serde_json::to_string(&GatewayMessage::<Tick>::Debug(stats.into()))
+                    .context("serialize gateway debug frame")?;
+                if !send_frame(&mut ws_sender, Message::Text(payload), client.send_timeout).await {
+                    break;
+                }
+                continue;
+            }
+            _ = next_optional_tick(&mut heartbeat_ticker) => {
+                let Some(heartbeat) = &client.heartbeat else {
+                    continue;
+                };
+                let frame = GatewayMessage::<Tick>::Heartbeat(heartbeat.payload(current_timestamp_ms()));
+                let payload = serde_json::to_string(&frame)
+                    .context("serialize gateway heartbeat frame")?;
+                if !send_frame(&mut ws_sender, Message::Text(payload), client.send_timeout).await {
+                    break;
+                }
+                continue;
+            }
+            _ = sleep_until_optional(chaos_drop) => {
+                logging::info_simple(
+                    "gateway.client.chaos_drop",
+                    "Dropping websocket client on the chaos schedule",
+                );
+                let close = Message::Close(Some(CloseFrame {
+                    code: close_code::RESTART,
+                    reason: "chaos".into(),
+                }));
+                send_frame(&mut ws_sender, close, client.send_timeout).await;
+                break;
+            }
+            ended = &mut inbound => {
+                if let Ok(Some(size)) = ended {
+                    close_oversized(&mut ws_sender, size, &client).await;
+                }
+                break;
+            }
+        };
+        match recv {
             Ok(batch) => {
+                let batch = subscribed(batch, subscription.as_deref());
                 if batch.is_empty() {
                     continue;
                 }
-                let payload = serde_json::to_string(&TickBatchPayload {
-                    version: TICK_BATCH_VERSION,
-                    ticks: batch,
-                })
-                .context("serialize tick payload")?;
-                if ws_sender.send(Message::Text(payload)).await.is_err() {
+                let payload = encode_batch(batch, client.compact_keys, client.wire_decimals)?;
+                if !send_frame(&mut ws_sender, Message::Text(payload), client.send_timeout).await {
                     break;
                 }
             }
             Err(broadcast::error::RecvError::Lagged(skipped)) => {
-                metrics.report(MetricsEvent::GatewayLag {
+                client.metrics.report(MetricsEvent::GatewayLag {
                     skipped: skipped as usize,
                     component: "client",
                 });
@@ -397,13 +2071,43 @@ async fn forward_ticks_to_client(
                         json!({ "skipped_total": total, "max_skipped": max }),
                     );
                 }
+                match client.lag_policy {
+                    ClientLagPolicy::Skip => {}
+                    ClientLagPolicy::CloseOnLag => {
+                        logging::warn(
+                            "gateway.client.lag_close",
+                            "Closing lagging websocket client so it can resync",
+                            json!({ "skipped": skipped }),
+                        );
+                        let close = Message::Close(Some(CloseFrame {
+                            code: close_code::AGAIN,
+                            reason: "lagged".into(),
+                        }));
+                        send_frame(&mut ws_sender, close, client.send_timeout).await;
+                        break;
+                    }
+                    ClientLagPolicy::SnapshotResync => {
+                        let Some(snapshot) = &client.snapshot else {
+                            continue;
+                        };
+                        let payload = encode_batch(
+                            subscribed(snapshot.candles(), subscription.as_deref()),
+                            client.compact_keys,
+                            client.wire_decimals,
+                        )?;
+                        if !send_frame(&mut ws_sender, Message::Text(payload), client.send_timeout)
+                            .await
+                        {
+                            break;
+                        }
+                    }
+                }
             }
             Err(broadcast::error::RecvError::Closed) => break,
         }
     }
 
-    reader.abort();
-    let _ = reader.await;
+    inbound.abort();
 
     if let Some((total, max)) = lag_tracker.flush() {
         logging::warn(
@@ -419,3 +2123,140 @@ async fn forward_ticks_to_client(
     );
     Ok(())
 }
+
+/// How long a client refused for an oversized message keeps its connection
+/// after the close frame, so the frame arrives ahead of the reset.
+const OVERSIZED_CLOSE_LINGER: Duration = Duration::from_millis(500);
+
+/// Drain a client's inbound frames until it disconnects, returning the size of
+/// the message that broke the inbound limit if that is why. The upgrade caps
+/// frames and messages at the limit, so tungstenite refuses an oversized
+/// message while decoding it rather than after buffering it.
+async fn watch_inbound(mut receiver: SplitStream<WebSocket>) -> Option<usize> {
+    while let Some(message) = receiver.next().await {
+        match message {
+            Ok(Message::Close(_)) => return None,
+            Ok(_) => continue,
+            Err(err) => return message_too_long(err),
+        }
+    }
+    None
+}
+
+/// Declared size of the message behind a read error, if it was refused for length.
+fn message_too_long(err: axum::Error) -> Option<usize> {
+    match err.into_inner().downcast_ref::<WsError>() {
+        Some(WsError::Capacity(CapacityError::MessageTooLong { size, .. })) => Some(*size),
+        _ => None,
+    }
+}
+
+/// Close a client that sent a message over the inbound limit.
+async fn close_oversized<S>(sender: &mut S, size: usize, client: &ClientContext)
+where
+    S: Sink<Message> + Unpin,
+{
+    logging::warn(
+        "gateway.client.oversized_message",
+        "Closing websocket client that sent an oversized message",
+        json!({ "bytes": size, "limit": client.max_inbound_message_bytes }),
+    );
+    let close = Message::Close(Some(CloseFrame {
+        code: close_code::POLICY,
+        reason: "message too large".into(),
+    }));
+    if send_frame(sender, close, client.send_timeout).await {
+        // the rest of the refused message is still unread, and dropping a
+        // socket over unread input resets it before the client sees the close
+        tokio::time::sleep(OVERSIZED_CLOSE_LINGER).await;
+    }
+}
+
+/// The part of `batch` a client subscribed to.
+fn subscribed(mut batch: GatewayBatch, subscription: Option<&Subscription>) -> GatewayBatch {
+    if let Some(subscription) = subscription {
+        batch.retain(|candle| subscription.contains(&candle.tick.symbol));
+    }
+    batch
+}
+
+/// Send one frame, giving up after `timeout`. Returns `false` when the client
+/// is gone or stalled, so the caller should drop the connection.
+async fn send_frame<S>(sender: &mut S, message: Message, timeout: Option<Duration>) -> bool
+where
+    S: Sink<Message> + Unpin,
+{
+    let Some(limit) = timeout else {
+        return sender.send(message).await.is_ok();
+    };
+    match tokio::time::timeout(limit, sender.send(message)).await {
+        Ok(sent) => sent.is_ok(),
+        Err(_) => {
+            logging::warn(
+                "gateway.client.send_timeout",
+                "Disconnecting websocket client that stopped draining frames",
+                json!({ "timeout_ms": limit.as_millis() as u64 }),
+            );
+            false
+        }
+    }
+}
+
+async fn forward_trades_to_client(socket: WebSocket, client: ClientContext) -> Result<()> {
+    logging::info_simple(
+        "gateway.trades_client.connected",
+        "Gateway trades client connected",
+    );
+
+    let (mut ws_sender, ws_receiver) = socket.split();
+    let mut receiver = client.trade_sender.subscribe();
+    let mut lag_tracker = RateTracker::new(Duration::from_secs(1));
+    let mut inbound = tokio::spawn(watch_inbound(ws_receiver));
+
+    loop {
+        let recv = tokio::select! {
+            recv = receiver.recv() => recv,
+            ended = &mut inbound => {
+                if let Ok(Some(size)) = ended {
+                    close_oversized(&mut ws_sender, size, &client).await;
+                }
+                break;
+            }
+        };
+        match recv {
+            Ok(trades) => {
+                let frame = GatewayMessage::<Tick>::Trades(TradesMessage {
+                    version: TICK_BATCH_VERSION,
+                    trades,
+                });
+                let payload =
+                    serde_json::to_string(&frame).context("serialize gateway trades frame")?;
+                if !send_frame(&mut ws_sender, Message::Text(payload), client.send_timeout).await {
+                    break;
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                client.metrics.report(MetricsEvent::GatewayLag {
+                    skipped: skipped as usize,
+                    component: "trades_client",
+                });
+                if let Some((total, max)) = lag_tracker.record(skipped as usize) {
+                    logging::warn(
+                        "gateway.trades_client.lagged",
+                        "Trades client lagged trade prints",
+                        json!({ "skipped_total": total, "max_skipped": max }),
+                    );
+                }
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+
+    inbound.abort();
+
+    logging::info_simple(
+        "gateway.trades_client.disconnected",
+        "Gateway trades client disconnected",
+    );
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use nalgebra::DMatrix;
+use serde::Serialize;
+
+/// Correlation matrix reordered so strongly correlated symbols sit next to each
+/// other, ready to draw as a heatmap. Served on the gateway's `/correlation` route.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CorrelationHeatmap {
+    /// Row and column labels, in display order.
+    pub symbols: Vec<String>,
+    /// Correlations in display order, one row per symbol.
+    pub matrix: Vec<Vec<f64>>,
+}
+
+/// Reorder `correlation` (with one label per row) by [`cluster_order`].
+pub fn clustered_heatmap<L: ToString>(
+    correlation: &DMatrix<f64>,
+    labels: &[L],
+) -> CorrelationHeatmap {
+    let order = cluster_order(correlation);
+    CorrelationHeatmap {
+        symbols: order.iter().map(|&idx| labels[idx].to_string()).collect(),
+        matrix: order
+            .iter()
+            .map(|&row| order.iter().map(|&col| correlation[(row, col)]).collect())
+            .collect(),
+    }
+}
+
+/// Leaf order of an average-linkage hierarchical clustering on `1 - correlation`.
+///
+/// Each merge appends the second cluster's leaves after the first's, so every
+/// cluster occupies a contiguous run of the returned order. Ties merge the
+/// lowest indices first, keeping the order deterministic.
+pub fn cluster_order(correlation: &DMatrix<f64>) -> Vec<usize> {
+    let size = correlation.nrows().min(correlation.ncols());
+    let mut distance = correlation.map(|value| 1.0 - value);
+    let mut clusters: Vec<Option<Vec<usize>>> = (0..size).map(|idx| Some(vec![idx])).collect();
+
+    for _ in 1..size {
+        let mut closest: Option<(usize, usize, f64)> = None;
+        for a in 0..size {
+            if clusters[a].is_none() {
+                continue;
+            }
+            for b in (a + 1)..size {
+                if clusters[b].is_none() {
+                    continue;
+                }
+                let d = distance[(a, b)];
+                let closer = match closest {
+                    Some((_, _, best)) => d < best,
+                    None => true,
+                };
+                if closer {
+                    closest = Some((a, b, d));
+                }
+            }
+        }
+        let Some((a, b, _)) = closest else {
+            break;
+        };
+
+        let merged = clusters[b].take().unwrap_or_default();
+        let (size_a, size_b) = (
+            clusters[a].as_ref().map_or(0, Vec::len) as f64,
+            merged.len() as f64,
+        );
+        for k in 0..size {
+            if k == a || clusters[k].is_none() {
+                continue;
+            }
+            let average =
+                (size_a * distance[(a, k)] + size_b * distance[(b, k)]) / (size_a + size_b);
+            distance[(a, k)] = average;
+            distance[(k, a)] = average;
+        }
+        if let Some(leaves) = clusters[a].as_mut() {
+            leaves.extend(merged);
+        }
+    }
+
+    clusters.into_iter().flatten().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Three blocks of sizes 3, 2, and 3, interleaved across the index range.
+    fn interleaved_blocks() -> (DMatrix<f64>, Vec<usize>) {
+        let block = vec![0, 1, 2, 0, 1, 2, 0, 2];
+        let matrix = DMatrix::from_fn(block.len(), block.len(), |row, col| {
+            if row == col {
+                1.0
+            } else if block[row] == block[col] {
+                0.8
+            } else {
+                0.1
+            }
+        });
+        (matrix, block)
+    }
+
+    #[test]
+    fn blocks_come_back_contiguous() {
+        let (matrix, block) = interleaved_blocks();
+        let order = cluster_order(&matrix);
+
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(
+            sorted,
+            (0..block.len()).collect::<Vec<_>>(),
+            "a permutation"
+        );
+
+        let runs: Vec<usize> = order.iter().map(|&idx| block[idx]).collect();
+        let changes = runs.windows(2).filter(|pair| pair[0] != pair[1]).count();
+        assert_eq!(changes, 2, "each block forms one run: {runs:?}");
+    }
+
+    #[test]
+    fn heatmap_reorders_rows_columns_and_labels_together() {
+        let (matrix, block) = interleaved_blocks();
+        let labels: Vec<String> = block
+            .iter()
+            .enumerate()
+            .map(|(idx, block)| format!("B{block}-{idx}"))
+            .collect();
+        let heatmap = clustered_heatmap(&matrix, &labels);
+
+        assert_eq!(heatmap.symbols.len(), labels.len());
+        for (row, symbol) in heatmap.symbols.iter().enumerate() {
+            assert_eq!(heatmap.matrix[row][row], 1.0);
+            let neighbour = &heatmap.symbols[(row + 1) % labels.len()];
+            let same_block = symbol[..2] == neighbour[..2];
+            let expected = if same_block { 0.8 } else { 0.1 };
+            assert_eq!(heatmap.matrix[row][(row + 1) % labels.len()], expected);
+        }
+    }
+
+    #[test]
+    fn empty_and_single_matrices_are_trivial() {
+        assert!(cluster_order(&DMatrix::zeros(0, 0)).is_empty());
+        assert_eq!(cluster_order(&DMatrix::identity(1, 1)), vec![0]);
+    }
+
+    #[test]
+    fn serializes_in_the_documented_shape() {
+        let sample: serde_json::Value =
+            serde_json::from_str(include_str!("../../../schemas/correlation.sample.json"))
+                .expect("sample parses");
+        let symbols: Vec<&str> = sample["symbols"]
+            .as_array()
+            .expect("symbols array")
+            .iter()
+            .map(|symbol| symbol.as_str().expect("symbol string"))
+            .collect();
+        let matrix = DMatrix::from_fn(symbols.len(), symbols.len(), |row, col| {
+            sample["matrix"][row][col].as_f64().expect("correlation")
+        });
+
+        let encoded = serde_json::to_value(clustered_heatmap(&matrix, &symbols)).unwrap();
+        assert_eq!(encoded, sample);
+    }
+}
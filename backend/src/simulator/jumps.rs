@@ -0,0 +1,153 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use crate::model::{Equity, Sector};
+
+/// Rare discontinuous moves layered on top of the correlated diffusion shocks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JumpModel {
+    /// Chance per tick that a symbol jumps, or a whole sector when shared.
+    pub probability: f64,
+    /// Typical jump size in shock units (multiples of a one-sigma tick move).
+    pub scale: f64,
+    /// Fire jumps per sector with one sign for every member, scaled by each
+    /// symbol's sector beta, instead of independently per symbol.
+    pub sector_shared: bool,
+}
+
+impl JumpModel {
+    pub fn is_enabled(&self) -> bool {
+        self.probability > 0.0 && self.scale != 0.0
+    }
+
+    /// Add this tick's jump component for each equity to `shocks`, leaving
+    /// the shock untouched where nothing fired.
+    pub(super) fn draw(
+        &self,
+        rng: &mut StdRng,
+        equities: &[Equity],
+        sector_betas: &[f64],
+        shocks: &mut [f64],
+    ) {
+        let probability = self.probability.clamp(0.0, 1.0);
+        if self.sector_shared {
+            let mut sector_jumps = [0.0; Sector::ALL.len()];
+            for jump in &mut sector_jumps {
+                if rng.gen_bool(probability) {
+                    *jump = self.jump_size(rng);
+                }
+            }
+            for ((shock, equity), beta) in shocks.iter_mut().zip(equities).zip(sector_betas) {
+                *shock += sector_jumps[equity.sector.index()] * beta;
+            }
+        } else {
+            for (shock, _) in shocks.iter_mut().zip(equities) {
+                if rng.gen_bool(probability) {
+                    *shock += self.jump_size(rng);
+                }
+            }
+        }
+    }
+
+    fn jump_size(&self, rng: &mut StdRng) -> f64 {
+        let magnitude = self.scale * rng.gen_range(0.5..1.5);
+        if rng.gen_bool(0.5) {
+            magnitude
+        } else {
+            -magnitude
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::default_equities;
+    use rand::SeedableRng;
+
+    #[test]
+    fn shared_sector_jumps_move_members_together() {
+        let equities = default_equities();
+        let betas: Vec<f64> = (0..equities.len())
+            .map(|idx| 0.4 + (idx % 7) as f64 * 0.04)
+            .collect();
+        let model = JumpModel {
+            probability: 0.3,
+            scale: 5.0,
+            sector_shared: true,
+        };
+        let mut rng = StdRng::seed_from_u64(11);
+        let (mut fired, mut quiet) = (0, 0);
+
+        for _ in 0..50 {
+            let mut jumps = vec![0.0; equities.len()];
+            model.draw(&mut rng, &equities, &betas, &mut jumps);
+            for sector in Sector::ALL {
+                let members: Vec<f64> = equities
+                    .iter()
+                    .zip(&jumps)
+                    .filter(|(equity, _)| equity.sector == sector)
+                    .map(|(_, jump)| *jump)
+                    .collect();
+                if members.iter().all(|jump| *jump == 0.0) {
+                    quiet += 1;
+                } else {
+                    fired += 1;
+                    let up = members[0] > 0.0;
+                    assert!(
+                        members
+                            .iter()
+                            .all(|jump| (*jump > 0.0) == up && *jump != 0.0),
+                        "{sector:?} members moved in different directions"
+                    );
+                }
+            }
+        }
+        assert!(
+            fired > 0 && quiet > 0,
+            "expected both jumping and quiet sectors"
+        );
+    }
+
+    #[test]
+    fn shared_jumps_scale_with_sector_beta() {
+        let equities = default_equities();
+        let betas: Vec<f64> = (0..equities.len())
+            .map(|idx| 0.4 + (idx % 2) as f64 * 0.2)
+            .collect();
+        let model = JumpModel {
+            probability: 1.0,
+            scale: 2.0,
+            sector_shared: true,
+        };
+        let mut jumps = vec![0.0; equities.len()];
+        model.draw(&mut StdRng::seed_from_u64(3), &equities, &betas, &mut jumps);
+        let same_sector = equities
+            .iter()
+            .enumerate()
+            .skip(1)
+            .find(|(_, equity)| equity.sector == equities[0].sector)
+            .map(|(idx, _)| idx)
+            .expect("another member of the first sector");
+        assert!((jumps[0] / betas[0] - jumps[same_sector] / betas[same_sector]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn disabled_model_never_jumps() {
+        let model = JumpModel {
+            probability: 0.0,
+            scale: 5.0,
+            sector_shared: false,
+        };
+        assert!(!model.is_enabled());
+        let equities = default_equities();
+        let mut shocks = vec![1.0; equities.len()];
+        model.draw(
+            &mut StdRng::seed_from_u64(1),
+            &equities,
+            &vec![0.5; equities.len()],
+            &mut shocks,
+        );
+        assert!(shocks.iter().all(|shock| *shock == 1.0));
+    }
+}
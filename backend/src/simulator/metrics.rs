@@ -1,13 +1,17 @@
 use std::{collections::HashMap, time::Duration};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
 use tokio::{
     sync::{mpsc, watch},
     time::{interval, MissedTickBehavior},
 };
 
-use crate::logging;
+use crate::{
+    constants::{HEALTH_DEGRADED_LOSS_PER_SEC, HEALTH_UNHEALTHY_LOSS_PER_SEC},
+    logging,
+};
 
 use super::ShutdownSignal;
 
@@ -26,8 +30,108 @@ pub enum MetricsEvent {
     GatewayBackpressure {
         dropped: usize,
     },
+    SubscriberCounts {
+        source: usize,
+        gateway: usize,
+    },
+}
+
+/// Running gateway batching totals since startup plus the latest health,
+/// published for debug websocket clients and `/readyz`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GatewayStats {
+    pub batch_count: u64,
+    pub avg_symbols: f64,
+    pub health: Health,
+}
+
+/// Overall pipeline health, judged by how many batches were lost to lag or
+/// backpressure during the last reporting interval.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Health {
+    #[default]
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
+impl Health {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Health::Healthy => "healthy",
+            Health::Degraded => "degraded",
+            Health::Unhealthy => "unhealthy",
+        }
+    }
+}
+
+/// Loss rates, in batches skipped by lagging receivers plus batches dropped
+/// under backpressure per second, at or above which health degrades.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthThresholds {
+    pub degraded_per_sec: f64,
+    pub unhealthy_per_sec: f64,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            degraded_per_sec: HEALTH_DEGRADED_LOSS_PER_SEC,
+            unhealthy_per_sec: HEALTH_UNHEALTHY_LOSS_PER_SEC,
+        }
+    }
+}
+
+impl HealthThresholds {
+    /// Reject rates that are not finite or negative, and a degraded rate above
+    /// the unhealthy one, any of which would make [`assess_health`] misreport.
+    pub(super) fn validate(&self) -> Result<()> {
+        for (name, rate) in [
+            ("degraded_per_sec", self.degraded_per_sec),
+            ("unhealthy_per_sec", self.unhealthy_per_sec),
+        ] {
+            if !rate.is_finite() || rate < 0.0 {
+                bail!("health_thresholds.{name} must be a finite, non-negative rate, got {rate}");
+            }
+        }
+        if self.degraded_per_sec > self.unhealthy_per_sec {
+            bail!(
+                "health_thresholds.degraded_per_sec ({}) must not exceed unhealthy_per_sec ({})",
+                self.degraded_per_sec,
+                self.unhealthy_per_sec
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Health for `skipped` lagged and `dropped` backpressured batches over `window`.
+/// An interval without losses is always healthy.
+pub fn assess_health(
+    skipped: usize,
+    dropped: usize,
+    window: Duration,
+    thresholds: HealthThresholds,
+) -> Health {
+    let lost = skipped.saturating_add(dropped);
+    if lost == 0 || window.is_zero() {
+        return Health::Healthy;
+    }
+    let rate = lost as f64 / window.as_secs_f64();
+    if rate >= thresholds.unhealthy_per_sec {
+        Health::Unhealthy
+    } else if rate >= thresholds.degraded_per_sec {
+        Health::Degraded
+    } else {
+        Health::Healthy
+    }
 }
 
+/// How often counters are summarized and health is reassessed.
+const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Clone, Default)]
 pub struct MetricsTx(Option<mpsc::UnboundedSender<MetricsEvent>>);
 
@@ -41,95 +145,168 @@ impl MetricsTx {
     pub fn noop() -> Self {
         Self(None)
     }
+
+    #[cfg(test)]
+    pub fn capture() -> (Self, mpsc::UnboundedReceiver<MetricsEvent>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self(Some(tx)), rx)
+    }
 }
 
 pub fn reporter(
     shutdown: watch::Receiver<ShutdownSignal>,
-) -> (MetricsTx, impl std::future::Future<Output = Result<()>>) {
+    thresholds: HealthThresholds,
+) -> (
+    MetricsTx,
+    watch::Receiver<GatewayStats>,
+    impl std::future::Future<Output = Result<()>>,
+) {
     let (tx, rx) = mpsc::unbounded_channel();
-    (MetricsTx(Some(tx)), process_events(rx, shutdown))
+    let (stats_tx, stats_rx) = watch::channel(GatewayStats::default());
+    let emit = |summary| logging::info("metrics.throughput", "tick throughput summary", summary);
+    (
+        MetricsTx(Some(tx)),
+        stats_rx,
+        process_events(rx, stats_tx, shutdown, thresholds, emit),
+    )
+}
+
+/// Counters accumulated over one reporting interval.
+#[derive(Default)]
+struct IntervalCounters {
+    tick_batches: usize,
+    total_ticks: usize,
+    gateway_batches: usize,
+    gateway_symbols: usize,
+    gateway_max_batch: usize,
+    gateway_lag: HashMap<&'static str, (usize, usize)>,
+    backpressure_drops: usize,
+    /// Latest sampled subscriber counts; carried across intervals.
+    source_subscribers: usize,
+    gateway_subscribers: usize,
+    /// Symbols across every gateway batch since startup; carried across intervals.
+    gateway_symbols_total: u64,
+}
+
+impl IntervalCounters {
+    fn record(&mut self, event: MetricsEvent, stats: &watch::Sender<GatewayStats>) {
+        match event {
+            MetricsEvent::TickBatch { generated } => {
+                self.tick_batches = self.tick_batches.saturating_add(1);
+                self.total_ticks = self.total_ticks.saturating_add(generated);
+            }
+            MetricsEvent::GatewayBatch { symbols } => {
+                self.gateway_batches = self.gateway_batches.saturating_add(1);
+                self.gateway_symbols = self.gateway_symbols.saturating_add(symbols);
+                self.gateway_max_batch = self.gateway_max_batch.max(symbols);
+                self.gateway_symbols_total =
+                    self.gateway_symbols_total.saturating_add(symbols as u64);
+                let symbols_total = self.gateway_symbols_total;
+                stats.send_modify(|stats| {
+                    stats.batch_count = stats.batch_count.saturating_add(1);
+                    stats.avg_symbols = symbols_total as f64 / stats.batch_count as f64;
+                });
+            }
+            MetricsEvent::GatewayLag { skipped, component } => {
+                let entry = self.gateway_lag.entry(component).or_insert((0, 0));
+                entry.0 = entry.0.saturating_add(1);
+                entry.1 = entry.1.saturating_add(skipped);
+            }
+            MetricsEvent::GatewayBackpressure { dropped } => {
+                self.backpressure_drops = self.backpressure_drops.saturating_add(dropped);
+            }
+            MetricsEvent::SubscriberCounts { source, gateway } => {
+                self.source_subscribers = source;
+                self.gateway_subscribers = gateway;
+            }
+        }
+    }
+
+    fn health(&self, window: Duration, thresholds: HealthThresholds) -> Health {
+        let skipped = self
+            .gateway_lag
+            .values()
+            .fold(0usize, |total, (_, skipped)| total.saturating_add(*skipped));
+        assess_health(skipped, self.backpressure_drops, window, thresholds)
+    }
+
+    fn has_activity(&self) -> bool {
+        self.tick_batches > 0
+            || self.gateway_batches > 0
+            || !self.gateway_lag.is_empty()
+            || self.backpressure_drops > 0
+    }
+
+    /// Summarize and reset the interval, or `None` if nothing happened in it.
+    fn flush(&mut self) -> Option<Value> {
+        let summary = self.has_activity().then(|| self.summary());
+        self.tick_batches = 0;
+        self.total_ticks = 0;
+        self.gateway_batches = 0;
+        self.gateway_symbols = 0;
+        self.gateway_max_batch = 0;
+        self.gateway_lag.clear();
+        self.backpressure_drops = 0;
+        summary
+    }
+
+    fn summary(&self) -> Value {
+        let lag_snapshot = if self.gateway_lag.is_empty() {
+            Value::Null
+        } else {
+            let mut map = Map::new();
+            for (component, (events, skipped)) in &self.gateway_lag {
+                map.insert(
+                    component.to_string(),
+                    json!({
+                        "events": events,
+                        "skipped": skipped
+                    }),
+                );
+            }
+            Value::Object(map)
+        };
+
+        json!({
+            "tick_batches": self.tick_batches,
+            "total_ticks": self.total_ticks,
+            "avg_ticks_per_batch": if self.tick_batches > 0 { self.total_ticks as f64 / self.tick_batches as f64 } else { 0.0 },
+            "gateway_batches": self.gateway_batches,
+            "avg_gateway_symbols": if self.gateway_batches > 0 { self.gateway_symbols as f64 / self.gateway_batches as f64 } else { 0.0 },
+            "gateway_max_symbols": self.gateway_max_batch,
+            "gateway_lag": lag_snapshot,
+            "gateway_backpressure_drops": self.backpressure_drops,
+            "source_subscribers": self.source_subscribers,
+            "gateway_subscribers": self.gateway_subscribers,
+        })
+    }
 }
 
 async fn process_events(
     mut rx: mpsc::UnboundedReceiver<MetricsEvent>,
+    stats: watch::Sender<GatewayStats>,
     mut shutdown: watch::Receiver<ShutdownSignal>,
+    thresholds: HealthThresholds,
+    mut emit: impl FnMut(Value),
 ) -> Result<()> {
-    let mut tick_batches: usize = 0;
-    let mut total_ticks: usize = 0;
-    let mut gateway_batches: usize = 0;
-    let mut gateway_symbols: usize = 0;
-    let mut gateway_max_batch: usize = 0;
-    let mut gateway_lag: HashMap<&'static str, (usize, usize)> = HashMap::new();
-    let mut backpressure_drops: usize = 0;
-
-    let mut reporter = interval(Duration::from_secs(1));
+    let mut counters = IntervalCounters::default();
+
+    let mut reporter = interval(REPORT_INTERVAL);
     reporter.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
     loop {
         tokio::select! {
             maybe_event = rx.recv() => {
                 match maybe_event {
-                    Some(MetricsEvent::TickBatch { generated }) => {
-                        tick_batches = tick_batches.saturating_add(1);
-                        total_ticks = total_ticks.saturating_add(generated);
-                    }
-                    Some(MetricsEvent::GatewayBatch { symbols }) => {
-                        gateway_batches = gateway_batches.saturating_add(1);
-                        gateway_symbols = gateway_symbols.saturating_add(symbols);
-                        gateway_max_batch = gateway_max_batch.max(symbols);
-                    }
-                    Some(MetricsEvent::GatewayLag { skipped, component }) => {
-                        let entry = gateway_lag.entry(component).or_insert((0, 0));
-                        entry.0 = entry.0.saturating_add(1);
-                        entry.1 = entry.1.saturating_add(skipped);
-                    }
-                    Some(MetricsEvent::GatewayBackpressure { dropped }) => {
-                        backpressure_drops = backpressure_drops.saturating_add(dropped);
-                    }
+                    Some(event) => counters.record(event, &stats),
                     None => break,
                 }
             }
             _ = reporter.tick() => {
-                if tick_batches > 0 || gateway_batches > 0 || !gateway_lag.is_empty() || backpressure_drops > 0 {
-                    let lag_snapshot = if gateway_lag.is_empty() {
-                        Value::Null
-                    } else {
-                        let mut map = Map::new();
-                        for (component, (events, skipped)) in &gateway_lag {
-                            map.insert(
-                                component.to_string(),
-                                json!({
-                                    "events": events,
-                                    "skipped": skipped
-                                }),
-                            );
-                        }
-                        Value::Object(map)
-                    };
-
-                    logging::info(
-                        "metrics.throughput",
-                        "tick throughput summary",
-                        json!({
-                            "tick_batches": tick_batches,
-                            "total_ticks": total_ticks,
-                            "avg_ticks_per_batch": if tick_batches > 0 { total_ticks as f64 / tick_batches as f64 } else { 0.0 },
-                            "gateway_batches": gateway_batches,
-                            "avg_gateway_symbols": if gateway_batches > 0 { gateway_symbols as f64 / gateway_batches as f64 } else { 0.0 },
-                            "gateway_max_symbols": gateway_max_batch,
-                            "gateway_lag": lag_snapshot,
-                            "gateway_backpressure_drops": backpressure_drops,
-                        })
-                    );
+                update_health(&stats, counters.health(REPORT_INTERVAL, thresholds));
+                if let Some(summary) = counters.flush() {
+                    emit(summary);
                 }
-
-                tick_batches = 0;
-                total_ticks = 0;
-                gateway_batches = 0;
-                gateway_symbols = 0;
-                gateway_max_batch = 0;
-                gateway_lag.clear();
-                backpressure_drops = 0;
             }
             changed = shutdown.changed() => {
                 if changed.is_ok() && !matches!(*shutdown.borrow(), ShutdownSignal::None) {
@@ -139,6 +316,163 @@ async fn process_events(
         }
     }
 
+    // report the final partial interval, including events still queued at shutdown
+    while let Ok(event) = rx.try_recv() {
+        counters.record(event, &stats);
+    }
+    if let Some(summary) = counters.flush() {
+        emit(summary);
+    }
+
     logging::info_simple("metrics.stop", "Metrics reporter stopped");
     Ok(())
 }
+
+/// Publish `health`, logging when it changes.
+fn update_health(stats: &watch::Sender<GatewayStats>, health: Health) {
+    let previous = stats.borrow().health;
+    if health == previous {
+        return;
+    }
+    stats.send_modify(|stats| stats.health = health);
+    let metadata = json!({ "health": health.as_str(), "previous": previous.as_str() });
+    if health == Health::Healthy {
+        logging::info("metrics.health", "Pipeline health recovered", metadata);
+    } else {
+        logging::warn("metrics.health", "Pipeline health changed", metadata);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_flushes_the_partial_interval() {
+        let (metrics, rx) = MetricsTx::capture();
+        let (stats_tx, _stats_rx) = watch::channel(GatewayStats::default());
+        let (shutdown_tx, shutdown_rx) = watch::channel(ShutdownSignal::None);
+        let mut summaries = Vec::new();
+
+        let events = async {
+            // let the reporter's immediate first tick pass with empty counters
+            tokio::time::advance(Duration::from_millis(20)).await;
+            metrics.report(MetricsEvent::TickBatch { generated: 500 });
+            metrics.report(MetricsEvent::TickBatch { generated: 250 });
+            metrics.report(MetricsEvent::GatewayBatch { symbols: 40 });
+            let _ = shutdown_tx.send(ShutdownSignal::Graceful);
+        };
+        let started = tokio::time::Instant::now();
+        let (_, result) = tokio::join!(
+            events,
+            process_events(
+                rx,
+                stats_tx,
+                shutdown_rx,
+                HealthThresholds::default(),
+                |summary| summaries.push(summary)
+            )
+        );
+        result.expect("metrics reporter");
+
+        assert!(
+            started.elapsed() < Duration::from_secs(1),
+            "stopped before the next tick"
+        );
+        assert_eq!(summaries.len(), 1, "{summaries:?}");
+        assert_eq!(summaries[0]["tick_batches"], 2);
+        assert_eq!(summaries[0]["total_ticks"], 750);
+        assert_eq!(summaries[0]["gateway_batches"], 1);
+    }
+
+    #[test]
+    fn health_thresholds_map_loss_rates_to_states() {
+        let thresholds = HealthThresholds {
+            degraded_per_sec: 1.0,
+            unhealthy_per_sec: 20.0,
+        };
+        let second = Duration::from_secs(1);
+        assert_eq!(assess_health(0, 0, second, thresholds), Health::Healthy);
+        assert_eq!(
+            assess_health(1, 0, Duration::from_secs(2), thresholds),
+            Health::Healthy
+        );
+        assert_eq!(assess_health(0, 1, second, thresholds), Health::Degraded);
+        assert_eq!(assess_health(12, 7, second, thresholds), Health::Degraded);
+        assert_eq!(assess_health(12, 8, second, thresholds), Health::Unhealthy);
+        assert_eq!(
+            assess_health(5, 5, Duration::ZERO, thresholds),
+            Health::Healthy
+        );
+    }
+
+    #[test]
+    fn invalid_health_thresholds_are_rejected() {
+        assert!(HealthThresholds::default().validate().is_ok());
+        for (degraded_per_sec, unhealthy_per_sec) in [
+            (f64::NAN, 20.0),
+            (1.0, f64::INFINITY),
+            (-1.0, 20.0),
+            (30.0, 20.0),
+        ] {
+            let thresholds = HealthThresholds {
+                degraded_per_sec,
+                unhealthy_per_sec,
+            };
+            assert!(thresholds.validate().is_err(), "{thresholds:?}");
+        }
+    }
+
+    #[test]
+    fn high_drop_rates_turn_health_unhealthy_until_they_stop() {
+        let (stats_tx, stats_rx) = watch::channel(GatewayStats::default());
+        let thresholds = HealthThresholds::default();
+        let mut counters = IntervalCounters::default();
+
+        counters.record(MetricsEvent::GatewayBatch { symbols: 10 }, &stats_tx);
+        update_health(&stats_tx, counters.health(REPORT_INTERVAL, thresholds));
+        assert_eq!(stats_rx.borrow().health, Health::Healthy);
+        counters.flush();
+
+        counters.record(
+            MetricsEvent::GatewayLag {
+                skipped: 2,
+                component: "client",
+            },
+            &stats_tx,
+        );
+        update_health(&stats_tx, counters.health(REPORT_INTERVAL, thresholds));
+        assert_eq!(stats_rx.borrow().health, Health::Degraded);
+        counters.flush();
+
+        for _ in 0..100 {
+            counters.record(MetricsEvent::GatewayBackpressure { dropped: 1 }, &stats_tx);
+        }
+        update_health(&stats_tx, counters.health(REPORT_INTERVAL, thresholds));
+        assert_eq!(stats_rx.borrow().health, Health::Unhealthy);
+        counters.flush();
+
+        update_health(&stats_tx, counters.health(REPORT_INTERVAL, thresholds));
+        assert_eq!(stats_rx.borrow().health, Health::Healthy);
+    }
+
+    #[test]
+    fn idle_intervals_emit_nothing() {
+        let (stats_tx, stats_rx) = watch::channel(GatewayStats::default());
+        let mut counters = IntervalCounters::default();
+        counters.record(
+            MetricsEvent::SubscriberCounts {
+                source: 2,
+                gateway: 1,
+            },
+            &stats_tx,
+        );
+        assert!(counters.flush().is_none());
+
+        counters.record(MetricsEvent::GatewayBatch { symbols: 10 }, &stats_tx);
+        let summary = counters.flush().expect("activity produces a summary");
+        assert_eq!(summary["source_subscribers"], 2);
+        assert_eq!(stats_rx.borrow().batch_count, 1);
+        assert!(counters.flush().is_none(), "flush resets the interval");
+    }
+}
@@ -0,0 +1,68 @@
+pub use market_core::Regime;
+
+/// Parameters for deriving a bid/ask around a mid price.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpreadModel {
+    /// Full calm-regime spread, in basis points of the mid price.
+    pub base_spread_bps: f64,
+    /// Factor applied to the base spread while the market is stressed (1.0 disables widening).
+    pub stress_spread_multiplier: f64,
+}
+
+impl SpreadModel {
+    /// Full spread in basis points for the given regime.
+    pub fn spread_bps(&self, regime: Regime) -> f64 {
+        match regime {
+            Regime::Calm => self.base_spread_bps,
+            Regime::Stressed => self.base_spread_bps * self.stress_spread_multiplier.max(1.0),
+        }
+    }
+
+    /// Bid and ask straddling `mid` symmetrically.
+    pub fn quote(&self, mid: f64, regime: Regime) -> (f64, f64) {
+        let half_spread = mid * self.spread_bps(regime) / 20_000.0;
+        (mid - half_spread, mid + half_spread)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODEL: SpreadModel = SpreadModel {
+        base_spread_bps: 4.0,
+        stress_spread_multiplier: 3.0,
+    };
+
+    fn average_spread(regime: Regime) -> f64 {
+        let mids: Vec<f64> = (0..100).map(|step| 80.0 + step as f64).collect();
+        mids.iter()
+            .map(|&mid| {
+                let (bid, ask) = MODEL.quote(mid, regime);
+                assert!(bid < mid && mid < ask);
+                ask - bid
+            })
+            .sum::<f64>()
+            / mids.len() as f64
+    }
+
+    #[test]
+    fn stressed_regime_widens_average_spread() {
+        let calm = average_spread(Regime::Calm);
+        let stressed = average_spread(Regime::Stressed);
+        assert!(stressed > calm);
+        assert!((stressed / calm - MODEL.stress_spread_multiplier).abs() < 1e-9);
+    }
+
+    #[test]
+    fn multiplier_below_one_never_tightens_spreads() {
+        let model = SpreadModel {
+            stress_spread_multiplier: 0.5,
+            ..MODEL
+        };
+        assert_eq!(
+            model.spread_bps(Regime::Stressed),
+            model.spread_bps(Regime::Calm)
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::calendar::MINUTES_PER_DAY;
+
+/// Range the interval factors are clamped to, so a bad config can neither
+/// spin the generator nor stall it.
+const MIN_FACTOR: f64 = 0.05;
+const MAX_FACTOR: f64 = 20.0;
+/// Shortest interval the schedule ever returns.
+const MIN_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Intraday schedule for the tick interval: ticks come faster around the open
+/// and close of `session` and slower midday, following a U-shaped curve.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TickRamp {
+    /// Session as `(open, close)` minutes of the UTC day, wrapping past
+    /// midnight when open > close. Outside it the base interval applies.
+    pub session: (u32, u32),
+    /// Multiple of the base interval at the open and close (0.25 ticks 4x as often).
+    pub edge_factor: f64,
+    /// Multiple of the base interval at the middle of the session.
+    pub midday_factor: f64,
+}
+
+impl TickRamp {
+    /// Tick interval at `time_of_day` (time since UTC midnight) for a `base`
+    /// interval, always between `base` scaled by the smaller and larger factor
+    /// and never below 1ms.
+    pub fn interval_for(&self, base: Duration, time_of_day: Duration) -> Duration {
+        let edge = clamp_factor(self.edge_factor);
+        let midday = clamp_factor(self.midday_factor);
+        let factor = match self.session_progress(time_of_day) {
+            // 0 at the open and close, 1 midday
+            Some(progress) => {
+                let centred = 2.0 * progress - 1.0;
+                midday + (edge - midday) * centred * centred
+            }
+            None => 1.0,
+        };
+        base.mul_f64(factor).max(MIN_INTERVAL)
+    }
+
+    /// Fraction of the session elapsed at `time_of_day`, or `None` outside it.
+    fn session_progress(&self, time_of_day: Duration) -> Option<f64> {
+        let (open, close) = (f64::from(self.session.0), f64::from(self.session.1));
+        let day = MINUTES_PER_DAY as f64;
+        let minute = (time_of_day.as_secs_f64() / 60.0) % day;
+        let length = (close - open).rem_euclid(day);
+        let elapsed = (minute - open).rem_euclid(day);
+        (length > 0.0 && elapsed < length).then(|| elapsed / length)
+    }
+}
+
+fn clamp_factor(factor: f64) -> f64 {
+    if factor.is_finite() {
+        factor.clamp(MIN_FACTOR, MAX_FACTOR)
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: Duration = Duration::from_millis(8);
+
+    fn at_minute(minute: u64) -> Duration {
+        Duration::from_secs(minute * 60)
+    }
+
+    fn ramp() -> TickRamp {
+        TickRamp {
+            session: (14 * 60 + 30, 21 * 60),
+            edge_factor: 0.25,
+            midday_factor: 2.0,
+        }
+    }
+
+    #[test]
+    fn ticks_speed_up_at_the_open_and_close_and_slow_midday() {
+        let ramp = ramp();
+        let open = ramp.interval_for(BASE, at_minute(14 * 60 + 30));
+        let midday = ramp.interval_for(BASE, at_minute(17 * 60 + 45));
+        let near_close = ramp.interval_for(BASE, at_minute(20 * 60 + 59));
+
+        assert_eq!(open, Duration::from_millis(2));
+        assert_eq!(midday, Duration::from_millis(16));
+        assert!(near_close < Duration::from_millis(3), "{near_close:?}");
+        assert!(ramp.interval_for(BASE, at_minute(16 * 60)) < midday);
+        assert_eq!(ramp.interval_for(BASE, at_minute(21 * 60)), BASE);
+        assert_eq!(ramp.interval_for(BASE, at_minute(3 * 60)), BASE);
+    }
+
+    #[test]
+    fn sessions_can_wrap_past_midnight() {
+        let ramp = TickRamp {
+            session: (22 * 60, 2 * 60),
+            ..ramp()
+        };
+        assert_eq!(
+            ramp.interval_for(BASE, at_minute(0)),
+            Duration::from_millis(16)
+        );
+        assert_eq!(ramp.interval_for(BASE, at_minute(12 * 60)), BASE);
+    }
+
+    #[test]
+    fn intervals_stay_within_the_factor_bounds() {
+        let ramps = [
+            ramp(),
+            TickRamp {
+                edge_factor: 3.0,
+                midday_factor: 0.5,
+                ..ramp()
+            },
+            TickRamp {
+                edge_factor: 0.0,
+                midday_factor: f64::INFINITY,
+                ..ramp()
+            },
+            TickRamp {
+                session: (9 * 60, 9 * 60),
+                ..ramp()
+            },
+        ];
+        for ramp in ramps {
+            let (low, high) = {
+                let (a, b) = (
+                    clamp_factor(ramp.edge_factor),
+                    clamp_factor(ramp.midday_factor),
+                );
+                (a.min(b).min(1.0), a.max(b).max(1.0))
+            };
+            for second in (0..86_400).step_by(37) {
+                let interval = ramp.interval_for(BASE, Duration::from_secs(second));
+                assert!(interval >= MIN_INTERVAL, "{ramp:?} at {second}s");
+                assert!(
+                    interval >= BASE.mul_f64(low).max(MIN_INTERVAL)
+                        && interval <= BASE.mul_f64(high),
+                    "{ramp:?} at {second}s gave {interval:?}"
+                );
+            }
+        }
+    }
+}
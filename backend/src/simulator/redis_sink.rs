@@ -0,0 +1,48 @@
+use anyhow::{Context, Result};
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+
+use super::sink::TickSink;
+
+/// Publishes batch frames to a Redis pub/sub channel.
+pub(super) struct RedisSink {
+    client: redis::Client,
+    channel: String,
+    connection: Option<MultiplexedConnection>,
+}
+
+impl RedisSink {
+    /// Only a malformed `url` fails here; the server is contacted on connect.
+    pub(super) fn new(url: &str, channel: String) -> Result<Self> {
+        let client = redis::Client::open(url).context("parse redis_url")?;
+        Ok(Self {
+            client,
+            channel,
+            connection: None,
+        })
+    }
+}
+
+impl TickSink for RedisSink {
+    const NAME: &'static str = "redis";
+
+    fn target(&self) -> &str {
+        &self.channel
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        self.connection = Some(self.client.get_multiplexed_async_connection().await?);
+        Ok(())
+    }
+
+    async fn publish(&mut self, payload: String) -> Result<()> {
+        let connection = self.connection.as_mut().context("not connected")?;
+        let published = connection
+            .publish::<_, _, ()>(self.channel.as_str(), payload)
+            .await;
+        if published.is_err() {
+            self.connection = None;
+        }
+        Ok(published?)
+    }
+}
@@ -0,0 +1,569 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use nalgebra::{DMatrix, DVector};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use serde::de::DeserializeOwned;
+
+use crate::constants::{CORRELATED_INIT_BASE_PRICE, CORRELATED_INIT_DISPERSION};
+use crate::model::{equities_with_separator, Equity, Region, Sector, Symbol};
+
+use super::universe::{self, MarketFactor, StockUniverse};
+use super::SimulatorConfig;
+
+pub const UNIVERSE_FILE: &str = "universe.json";
+pub const CORRELATION_FILE: &str = "correlation.csv";
+pub const SEED_FILE: &str = "seed.txt";
+
+/// Independent random streams derived from a single seed, so loading the
+/// correlation from disk does not shift the draws used for prices or ticks.
+#[derive(Clone, Copy)]
+pub(super) enum RngStream {
+    Universe = 0,
+    Prices = 1,
+    Generator = 2,
+    Correlation = 3,
+    Trades = 4,
+}
+
+pub(super) fn seeded_rng(seed: Option<u64>, stream: RngStream) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(stream as u64)),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Build the starting universe and prices described by the config's seed and scenario files.
+pub(super) fn build_universe(config: &SimulatorConfig) -> Result<(StockUniverse, Vec<f64>)> {
+    let equities = match &config.universe_path {
+        Some(path) => load_equities(path)?,
+        None => {
+            equities_with_separator(config.symbol_separator).context("invalid symbol separator")?
+        }
+    };
+    let equities = select_equities(config, equities)?;
+
+    if let Some(cap) = config.max_correlation {
+        universe::check_max_correlation(cap)?;
+    }
+    universe::check_drift_persistence(config.correlation_drift_persistence)?;
+    let market =
+        (config.market_index.is_some() || !config.market_betas.is_empty()).then(|| MarketFactor {
+            index: config.market_index.clone(),
+            betas: config.market_betas.clone(),
+        });
+    let universe = match &config.correlation_path {
+        Some(_) if market.is_some() => {
+            bail!("market_index and market_betas need a generated correlation structure, not correlation_path")
+        }
+        Some(path) => {
+            let mut correlation = read_correlation_csv(path, &equities)?;
+            // cap before factoring, so a file with duplicated symbols still loads
+            if let Some(cap) = config.max_correlation {
+                correlation = universe::cap_correlations(correlation, cap);
+            }
+            StockUniverse::from_correlation(equities, correlation, &config.sector_correlation)?
+        }
+        None => {
+            let mut rng = match config.scenario_id {
+                Some(scenario_id) => universe::scenario_rng(scenario_id),
+                None => seeded_rng(config.seed, RngStream::Universe),
+            };
+            let universe = StockUniverse::new(equities, &config.sector_correlation, &mut rng)?;
+            match market {
+                Some(market) => universe.with_market_factor(market, &mut rng)?,
+                None => universe,
+            }
+        }
+    };
+    let universe = match config.max_correlation {
+        Some(cap) => universe.with_max_correlation(cap)?,
+        None => universe,
+    };
+
+    let initial_prices = initial_prices(
+        config,
+        &universe,
+        &mut seeded_rng(config.seed, RngStream::Prices),
+    );
+
+    Ok((universe, initial_prices))
+}
+
+/// Starting prices: flat or i.i.d. uniform by default, or one correlated
+/// log-normal draw around a base price when `correlated_init` is set.
+fn initial_prices(
+    config: &SimulatorConfig,
+    universe: &StockUniverse,
+    rng: &mut StdRng,
+) -> Vec<f64> {
+    let size = universe.equities().len();
+    if !config.correlated_init {
+        return (0..size)
+            .map(|_| {
+                config
+                    .initial_price
+                    .unwrap_or_else(|| rng.gen_range(80.0..150.0))
+            })
+            .collect();
+    }
+
+    let base = config.initial_price.unwrap_or(CORRELATED_INIT_BASE_PRICE);
+    let draws = DVector::from_fn(size, |_, _| rng.sample::<f64, _>(StandardNormal));
+    (universe.cholesky() * draws)
+        .iter()
+        .map(|shock| base * (CORRELATED_INIT_DISPERSION * shock).exp())
+        .collect()
+}
+
+/// Apply the config's region and sector filters, refusing to continue with no equities
+/// since an empty universe has no correlation matrix to factor.
+pub(super) fn select_equities(
+    config: &SimulatorConfig,
+    equities: Vec<Equity>,
+) -> Result<Vec<Equity>> {
+    let equities = restrict_buckets(
+        equities,
+        config.regions.as_deref(),
+        config.sectors.as_deref(),
+    )?;
+    if equities.is_empty() {
+        bail!(
+            "universe is empty after filters (regions {:?}, sectors {:?})",
+            config.regions,
+            config.sectors
+        );
+    }
+    Ok(equities)
+}
+
+/// Keep only equities in the selected regions and sectors; `None` keeps every bucket.
+fn restrict_buckets(
+    equities: Vec<Equity>,
+    regions: Option<&[Region]>,
+    sectors: Option<&[Sector]>,
+) -> Result<Vec<Equity>> {
+    if regions.is_some_and(<[Region]>::is_empty) {
+        bail!("region subset must not be empty");
+    }
+    if sectors.is_some_and(<[Sector]>::is_empty) {
+        bail!("sector subset must not be empty");
+    }
+    let keep = |equity: &Equity| {
+        let region_ok = match regions {
+            Some(regions) => regions.contains(&equity.region),
+            None => true,
+        };
+        let sector_ok = match sectors {
+            Some(sectors) => sectors.contains(&equity.sector),
+            None => true,
+        };
+        region_ok && sector_ok
+    };
+    Ok(equities.into_iter().filter(keep).collect())
+}
+
+/// Files written by [`export_bundle`].
+#[derive(Debug, Clone)]
+pub struct ScenarioBundle {
+    pub seed: u64,
+    pub universe_path: PathBuf,
+    pub correlation_path: PathBuf,
+    pub seed_path: PathBuf,
+}
+
+/// Write the universe, initial correlation matrix, and seed so another run can reproduce them.
+///
+/// A random seed is chosen and recorded when the config does not specify one.
+pub fn export_bundle(config: &SimulatorConfig, out_dir: &Path) -> Result<ScenarioBundle> {
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let config = SimulatorConfig {
+        seed: Some(seed),
+        ..config.clone()
+    };
+    let (universe, _) = build_universe(&config)?;
+
+    fs::create_dir_all(out_dir)
+        .with_context(|| format!("failed to create export directory {:?}", out_dir))?;
+
+    let bundle = ScenarioBundle {
+        seed,
+        universe_path: out_dir.join(UNIVERSE_FILE),
+        correlation_path: out_dir.join(CORRELATION_FILE),
+        seed_path: out_dir.join(SEED_FILE),
+    };
+
+    let universe_json =
+        serde_json::to_string_pretty(universe.equities()).context("serialize equity universe")?;
+    write_file(&bundle.universe_path, &universe_json)?;
+    write_file(
+        &bundle.correlation_path,
+        &correlation_csv(universe.equities(), universe.correlation_matrix()),
+    )?;
+    write_file(&bundle.seed_path, &format!("{seed}\n"))?;
+
+    Ok(bundle)
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).with_context(|| format!("failed to write {:?}", path))
+}
+
+/// Read an equity universe: a JSON array as written by `export`, or for a
+/// `.csv` path, `symbol,region,sector` rows under that header. Symbols must be
+/// valid and unique; errors name the offending entry or line.
+pub(super) fn load_equities(path: &Path) -> Result<Vec<Equity>> {
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("failed to read universe {:?}", path))?;
+    let is_csv = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+    let equities = if is_csv {
+        parse_equities_csv(&raw)
+    } else {
+        parse_equities_json(&raw)
+    }
+    .with_context(|| format!("failed to parse universe {:?}", path))?;
+    if equities.is_empty() {
+        bail!("universe {:?} contains no equities", path);
+    }
+    Ok(equities)
+}
+
+fn parse_equities_json(raw: &str) -> Result<Vec<Equity>> {
+    let entries: Vec<serde_json::Value> =
+        serde_json::from_str(raw).context("expected a JSON array of equities")?;
+    let mut universe = UniverseRows::default();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let row = format!("entry {}", index + 1);
+        let equity = serde_json::from_value(entry).with_context(|| format!("{row}: bad equity"))?;
+        universe.push(equity, row)?;
+    }
+    Ok(universe.equities)
+}
+
+fn parse_equities_csv(raw: &str) -> Result<Vec<Equity>> {
+    let mut lines = raw
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let (_, header) = lines.next().context("missing header row")?;
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    if columns != ["symbol", "region", "sector"] {
+        bail!(
+            "header must be `symbol,region,sector`, found `{}`",
+            header.trim()
+        );
+    }
+
+    let mut universe = UniverseRows::default();
+    for (index, line) in lines {
+        let row = format!("line {}", index + 1);
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let [symbol, region, sector] = fields[..] else {
+            bail!("{row}: expected 3 columns, found {}", fields.len());
+        };
+        let equity = Equity {
+            symbol: parse_field(symbol).with_context(|| format!("{row}: bad symbol"))?,
+            region: parse_field(region).with_context(|| format!("{row}: bad region"))?,
+            sector: parse_field(sector).with_context(|| format!("{row}: bad sector"))?,
+        };
+        universe.push(equity, row)?;
+    }
+    Ok(universe.equities)
+}
+
+/// Read a CSV field through its serde representation (snake_case for enums),
+/// so both file formats accept exactly the same values.
+fn parse_field<T: DeserializeOwned>(value: &str) -> Result<T> {
+    Ok(serde_json::from_value(serde_json::Value::String(
+        value.to_string(),
+    ))?)
+}
+
+/// Equities read so far, with the row each symbol came from.
+#[derive(Default)]
+struct UniverseRows {
+    equities: Vec<Equity>,
+    rows: HashMap<Symbol, String>,
+}
+
+impl UniverseRows {
+    fn push(&mut self, equity: Equity, row: String) -> Result<()> {
+        if let Some(first) = self.rows.get(&equity.symbol) {
+            bail!(
+                "{row}: duplicate symbol {} (first listed at {first})",
+                equity.symbol
+            );
+        }
+        self.rows.insert(equity.symbol.clone(), row);
+        self.equities.push(equity);
+        Ok(())
+    }
+}
+
+/// Render the matrix as CSV with a header row of symbols; values round-trip exactly.
+fn correlation_csv(equities: &[Equity], matrix: &DMatrix<f64>) -> String {
+    let mut csv = equities
+        .iter()
+        .map(|equity| equity.symbol.as_str())
+        .collect::<Vec<_>>()
+        .join(",");
+    csv.push('\n');
+    for row in matrix.row_iter() {
+        let values: Vec<String> = row.iter().map(|value| value.to_string()).collect();
+        let _ = writeln!(csv, "{}", values.join(","));
+    }
+    csv
+}
+
+fn read_correlation_csv(path: &Path, equities: &[Equity]) -> Result<DMatrix<f64>> {
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("failed to read correlation matrix {:?}", path))?;
+    parse_correlation_csv(&raw, equities)
+        .with_context(|| format!("invalid correlation matrix {:?}", path))
+}
+
+fn parse_correlation_csv(raw: &str, equities: &[Equity]) -> Result<DMatrix<f64>> {
+    let mut lines = raw.lines().filter(|line| !line.trim().is_empty());
+    let header: Vec<&str> = lines
+        .next()
+        .context("missing symbol header row")?
+        .split(',')
+        .map(str::trim)
+        .collect();
+    let expected: Vec<&str> = equities
+        .iter()
+        .map(|equity| equity.symbol.as_str())
+        .collect();
+    if header != expected {
+        bail!("header symbols do not match the universe");
+    }
+
+    let size = equities.len();
+    let mut values = Vec::with_capacity(size * size);
+    for (row, line) in lines.enumerate() {
+        let parsed = line
+            .split(',')
+            .map(|value| value.trim().parse::<f64>())
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("row {row} contains a non-numeric value"))?;
+        if parsed.len() != size {
+            bail!("row {row} has {} columns, expected {size}", parsed.len());
+        }
+        values.extend(parsed);
+    }
+    if values.len() != size * size {
+        bail!("expected {size} rows, found {}", values.len() / size);
+    }
+
+    Ok(DMatrix::from_row_slice(size, size, &values))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::super::gateway;
+    use super::*;
+
+    #[test]
+    fn flat_start_overrides_random_prices() {
+        let config = SimulatorConfig {
+            initial_price: Some(100.0),
+            seed: Some(1),
+            ..SimulatorConfig::default()
+        };
+        let (universe, prices) = build_universe(&config).expect("universe");
+        assert_eq!(prices.len(), universe.equities().len());
+        assert!(prices.iter().all(|&price| price == 100.0));
+    }
+
+    /// Mean absolute gap between the target correlation and the sample
+    /// correlation of log starting prices across repeated seedings.
+    fn initial_correlation_error(correlated_init: bool) -> f64 {
+        let config = SimulatorConfig {
+            correlated_init,
+            regions: Some(vec![Region::NorthAmerica, Region::Europe]),
+            sectors: Some(vec![Sector::Technology, Sector::Financials]),
+            seed: Some(7),
+            ..SimulatorConfig::default()
+        };
+        let (universe, _) = build_universe(&config).expect("universe");
+        let mut rng = StdRng::seed_from_u64(11);
+        let samples: Vec<Vec<f64>> = (0..400)
+            .map(|_| {
+                initial_prices(&config, &universe, &mut rng)
+                    .into_iter()
+                    .map(f64::ln)
+                    .collect()
+            })
+            .collect();
+
+        let size = universe.equities().len();
+        let column = |i: usize| samples.iter().map(move |sample| sample[i]);
+        let mean = |i: usize| column(i).sum::<f64>() / samples.len() as f64;
+        let means: Vec<f64> = (0..size).map(mean).collect();
+        let std = |i: usize| {
+            column(i)
+                .map(|value| (value - means[i]).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        };
+        let stds: Vec<f64> = (0..size).map(std).collect();
+
+        let target = universe.correlation_matrix();
+        let mut error = 0.0;
+        let mut pairs = 0;
+        for i in 0..size {
+            for j in (i + 1)..size {
+                let covariance: f64 = column(i)
+                    .zip(column(j))
+                    .map(|(a, b)| (a - means[i]) * (b - means[j]))
+                    .sum();
+                error += (covariance / (stds[i] * stds[j]) - target[(i, j)]).abs();
+                pairs += 1;
+            }
+        }
+        error / pairs as f64
+    }
+
+    #[test]
+    fn correlated_init_reflects_the_correlation_matrix() {
+        let correlated = initial_correlation_error(true);
+        let independent = initial_correlation_error(false);
+        assert!(
+            correlated * 2.0 < independent,
+            "correlated {correlated:.3} vs i.i.d. {independent:.3}"
+        );
+    }
+
+    #[test]
+    fn correlated_init_centres_on_the_initial_price() {
+        let config = SimulatorConfig {
+            correlated_init: true,
+            initial_price: Some(50.0),
+            seed: Some(3),
+            ..SimulatorConfig::default()
+        };
+        let (_, prices) = build_universe(&config).expect("universe");
+        assert!(prices.iter().all(|price| price.is_finite() && *price > 0.0));
+        let log_mean = prices.iter().map(|price| price.ln()).sum::<f64>() / prices.len() as f64;
+        // the shared market factor moves the whole cross-section together, so
+        // allow a couple of sigma of drift while staying well clear of the 115 base
+        assert!((log_mean - 50f64.ln()).abs() < 0.4, "log mean {log_mean}");
+    }
+
+    #[test]
+    fn bucket_subset_restricts_the_generated_universe() {
+        let regions = [Region::NorthAmerica, Region::Europe];
+        let sectors = [Sector::Technology, Sector::Financials];
+        let config = SimulatorConfig {
+            regions: Some(regions.to_vec()),
+            sectors: Some(sectors.to_vec()),
+            seed: Some(1),
+            ..SimulatorConfig::default()
+        };
+        let (universe, prices) = build_universe(&config).expect("universe");
+
+        // 2 regions × 2 sectors × 10 replicas
+        assert_eq!(universe.equities().len(), 40);
+        assert_eq!(prices.len(), 40);
+        assert_eq!(universe.correlation_matrix().nrows(), 40);
+        for equity in universe.equities() {
+            assert!(regions.contains(&equity.region), "{}", equity.symbol);
+            assert!(sectors.contains(&equity.sector), "{}", equity.symbol);
+        }
+
+        let meta = gateway::meta_message(universe.equities().to_vec(), Duration::ZERO, false);
+        assert_eq!(meta.regions, regions);
+        assert_eq!(meta.sectors, sectors);
+    }
+
+    #[test]
+    fn empty_bucket_subsets_are_rejected() {
+        for (regions, sectors) in [(Some(Vec::new()), None), (None, Some(Vec::new()))] {
+            let config = SimulatorConfig {
+                regions,
+                sectors,
+                ..SimulatorConfig::default()
+            };
+            assert!(build_universe(&config).is_err());
+        }
+    }
+
+    fn equities() -> Vec<Equity> {
+        ["A", "B"]
+            .into_iter()
+            .map(|symbol| Equity {
+                symbol: symbol.parse().unwrap(),
+                region: Region::Europe,
+                sector: Sector::Energy,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn correlation_csv_round_trips_exactly() {
+        let third = 1.0 / 3.0;
+        let matrix = DMatrix::from_row_slice(2, 2, &[1.0, third, third, 1.0]);
+        let csv = correlation_csv(&equities(), &matrix);
+        assert!(csv.starts_with("A,B\n"));
+        assert_eq!(parse_correlation_csv(&csv, &equities()).unwrap(), matrix);
+    }
+
+    #[test]
+    fn correlation_csv_rejects_mismatched_shapes() {
+        assert!(parse_correlation_csv("A,C\n1,0\n0,1\n", &equities()).is_err());
+        assert!(parse_correlation_csv("A,B\n1,0\n", &equities()).is_err());
+        assert!(parse_correlation_csv("A,B\n1,0,0\n0,1\n", &equities()).is_err());
+    }
+
+    #[test]
+    fn csv_universes_load_like_json_ones() {
+        let path = std::env::temp_dir().join(format!("rmd-universe-{}.csv", std::process::id()));
+        fs::write(
+            &path,
+            "symbol,region,sector\nA, europe ,energy\n\nB,europe,energy\n",
+        )
+        .unwrap();
+        let loaded = load_equities(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded.expect("csv universe"), equities());
+        let json = serde_json::to_string(&equities()).unwrap();
+        assert_eq!(parse_equities_json(&json).unwrap(), equities());
+    }
+
+    #[test]
+    fn invalid_universe_rows_are_named_in_the_error() {
+        let error = |result: Result<Vec<Equity>>| format!("{:#}", result.unwrap_err());
+
+        let csv = "symbol,region,sector\nA,europe,energy\nB,mars,energy\n";
+        assert!(error(parse_equities_csv(csv)).starts_with("line 3: bad region"));
+        let csv = "symbol,region,sector\nA,europe,energy\nA,europe,energy\n";
+        assert_eq!(
+            error(parse_equities_csv(csv)),
+            "line 3: duplicate symbol A (first listed at line 2)"
+        );
+        let csv = "symbol,region,sector\n ,europe,energy\n";
+        let err = error(parse_equities_csv(csv));
+        assert!(err.starts_with("line 2: bad symbol"), "{err}");
+        assert!(err.contains("symbol is empty"), "{err}");
+        assert!(error(parse_equities_csv("A,europe,energy\n")).contains("header"));
+        assert!(error(parse_equities_csv("symbol,region,sector\nA,europe\n")).contains("line 2"));
+
+        let json = r#"[{"symbol":"A","region":"europe","sector":"energy"},
+                       {"symbol":"","region":"europe","sector":"energy"}]"#;
+        let err = error(parse_equities_json(json));
+        assert!(err.starts_with("entry 2: bad equity"), "{err}");
+        assert!(err.contains("symbol is empty"), "{err}");
+        let json = r#"[{"symbol":"A","region":"europe","sector":"oil"}]"#;
+        assert!(error(parse_equities_json(json)).starts_with("entry 1: bad equity"));
+    }
+}
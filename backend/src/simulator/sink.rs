@@ -0,0 +1,108 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::json;
+use tokio::sync::{broadcast, watch};
+
+use crate::{constants::REDIS_RETRY_MS, logging};
+
+use super::gateway::{encode_batch, GatewayBatch};
+use super::{ShutdownSignal, SimulatorConfig};
+
+/// External destination for gateway batch frames, such as a Redis channel.
+pub(super) trait TickSink {
+    /// Prefix of the sink's log events, e.g. `redis.connect.failed`.
+    const NAME: &'static str;
+
+    /// Where frames go, for log metadata.
+    fn target(&self) -> &str;
+
+    /// Open a connection; called again after any connect or publish failure.
+    async fn connect(&mut self) -> Result<()>;
+
+    /// Publish one encoded batch frame on the open connection.
+    async fn publish(&mut self, payload: String) -> Result<()>;
+}
+
+/// Publish every gateway batch to `sink`, encoded exactly like the `/ws`
+/// batch frames.
+///
+/// Connection and publish failures are logged and retried every
+/// `REDIS_RETRY_MS`; batches produced while the sink is unreachable are
+/// dropped.
+pub(super) async fn run_tick_sink<S: TickSink>(
+    mut sink: S,
+    config: Arc<SimulatorConfig>,
+    mut batches: broadcast::Receiver<GatewayBatch>,
+    mut shutdown: watch::Receiver<ShutdownSignal>,
+) -> Result<()> {
+    let retry = Duration::from_millis(REDIS_RETRY_MS);
+    let mut connected = false;
+
+    loop {
+        if !connected {
+            match sink.connect().await {
+                Ok(()) => {
+                    logging::info(
+                        &format!("{}.connected", S::NAME),
+                        "Publishing gateway batches to the sink",
+                        json!({ "target": sink.target() }),
+                    );
+                    connected = true;
+                }
+                Err(err) => {
+                    logging::warn(
+                        &format!("{}.connect.failed", S::NAME),
+                        "Could not connect to the sink; retrying",
+                        json!({ "error": format!("{err:#}"), "retry_ms": REDIS_RETRY_MS }),
+                    );
+                    tokio::select! {
+                        _ = tokio::time::sleep(retry) => continue,
+                        changed = shutdown.changed() => {
+                            if changed.is_err() || !matches!(*shutdown.borrow(), ShutdownSignal::None) {
+                                break;
+                            }
+                            continue;
+                        }
+                    }
+                }
+            }
+        }
+
+        let batch = tokio::select! {
+            batch = batches.recv() => batch,
+            changed = shutdown.changed() => {
+                if changed.is_err() || !matches!(*shutdown.borrow(), ShutdownSignal::None) {
+                    break;
+                }
+                continue;
+            }
+        };
+        let batch = match batch {
+            Ok(batch) if batch.is_empty() => continue,
+            Ok(batch) => batch,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                logging::warn(
+                    &format!("{}.lagged", S::NAME),
+                    "Sink publisher fell behind gateway batches",
+                    json!({ "skipped": skipped }),
+                );
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+
+        let payload = encode_batch(batch, config.compact_keys, config.wire_decimals)?;
+        if let Err(err) = sink.publish(payload).await {
+            logging::warn(
+                &format!("{}.publish.failed", S::NAME),
+                "Failed to publish batch to the sink; reconnecting",
+                json!({ "error": format!("{err:#}") }),
+            );
+            connected = false;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use market_core::{NoticeCode, NoticeMessage};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Equity, Symbol};
+
+/// What the gateway does with a `?symbols=` subscription naming more symbols
+/// than `max_client_symbols`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolOverflow {
+    /// Keep the first symbols in request order and send a notice frame listing
+    /// the ones dropped.
+    #[default]
+    Truncate,
+    /// Refuse the websocket upgrade with 400 Bad Request.
+    Reject,
+}
+
+/// Symbols a `/ws` client asked for, checked against the universe and the
+/// per-client limit.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Subscription {
+    symbols: HashSet<Symbol>,
+    /// Frames telling the client what was dropped from its request.
+    notices: Vec<NoticeMessage>,
+}
+
+/// A subscription refused under [`SymbolOverflow::Reject`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct TooManySymbols {
+    pub requested: usize,
+    pub limit: usize,
+}
+
+impl fmt::Display for TooManySymbols {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "subscription names {} symbols; the limit is {}",
+            self.requested, self.limit
+        )
+    }
+}
+
+impl Subscription {
+    /// Resolve a comma-separated `requested` list against `universe`.
+    ///
+    /// Blanks and repeats are ignored and unknown symbols are dropped with a
+    /// notice. The limit applies to the known symbols that remain.
+    pub(super) fn resolve(
+        requested: &str,
+        universe: &[Equity],
+        limit: Option<usize>,
+        overflow: SymbolOverflow,
+    ) -> Result<Self, TooManySymbols> {
+        let known: HashMap<&str, &Symbol> = universe
+            .iter()
+            .map(|equity| (equity.symbol.as_str(), &equity.symbol))
+            .collect();
+        let mut seen = HashSet::new();
+        let (mut symbols, unknown): (Vec<&str>, Vec<&str>) = requested
+            .split(',')
+            .map(str::trim)
+            .filter(|symbol| !symbol.is_empty() && seen.insert(*symbol))
+            .partition(|symbol| known.contains_key(symbol));
+
+        let mut notices = Vec::new();
+        if !unknown.is_empty() {
+            notices.push(NoticeMessage {
+                code: NoticeCode::UnknownSymbols,
+                message: format!("ignored {} symbols not in the universe", unknown.len()),
+                symbols: unknown.into_iter().map(str::to_string).collect(),
+            });
+        }
+        if let Some(limit) = limit.filter(|&limit| symbols.len() > limit) {
+            if overflow == SymbolOverflow::Reject {
+                return Err(TooManySymbols {
+                    requested: symbols.len(),
+                    limit,
+                });
+            }
+            let dropped = symbols.split_off(limit);
+            notices.push(NoticeMessage {
+                code: NoticeCode::SubscriptionTruncated,
+                message: format!(
+                    "subscription is limited to {limit} symbols; dropped {}",
+                    dropped.len()
+                ),
+                symbols: dropped.into_iter().map(str::to_string).collect(),
+            });
+        }
+
+        let symbols = symbols
+            .into_iter()
+            .map(|symbol| known[symbol].clone())
+            .collect();
+        Ok(Self { symbols, notices })
+    }
+
+    pub(super) fn contains(&self, symbol: &str) -> bool {
+        self.symbols.contains(symbol)
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub(super) fn notices(&self) -> &[NoticeMessage] {
+        &self.notices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::default_equities;
+
+    #[test]
+    fn unknown_symbols_are_dropped_with_a_notice() {
+        let subscription = Subscription::resolve(
+            "NATECH000, BOGUS,NATECH000,,EUENG001",
+            &default_equities(),
+            None,
+            SymbolOverflow::Reject,
+        )
+        .expect("subscription");
+
+        assert_eq!(subscription.len(), 2);
+        assert!(subscription.contains("NATECH000") && subscription.contains("EUENG001"));
+        assert_eq!(subscription.notices().len(), 1);
+        assert_eq!(subscription.notices()[0].code, NoticeCode::UnknownSymbols);
+        assert_eq!(subscription.notices()[0].symbols, vec!["BOGUS"]);
+    }
+
+    #[test]
+    fn oversized_subscriptions_are_truncated_or_rejected() {
+        let requested = "NATECH000,NATECH001,BOGUS,NATECH002,NATECH003";
+
+        let truncated = Subscription::resolve(
+            requested,
+            &default_equities(),
+            Some(2),
+            SymbolOverflow::Truncate,
+        )
+        .expect("truncated subscription");
+        assert_eq!(truncated.len(), 2);
+        assert!(truncated.contains("NATECH000") && truncated.contains("NATECH001"));
+        let notice = &truncated.notices()[1];
+        assert_eq!(notice.code, NoticeCode::SubscriptionTruncated);
+        assert_eq!(notice.symbols, vec!["NATECH002", "NATECH003"]);
+
+        let rejected = Subscription::resolve(
+            requested,
+            &default_equities(),
+            Some(2),
+            SymbolOverflow::Reject,
+        );
+        assert_eq!(
+            rejected,
+            Err(TooManySymbols {
+                requested: 4,
+                limit: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use market_core::{Side, Trade};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use crate::tick::Tick;
+
+use super::{Regime, SpreadModel};
+
+/// Prints produced by one generator iteration, broadcast to `/ws/trades` clients.
+pub(super) type TradeBatch = Vec<Trade>;
+
+/// Shares per round lot; print sizes are whole lots.
+const ROUND_LOT: u32 = 100;
+/// Largest print, in round lots.
+const MAX_LOTS: u32 = 10;
+
+/// Chance that a symbol prints during one tick when prints arrive as a
+/// Poisson process of `rate_per_sec`.
+pub(super) fn arrival_probability(rate_per_sec: f64, tick_interval: Duration) -> f64 {
+    if !rate_per_sec.is_finite() || rate_per_sec <= 0.0 {
+        return 0.0;
+    }
+    1.0 - (-rate_per_sec * tick_interval.as_secs_f64()).exp()
+}
+
+/// Draws time-and-sales prints from freshly generated ticks.
+pub(super) struct TradeGenerator {
+    rate_per_sec: f64,
+    spread: SpreadModel,
+    rng: StdRng,
+}
+
+impl TradeGenerator {
+    /// `None` when the rate is not positive, so callers skip the feed entirely.
+    pub(super) fn new(rate_per_sec: f64, spread: SpreadModel, rng: StdRng) -> Option<Self> {
+        (rate_per_sec.is_finite() && rate_per_sec > 0.0).then_some(Self {
+            rate_per_sec,
+            spread,
+            rng,
+        })
+    }
+
+    /// Prints for this tick batch, which covers `tick_interval` of the feed so
+    /// a ramped cadence keeps the configured rate. Buyer-initiated prints land
+    /// between the mid and the ask, seller-initiated ones between the bid and
+    /// the mid; the quote widens with the current `regime`.
+    pub(super) fn prints(
+        &mut self,
+        ticks: &[Tick],
+        regime: Regime,
+        tick_interval: Duration,
+    ) -> TradeBatch {
+        let probability = arrival_probability(self.rate_per_sec, tick_interval);
+        let mut trades = Vec::new();
+        for tick in ticks {
+            if !self.rng.gen_bool(probability) {
+                continue;
+            }
+            let mid = tick.price;
+            let (bid, ask) = self.spread.quote(mid, regime);
+            let aggressor_side = if self.rng.gen_bool(0.5) {
+                Side::Buy
+            } else {
+                Side::Sell
+            };
+            let depth: f64 = self.rng.gen_range(0.0..=1.0);
+            let price = match aggressor_side {
+                Side::Buy => mid + (ask - mid) * depth,
+                Side::Sell => mid - (mid - bid) * depth,
+            };
+            trades.push(Trade {
+                symbol: tick.symbol.clone(),
+                price: price.clamp(bid, ask),
+                size: ROUND_LOT * self.rng.gen_range(1..=MAX_LOTS),
+                timestamp_ms: tick.timestamp_ms,
+                aggressor_side,
+                bid,
+                ask,
+            });
+        }
+        trades
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Region, Sector, Symbol};
+    use rand::SeedableRng;
+
+    const SPREAD: SpreadModel = SpreadModel {
+        base_spread_bps: 4.0,
+        stress_spread_multiplier: 3.0,
+    };
+    const TICK: Duration = Duration::from_millis(8);
+
+    fn ticks(count: usize) -> Vec<Tick> {
+        (0..count)
+            .map(|idx| Tick {
+                symbol: Symbol::new(format!("EUENG{idx:03}")).unwrap(),
+                price: 50.0 + idx as f64,
+                timestamp_ms: 1_716_400_000_000 + idx as u64,
+                region: Region::Europe,
+                sector: Sector::Energy,
+                prev_close: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn arrival_probability_follows_the_rate() {
+        let tick = Duration::from_millis(8);
+        assert_eq!(arrival_probability(0.0, tick), 0.0);
+        assert_eq!(arrival_probability(f64::NAN, tick), 0.0);
+        let probability = arrival_probability(2.0, tick);
+        assert!((probability - 0.016).abs() < 1e-3, "{probability}");
+        assert!(TradeGenerator::new(0.0, SPREAD, StdRng::seed_from_u64(1)).is_none());
+    }
+
+    #[test]
+    fn print_rate_follows_the_interval_each_batch_covers() {
+        let mut generator =
+            TradeGenerator::new(5.0, SPREAD, StdRng::seed_from_u64(4)).expect("enabled");
+        let ticks = ticks(1_000);
+        let short = generator.prints(&ticks, Regime::Calm, TICK).len();
+        let long = generator.prints(&ticks, Regime::Calm, TICK * 8).len();
+        // about 40 and 270 prints; a quarter of the latter leaves ample margin
+        assert!(long > short * 4, "{short} vs {long}");
+    }
+
+    #[test]
+    fn prints_stay_inside_the_quote_on_the_aggressor_side() {
+        // a rate this high prints every symbol every tick
+        let mut generator =
+            TradeGenerator::new(1e6, SPREAD, StdRng::seed_from_u64(9)).expect("enabled");
+        let ticks = ticks(200);
+        let trades = generator.prints(&ticks, Regime::Calm, TICK);
+        assert_eq!(trades.len(), ticks.len());
+
+        for (trade, tick) in trades.iter().zip(&ticks) {
+            assert_eq!(trade.symbol, tick.symbol);
+            assert_eq!(trade.timestamp_ms, tick.timestamp_ms);
+            assert!(trade.within_spread(), "{trade:?}");
+            match trade.aggressor_side {
+                Side::Buy => assert!(trade.price >= tick.price),
+                Side::Sell => assert!(trade.price <= tick.price),
+            }
+            assert_eq!(trade.size % ROUND_LOT, 0);
+            assert!((ROUND_LOT..=ROUND_LOT * MAX_LOTS).contains(&trade.size));
+        }
+        assert!(trades.iter().any(|trade| trade.aggressor_side == Side::Buy));
+        assert!(trades
+            .iter()
+            .any(|trade| trade.aggressor_side == Side::Sell));
+    }
+
+    #[test]
+    fn stressed_prints_quote_a_wider_spread() {
+        let mut generator =
+            TradeGenerator::new(1e6, SPREAD, StdRng::seed_from_u64(9)).expect("enabled");
+        let ticks = ticks(20);
+        let calm = generator.prints(&ticks, Regime::Calm, TICK);
+        let stressed = generator.prints(&ticks, Regime::Stressed, TICK);
+
+        for (calm, stressed) in calm.iter().zip(&stressed) {
+            let ratio = (stressed.ask - stressed.bid) / (calm.ask - calm.bid);
+            assert!(
+                (ratio - SPREAD.stress_spread_multiplier).abs() < 1e-9,
+                "{ratio}"
+            );
+            assert!(stressed.within_spread(), "{stressed:?}");
+        }
+    }
+}
@@ -1,66 +1,546 @@
-use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
 use nalgebra::{Cholesky, DMatrix, DVector};
 use rand::rngs::StdRng;
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::model::{Equity, Region, Sector, Symbol};
+
+/// Explicit sector-to-sector correlation applied to the sector factor loadings.
+///
+/// Defaults to the identity, meaning sectors only co-move through the global beta.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SectorCorrelation {
+    matrix: DMatrix<f64>,
+}
+
+impl Default for SectorCorrelation {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl SectorCorrelation {
+    pub fn identity() -> Self {
+        Self {
+            matrix: DMatrix::identity(Sector::ALL.len(), Sector::ALL.len()),
+        }
+    }
+
+    /// Set the correlation between two distinct sectors, clamped to (-1, 1).
+    pub fn with_pair(mut self, a: Sector, b: Sector, correlation: f64) -> Self {
+        if a != b {
+            let value = correlation.clamp(-0.99, 0.99);
+            self.matrix[(a.index(), b.index())] = value;
+            self.matrix[(b.index(), a.index())] = value;
+        }
+        self
+    }
+
+    pub fn get(&self, a: Sector, b: Sector) -> f64 {
+        self.matrix[(a.index(), b.index())]
+    }
+
+    fn loadings(&self) -> Result<DMatrix<f64>> {
+        Cholesky::new(self.matrix.clone())
+            .map(|decomposition| decomposition.l().clone_owned())
+            .with_context(|| "sector correlation matrix is not positive definite")
+    }
+}
+
+/// One off-diagonal entry of a [`SectorCorrelation`] as written in config files.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SectorPair {
+    sectors: (Sector, Sector),
+    correlation: f64,
+}
+
+/// Deserializes from a list of `{ sectors = [a, b], correlation = x }` pairs
+/// applied with [`SectorCorrelation::with_pair`]; unlisted pairs stay at zero.
+impl<'de> Deserialize<'de> for SectorCorrelation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pairs = Vec::<SectorPair>::deserialize(deserializer)?;
+        Ok(pairs
+            .into_iter()
+            .fold(Self::identity(), |correlation, pair| {
+                correlation.with_pair(pair.sectors.0, pair.sectors.1, pair.correlation)
+            }))
+    }
+}
+
+/// Serializes the non-zero off-diagonal pairs in the same list form, so a
+/// config round-trips through [`Deserialize`].
+impl Serialize for SectorCorrelation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let pairs: Vec<SectorPair> = Sector::ALL
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &a)| Sector::ALL[i + 1..].iter().map(move |&b| (a, b)))
+            .filter(|&(a, b)| self.get(a, b) != 0.0)
+            .map(|(a, b)| SectorPair {
+                sectors: (a, b),
+                correlation: self.get(a, b),
+            })
+            .collect();
+        pairs.serialize(serializer)
+    }
+}
 
-use crate::model::{Equity, Region, Sector};
+/// Region and sector a market index symbol is tagged with, since every tick
+/// carries both.
+pub const MARKET_INDEX_REGION: Region = Region::NorthAmerica;
+pub const MARKET_INDEX_SECTOR: Sector = Sector::Financials;
+
+/// Global market factor settings.
+///
+/// `betas` overrides the randomly drawn global-factor loading of the listed
+/// symbols. `index` adds a synthetic symbol (e.g. `MKT`) whose returns are the
+/// global factor itself, so each symbol's correlation to it follows its beta.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarketFactor {
+    pub index: Option<Symbol>,
+    pub betas: HashMap<Symbol, f64>,
+}
+
+/// Share of a fresh candidate matrix mixed in on each refresh when no half-life is configured.
+pub(super) const DEFAULT_REFRESH_BLEND: f64 = 0.2;
+
+/// Blend weight that makes the initial correlation structure decay to half its
+/// influence after `half_life`, given one refresh every `interval`.
+///
+/// After `n` refreshes the initial matrix keeps `(1 - w)^n` of its weight, so
+/// solving `(1 - w)^(half_life / interval) = 0.5` gives `w = 1 - 0.5^(interval / half_life)`.
+pub(super) fn blend_weight_for_half_life(half_life: Duration, interval: Duration) -> f64 {
+    if half_life.is_zero() {
+        return 1.0;
+    }
+    1.0 - 0.5_f64.powf(interval.as_secs_f64() / half_life.as_secs_f64())
+}
+
+/// Shrinkage weights towards the identity tried, in order, when repairing a
+/// correlation matrix that lost positive definiteness.
+const REPAIR_SHRINKAGE: [f64; 4] = [0.05, 0.1, 0.25, 0.5];
+
+/// Sector beta assumed for universes loaded from a correlation file (midpoint of the generated range).
+const DEFAULT_SECTOR_BETA: f64 = 0.55;
+
+/// Global market beta assumed for universes loaded from a correlation file (midpoint of the generated range).
+const DEFAULT_MARKET_BETA: f64 = 0.675;
+
+/// Reject correlation caps outside `(0, 1)`.
+pub(super) fn check_max_correlation(cap: f64) -> Result<()> {
+    if !(cap > 0.0 && cap < 1.0) {
+        bail!("max correlation must lie strictly between 0 and 1, got {cap}");
+    }
+    Ok(())
+}
+
+/// Reject drift persistence outside `[0, 1)`.
+pub(super) fn check_drift_persistence(persistence: f64) -> Result<()> {
+    if !(0.0..1.0).contains(&persistence) {
+        bail!("correlation drift persistence must lie in [0, 1), got {persistence}");
+    }
+    Ok(())
+}
+
+/// Cap off-diagonal correlations at `cap` in absolute value, keeping a
+/// near-singular matrix (e.g. two symbols correlated at ~1.0) away from the
+/// edge of positive definiteness. The diagonal is left at 1. `cap` must pass
+/// [`check_max_correlation`].
+pub(super) fn cap_correlations(mut matrix: DMatrix<f64>, cap: f64) -> DMatrix<f64> {
+    for i in 0..matrix.nrows() {
+        for j in 0..matrix.ncols() {
+            if i != j {
+                matrix[(i, j)] = matrix[(i, j)].clamp(-cap, cap);
+            }
+        }
+    }
+    matrix
+}
+
+/// Mixed into scenario ids so scenario `n` does not reuse the correlation
+/// draws of a run seeded with `n`.
+const SCENARIO_SALT: u64 = 0x5eed_5ce7_a210_c0de;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorrelationError {
+    NotPositiveDefinite,
+}
+
+impl fmt::Display for CorrelationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CorrelationError::NotPositiveDefinite => {
+                f.write_str("correlation matrix is not positive definite")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CorrelationError {}
+
+/// How a refresh produced the correlation matrix now in use.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RefreshOutcome {
+    Applied,
+    /// The blended matrix was not positive definite and was shrunk towards the identity.
+    Repaired {
+        shrinkage: f64,
+    },
+}
+
+/// Symbol counts that changed when a universe was replaced at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniverseDiff {
+    pub added: usize,
+    pub removed: usize,
+}
+
+/// Output of one factor-model draw, in universe order.
+struct FactorModel {
+    correlation: DMatrix<f64>,
+    sector_betas: Vec<f64>,
+    market_betas: Vec<f64>,
+}
+
+/// Random stream that fixes the correlation structure for `scenario_id`.
+pub(super) fn scenario_rng(scenario_id: u64) -> StdRng {
+    StdRng::seed_from_u64(scenario_id ^ SCENARIO_SALT)
+}
 
 pub struct StockUniverse {
     equities: Vec<Equity>,
+    generation: u64,
+    sector_loadings: DMatrix<f64>,
+    /// Each equity's exposure to its sector factor, in universe order.
+    sector_betas: Vec<f64>,
+    /// Each equity's exposure to the global market factor, in universe order.
+    market_betas: Vec<f64>,
+    market: MarketFactor,
     correlation: DMatrix<f64>,
     cholesky: DMatrix<f64>,
+    /// Largest absolute off-diagonal correlation kept after every rebuild and refresh.
+    max_correlation: Option<f64>,
+    /// Candidate drawn for the previous refresh, the starting point of the next
+    /// one; cleared whenever the structure is redrawn.
+    last_candidate: Option<DMatrix<f64>>,
 }
 
 impl StockUniverse {
-    pub fn new(equities: Vec<Equity>, rng: &mut StdRng) -> Result<Self> {
-        let correlation = Self::factor_based_correlation(&equities, rng);
+    pub fn new(
+        equities: Vec<Equity>,
+        sector_correlation: &SectorCorrelation,
+        rng: &mut StdRng,
+    ) -> Result<Self> {
+        let sector_loadings = sector_correlation.loadings()?;
+        let market = MarketFactor::default();
+        let model = Self::factor_based_correlation(&equities, &sector_loadings, &market, rng);
+        let cholesky = Self::compute_cholesky(&model.correlation)?;
+        Ok(Self {
+            equities,
+            generation: 0,
+            sector_loadings,
+            sector_betas: model.sector_betas,
+            market_betas: model.market_betas,
+            market,
+            correlation: model.correlation,
+            cholesky,
+            max_correlation: None,
+            last_candidate: None,
+        })
+    }
+
+    /// Universe whose correlation structure is fixed by `scenario_id` alone, so
+    /// each id names a distinct but reproducible market independent of `seed`.
+    pub fn for_scenario(
+        equities: Vec<Equity>,
+        sector_correlation: &SectorCorrelation,
+        scenario_id: u64,
+    ) -> Result<Self> {
+        Self::new(equities, sector_correlation, &mut scenario_rng(scenario_id))
+    }
+
+    /// Build a universe around a previously exported correlation matrix.
+    pub fn from_correlation(
+        equities: Vec<Equity>,
+        correlation: DMatrix<f64>,
+        sector_correlation: &SectorCorrelation,
+    ) -> Result<Self> {
+        if correlation.nrows() != equities.len() || correlation.ncols() != equities.len() {
+            bail!(
+                "correlation matrix is {}x{} but the universe has {} equities",
+                correlation.nrows(),
+                correlation.ncols(),
+                equities.len()
+            );
+        }
+        let sector_loadings = sector_correlation.loadings()?;
         let cholesky = Self::compute_cholesky(&correlation)?;
+        // the file carries no factor betas, so assume typical exposures
+        let sector_betas = vec![DEFAULT_SECTOR_BETA; equities.len()];
+        let market_betas = vec![DEFAULT_MARKET_BETA; equities.len()];
         Ok(Self {
             equities,
+            generation: 0,
+            sector_loadings,
+            sector_betas,
+            market_betas,
+            market: MarketFactor::default(),
             correlation,
             cholesky,
+            max_correlation: None,
+            last_candidate: None,
         })
     }
 
+    /// Cap off-diagonal correlations at `cap` (in `(0, 1)`) now and after
+    /// every later rebuild or refresh. A refresh whose capped matrix no longer
+    /// factors fails like any other degenerate refresh.
+    pub fn with_max_correlation(mut self, cap: f64) -> Result<Self> {
+        check_max_correlation(cap)?;
+        let correlation = cap_correlations(self.correlation.clone(), cap);
+        self.cholesky = Self::compute_cholesky(&correlation)?;
+        self.correlation = correlation;
+        self.max_correlation = Some(cap);
+        Ok(self)
+    }
+
+    /// Redraw the factor structure with `market` applied, appending its index
+    /// symbol to the universe when it is not already there. Later rebuilds and
+    /// universe reloads keep the overrides and the index.
+    pub fn with_market_factor(mut self, market: MarketFactor, rng: &mut StdRng) -> Result<Self> {
+        if let Some((symbol, beta)) = market.betas.iter().find(|(_, beta)| !beta.is_finite()) {
+            bail!("market beta for {symbol} must be finite, got {beta}");
+        }
+        if let Some(symbol) = market
+            .betas
+            .keys()
+            .find(|symbol| !self.equities.iter().any(|equity| &equity.symbol == *symbol))
+        {
+            bail!("market beta given for {symbol}, which is not in the universe");
+        }
+        if let Some(index) = &market.index {
+            index.validate().context("invalid market index symbol")?;
+        }
+        self.market = market;
+        let equities = std::mem::take(&mut self.equities);
+        self.equities = self.with_market_index(equities);
+        self.rebuild(rng)?;
+        Ok(self)
+    }
+
     pub fn equities(&self) -> &[Equity] {
         &self.equities
     }
 
+    /// Incremented whenever the equity set changes, so consumers know to resync.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn sector_betas(&self) -> &[f64] {
+        &self.sector_betas
+    }
+
+    /// Loading of `symbol` on the global market factor, drawn at random or
+    /// taken from [`MarketFactor::betas`]. The market index itself has beta 1.
+    pub fn beta(&self, symbol: &str) -> Option<f64> {
+        let index = self
+            .equities
+            .iter()
+            .position(|equity| equity.symbol == symbol)?;
+        Some(self.market_betas[index])
+    }
+
     pub fn cholesky(&self) -> &DMatrix<f64> {
         &self.cholesky
     }
 
-    pub fn refresh(&mut self, rng: &mut StdRng) -> Result<()> {
-        let candidate = Self::factor_based_correlation(&self.equities, rng);
-        let blended = &self.correlation * 0.8 + candidate * 0.2;
-        let renormalized = Self::renormalize(blended);
-        let cholesky = Self::compute_cholesky(&renormalized)?;
-        self.correlation = renormalized;
+    pub fn correlation_matrix(&self) -> &DMatrix<f64> {
+        &self.correlation
+    }
+
+    /// Next matrix to blend into the current structure.
+    ///
+    /// With `persistence` 0 each candidate is an independent factor-based draw.
+    /// Otherwise candidates follow a random walk: the previous candidate keeps
+    /// weight `persistence` and a fresh draw supplies the rest, so successive
+    /// refreshes pull the structure in similar directions and it drifts
+    /// smoothly. A convex mix of correlation matrices is still one, so the walk
+    /// stays positive definite.
+    pub(super) fn candidate(&mut self, rng: &mut StdRng, persistence: f64) -> DMatrix<f64> {
+        let fresh = Self::factor_based_correlation(
+            &self.equities,
+            &self.sector_loadings,
+            &self.market,
+            rng,
+        )
+        .correlation;
+        let candidate = match self.last_candidate.take() {
+            Some(previous) if persistence > 0.0 => {
+                previous * persistence + fresh * (1.0 - persistence)
+            }
+            _ => fresh,
+        };
+        self.last_candidate = Some(candidate.clone());
+        candidate
+    }
+
+    /// Blend `candidate` into the current matrix. On failure the previous
+    /// correlation and Cholesky factor are left untouched.
+    pub(super) fn refresh_with(
+        &mut self,
+        candidate: DMatrix<f64>,
+        blend: f64,
+        repair: bool,
+    ) -> Result<RefreshOutcome, CorrelationError> {
+        let blended = &self.correlation * (1.0 - blend) + candidate * blend;
+        let renormalized = self.capped(Self::renormalize(blended));
+        let (correlation, cholesky, outcome) = match Self::compute_cholesky(&renormalized) {
+            Ok(cholesky) => (renormalized, cholesky, RefreshOutcome::Applied),
+            Err(err) if repair => Self::repair(&renormalized).ok_or(err)?,
+            Err(err) => return Err(err),
+        };
+        self.correlation = correlation;
         self.cholesky = cholesky;
-        Ok(())
+        Ok(outcome)
+    }
+
+    /// Shrink a non-positive-definite matrix towards the identity until it factors.
+    fn repair(matrix: &DMatrix<f64>) -> Option<(DMatrix<f64>, DMatrix<f64>, RefreshOutcome)> {
+        let identity = DMatrix::identity(matrix.nrows(), matrix.ncols());
+        REPAIR_SHRINKAGE.into_iter().find_map(|shrinkage| {
+            let shrunk = matrix * (1.0 - shrinkage) + &identity * shrinkage;
+            let cholesky = Self::compute_cholesky(&shrunk).ok()?;
+            Some((shrunk, cholesky, RefreshOutcome::Repaired { shrinkage }))
+        })
     }
 
     pub fn rebuild(&mut self, rng: &mut StdRng) -> Result<()> {
-        let correlation = Self::factor_based_correlation(&self.equities, rng);
+        let model = Self::factor_based_correlation(
+            &self.equities,
+            &self.sector_loadings,
+            &self.market,
+            rng,
+        );
+        let correlation = self.capped(model.correlation);
         let cholesky = Self::compute_cholesky(&correlation)?;
+        self.sector_betas = model.sector_betas;
+        self.market_betas = model.market_betas;
         self.correlation = correlation;
         self.cholesky = cholesky;
+        self.last_candidate = None;
         Ok(())
     }
 
-    fn factor_based_correlation(equities: &[Equity], rng: &mut StdRng) -> DMatrix<f64> {
+    /// Swap in a new equity set (IPOs/delistings) and rebuild the correlation structure for it.
+    pub fn replace_equities(
+        &mut self,
+        equities: Vec<Equity>,
+        rng: &mut StdRng,
+    ) -> Result<UniverseDiff> {
+        let equities = self.with_market_index(equities);
+        let previous: HashSet<&str> = self
+            .equities
+            .iter()
+            .map(|equity| equity.symbol.as_str())
+            .collect();
+        let next: HashSet<&str> = equities
+            .iter()
+            .map(|equity| equity.symbol.as_str())
+            .collect();
+        let diff = UniverseDiff {
+            added: next.difference(&previous).count(),
+            removed: previous.difference(&next).count(),
+        };
+
+        let model =
+            Self::factor_based_correlation(&equities, &self.sector_loadings, &self.market, rng);
+        let correlation = self.capped(model.correlation);
+        let cholesky = Self::compute_cholesky(&correlation)?;
+        self.equities = equities;
+        self.sector_betas = model.sector_betas;
+        self.market_betas = model.market_betas;
+        self.correlation = correlation;
+        self.cholesky = cholesky;
+        self.last_candidate = None;
+        self.generation += 1;
+        Ok(diff)
+    }
+
+    fn capped(&self, matrix: DMatrix<f64>) -> DMatrix<f64> {
+        match self.max_correlation {
+            Some(cap) => cap_correlations(matrix, cap),
+            None => matrix,
+        }
+    }
+
+    /// `equities` with the market index appended when one is configured and missing.
+    fn with_market_index(&self, mut equities: Vec<Equity>) -> Vec<Equity> {
+        if let Some(index) = &self.market.index {
+            if !equities.iter().any(|equity| equity.symbol == *index) {
+                equities.push(Equity {
+                    symbol: index.clone(),
+                    region: MARKET_INDEX_REGION,
+                    sector: MARKET_INDEX_SECTOR,
+                });
+            }
+        }
+        equities
+    }
+
+    /// Factor-model correlation matrix plus each equity's factor betas.
+    fn factor_based_correlation(
+        equities: &[Equity],
+        sector_loadings: &DMatrix<f64>,
+        market: &MarketFactor,
+        rng: &mut StdRng,
+    ) -> FactorModel {
         let base_columns = 1 + Region::ALL.len() + Sector::ALL.len();
         let mut feature_data = Vec::with_capacity(equities.len() * (base_columns + 1));
+        let mut sector_betas = Vec::with_capacity(equities.len());
+        let mut market_betas = Vec::with_capacity(equities.len());
+        let is_index = |equity: &Equity| {
+            market
+                .index
+                .as_ref()
+                .is_some_and(|index| equity.symbol == *index)
+        };
 
         for equity in equities {
             let mut row = vec![0.0; base_columns + 1];
-            row[0] = rng.gen_range(0.55..0.8); // global market beta
+            if is_index(equity) {
+                // loads on the global factor alone, so its returns are the factor
+                row[0] = 1.0;
+                sector_betas.push(0.0);
+                market_betas.push(1.0);
+                feature_data.extend(row);
+                continue;
+            }
+            let drawn = rng.gen_range(0.55..0.8);
+            row[0] = market.betas.get(&equity.symbol).copied().unwrap_or(drawn); // global market beta
+            market_betas.push(row[0]);
 
             let region_offset = 1 + equity.region.index();
             row[region_offset] = rng.gen_range(0.35..0.6);
 
-            let sector_offset = 1 + Region::ALL.len() + equity.sector.index();
-            row[sector_offset] = rng.gen_range(0.4..0.7);
+            // spread the sector loading across sector factors so that correlated
+            // sectors share exposure (identity loadings keep sectors independent)
+            let sector_offset = 1 + Region::ALL.len();
+            let sector_beta = rng.gen_range(0.4..0.7);
+            sector_betas.push(sector_beta);
+            for (factor, loading) in sector_loadings
+                .row(equity.sector.index())
+                .iter()
+                .enumerate()
+            {
+                row[sector_offset + factor] = sector_beta * loading;
+            }
 
             // idiosyncratic style factor to avoid perfect collinearity
             let idiosyncratic_offset = base_columns;
@@ -72,11 +552,18 @@ impl StockUniverse {
             DMatrix::from_row_slice(equities.len(), base_columns + 1, &feature_data);
         let mut covariance = &feature_matrix * feature_matrix.transpose();
 
-        for i in 0..equities.len() {
-            covariance[(i, i)] += rng.gen_range(0.08..0.15);
+        for (i, equity) in equities.iter().enumerate() {
+            let noise = rng.gen_range(0.08..0.15);
+            if !is_index(equity) {
+                covariance[(i, i)] += noise;
+            }
         }
 
-        Self::renormalize(covariance)
+        FactorModel {
+            correlation: Self::renormalize(covariance),
+            sector_betas,
+            market_betas,
+        }
     }
 
     fn renormalize(matrix: DMatrix<f64>) -> DMatrix<f64> {
@@ -95,39 +582,34 @@ impl StockUniverse {
         normalized
     }
 
-    fn compute_cholesky(matrix: &DMatrix<f64>) -> Result<DMatrix<f64>> {
+    fn compute_cholesky(matrix: &DMatrix<f64>) -> Result<DMatrix<f64>, CorrelationError> {
+        if matrix.iter().any(|value| !value.is_finite()) {
+            return Err(CorrelationError::NotPositiveDefinite);
+        }
         Cholesky::new(matrix.clone())
             .map(|decomposition| decomposition.l().clone_owned())
-            .with_context(|| "failed to compute Cholesky factor for correlation matrix")
-    }
-}
-
-#[cfg(test)]
-impl StockUniverse {
-    pub(crate) fn correlation_matrix(&self) -> &DMatrix<f64> {
-        &self.correlation
+            .ok_or(CorrelationError::NotPositiveDefinite)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand::SeedableRng;
 
     fn build_sample_equities() -> Vec<Equity> {
         vec![
             Equity {
-                symbol: "EQ0".into(),
+                symbol: "EQ0".parse().unwrap(),
                 region: Region::NorthAmerica,
                 sector: Sector::Technology,
             },
             Equity {
-                symbol: "EQ1".into(),
+                symbol: "EQ1".parse().unwrap(),
                 region: Region::Europe,
                 sector: Sector::Financials,
             },
             Equity {
-                symbol: "EQ2".into(),
+                symbol: "EQ2".parse().unwrap(),
                 region: Region::AsiaPacific,
                 sector: Sector::Energy,
             },
@@ -137,7 +619,12 @@ mod tests {
     #[test]
     fn new_universe_has_unit_diagonal() {
         let mut rng = StdRng::seed_from_u64(7);
-        let universe = StockUniverse::new(build_sample_equities(), &mut rng).expect("universe");
+        let universe = StockUniverse::new(
+            build_sample_equities(),
+            &SectorCorrelation::default(),
+            &mut rng,
+        )
+        .expect("universe");
         let corr = universe.correlation_matrix();
 
         for i in 0..corr.nrows() {
@@ -153,10 +640,19 @@ mod tests {
     #[test]
     fn refresh_preserves_positive_definiteness() {
         let mut rng = StdRng::seed_from_u64(42);
-        let mut universe = StockUniverse::new(build_sample_equities(), &mut rng).expect("universe");
+        let mut universe = StockUniverse::new(
+            build_sample_equities(),
+            &SectorCorrelation::default(),
+            &mut rng,
+        )
+        .expect("universe");
 
         for _ in 0..5 {
-            universe.refresh(&mut rng).expect("refresh");
+            let candidate = universe.candidate(&mut rng, 0.0);
+            let outcome = universe
+                .refresh_with(candidate, DEFAULT_REFRESH_BLEND, false)
+                .expect("refresh");
+            assert_eq!(outcome, RefreshOutcome::Applied);
             let corr = universe.correlation_matrix();
             assert!(
                 Cholesky::new(corr.clone()).is_some(),
@@ -165,10 +661,280 @@ mod tests {
         }
     }
 
+    #[test]
+    fn persistent_candidates_drift_more_smoothly_than_independent_draws() {
+        // mean size of the frame-to-frame change in the correlation matrix
+        let mean_step = |persistence: f64| {
+            let mut rng = StdRng::seed_from_u64(42);
+            let mut universe = StockUniverse::new(
+                build_sample_equities(),
+                &SectorCorrelation::default(),
+                &mut rng,
+            )
+            .expect("universe");
+            let refreshes = 60;
+            let mut total = 0.0;
+            for _ in 0..refreshes {
+                let before = universe.correlation_matrix().clone();
+                let candidate = universe.candidate(&mut rng, persistence);
+                let outcome = universe
+                    .refresh_with(candidate, DEFAULT_REFRESH_BLEND, false)
+                    .expect("refresh");
+                // applied without repair, so the walk stayed positive definite
+                assert_eq!(
+                    outcome,
+                    RefreshOutcome::Applied,
+                    "persistence {persistence}"
+                );
+                total += (universe.correlation_matrix() - before).norm();
+            }
+            total / f64::from(refreshes)
+        };
+
+        let independent = mean_step(0.0);
+        let persistent = mean_step(0.9);
+        assert!(
+            persistent < independent * 0.5,
+            "persistent steps {persistent} not smoother than independent {independent}"
+        );
+        assert!(check_drift_persistence(0.9).is_ok());
+        assert!(check_drift_persistence(1.0).is_err());
+        assert!(check_drift_persistence(-0.1).is_err());
+    }
+
+    #[test]
+    fn scenario_ids_give_distinct_reproducible_matrices() {
+        let scenario = |id| {
+            StockUniverse::for_scenario(build_sample_equities(), &SectorCorrelation::default(), id)
+                .expect("universe")
+                .correlation_matrix()
+                .clone()
+        };
+        let (first, second, third) = (scenario(1), scenario(2), scenario(3));
+
+        assert_eq!(first, scenario(1), "same id reproduces the matrix");
+        assert_ne!(first, second);
+        assert_ne!(second, third);
+        assert_ne!(first, third);
+        for corr in [first, second, third] {
+            assert!(Cholesky::new(corr.clone()).is_some(), "matrix must be SPD");
+            assert!(corr
+                .diagonal()
+                .iter()
+                .all(|value| (value - 1.0).abs() < 1e-9));
+        }
+    }
+
+    fn sample_universe() -> StockUniverse {
+        let mut rng = StdRng::seed_from_u64(42);
+        StockUniverse::new(
+            build_sample_equities(),
+            &SectorCorrelation::default(),
+            &mut rng,
+        )
+        .expect("universe")
+    }
+
+    #[test]
+    fn degenerate_refresh_keeps_previous_factor() {
+        let mut universe = sample_universe();
+        let correlation = universe.correlation_matrix().clone();
+        let cholesky = universe.cholesky().clone();
+
+        let degenerate = DMatrix::from_element(3, 3, f64::NAN);
+        for repair in [false, true] {
+            assert_eq!(
+                universe.refresh_with(degenerate.clone(), 0.5, repair),
+                Err(CorrelationError::NotPositiveDefinite)
+            );
+            assert_eq!(universe.correlation_matrix(), &correlation);
+            assert_eq!(universe.cholesky(), &cholesky);
+        }
+    }
+
+    #[test]
+    fn repair_shrinks_indefinite_blend_towards_identity() {
+        let mut universe = sample_universe();
+        // unit diagonal with off-diagonal correlations that cannot coexist
+        let indefinite =
+            DMatrix::from_row_slice(3, 3, &[1.0, 0.99, 0.99, 0.99, 1.0, -0.99, 0.99, -0.99, 1.0]);
+
+        assert!(universe
+            .refresh_with(indefinite.clone(), 1.0, false)
+            .is_err());
+        let outcome = universe
+            .refresh_with(indefinite, 1.0, true)
+            .expect("repaired refresh");
+        assert!(matches!(outcome, RefreshOutcome::Repaired { .. }));
+        assert!(Cholesky::new(universe.correlation_matrix().clone()).is_some());
+    }
+
+    #[test]
+    fn max_correlation_keeps_near_perfect_inputs_well_conditioned() {
+        const CAP: f64 = 0.99;
+        let equities = build_sample_equities();
+        let size = equities.len();
+        // a duplicated symbol (exactly 1.0) next to a near-duplicate
+        let mut input = DMatrix::from_element(size, size, 0.99999);
+        input[(0, 1)] = 1.0;
+        input[(1, 0)] = 1.0;
+        input.fill_diagonal(1.0);
+        assert!(Cholesky::new(input.clone()).is_none());
+
+        let capped = cap_correlations(input.clone(), CAP);
+        let mut universe =
+            StockUniverse::from_correlation(equities, capped, &SectorCorrelation::default())
+                .expect("capped matrix factors")
+                .with_max_correlation(CAP)
+                .expect("cap applies");
+
+        let assert_well_conditioned = |universe: &StockUniverse| {
+            let correlation = universe.correlation_matrix();
+            for i in 0..size {
+                for j in 0..size {
+                    let expected_max = if i == j { 1.0 } else { CAP };
+                    assert!(correlation[(i, j)].abs() <= expected_max);
+                }
+            }
+            let eigenvalues = correlation.clone().symmetric_eigenvalues();
+            assert!(eigenvalues.min() > 0.5 * (1.0 - CAP), "{eigenvalues}");
+            let diagonal = universe.cholesky().diagonal();
+            assert!(diagonal.min() > 0.1, "{diagonal}");
+            assert!(diagonal.max() / diagonal.min() < 10.0, "{diagonal}");
+        };
+        assert_well_conditioned(&universe);
+
+        // refreshes towards the degenerate matrix stay capped too
+        let outcome = universe.refresh_with(input, 1.0, false);
+        assert_eq!(outcome, Ok(RefreshOutcome::Applied));
+        assert_well_conditioned(&universe);
+
+        assert!(sample_universe().with_max_correlation(1.0).is_err());
+    }
+
+    #[test]
+    fn half_life_blend_weight_halves_initial_influence() {
+        let interval = Duration::from_secs(30);
+        let half_life = Duration::from_secs(300);
+        let weight = blend_weight_for_half_life(half_life, interval);
+        let refreshes = (half_life.as_secs_f64() / interval.as_secs_f64()) as i32;
+        let remaining = (1.0 - weight).powi(refreshes);
+        assert!(
+            (remaining - 0.5).abs() < 1e-12,
+            "remaining weight {remaining}"
+        );
+
+        assert!(
+            (blend_weight_for_half_life(interval, interval) - 0.5).abs() < 1e-12,
+            "half-life equal to the interval blends evenly"
+        );
+        assert_eq!(blend_weight_for_half_life(Duration::ZERO, interval), 1.0);
+        assert_eq!(blend_weight_for_half_life(half_life, Duration::ZERO), 0.0);
+    }
+
+    #[test]
+    fn replace_equities_resizes_correlation() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut universe = StockUniverse::new(
+            build_sample_equities(),
+            &SectorCorrelation::default(),
+            &mut rng,
+        )
+        .expect("universe");
+
+        let mut next = build_sample_equities();
+        next.remove(0);
+        next.push(Equity {
+            symbol: "EQ3".parse().unwrap(),
+            region: Region::Europe,
+            sector: Sector::Utilities,
+        });
+        next.push(Equity {
+            symbol: "EQ4".parse().unwrap(),
+            region: Region::Europe,
+            sector: Sector::Utilities,
+        });
+
+        let diff = universe.replace_equities(next, &mut rng).expect("replace");
+        assert_eq!(
+            diff,
+            UniverseDiff {
+                added: 2,
+                removed: 1
+            }
+        );
+        assert_eq!(universe.generation(), 1);
+        assert_eq!(universe.cholesky().nrows(), 4);
+        assert_eq!(universe.equities().len(), 4);
+    }
+
+    #[test]
+    fn market_index_correlates_with_each_symbol_by_its_beta() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let market = MarketFactor {
+            index: Some("MKT".parse().unwrap()),
+            betas: HashMap::from([("EQ0".parse().unwrap(), 1.5), ("EQ1".parse().unwrap(), 0.1)]),
+        };
+        let mut universe = StockUniverse::new(
+            build_sample_equities(),
+            &SectorCorrelation::default(),
+            &mut rng,
+        )
+        .expect("universe")
+        .with_market_factor(market, &mut rng)
+        .expect("market factor");
+
+        assert_eq!(universe.equities().len(), 4);
+        assert_eq!(universe.beta("EQ0"), Some(1.5));
+        assert_eq!(universe.beta("EQ1"), Some(0.1));
+        assert_eq!(universe.beta("MKT"), Some(1.0));
+        assert!((0.55..0.8).contains(&universe.beta("EQ2").unwrap()));
+        assert_eq!(universe.beta("MISSING"), None);
+
+        assert_eq!(universe.equities()[3].symbol, "MKT");
+        let correlation = universe.correlation_matrix();
+        let (high, low) = (correlation[(0, 3)], correlation[(1, 3)]);
+        assert!(high > 0.8 && low < 0.2, "high {high:.3}, low {low:.3}");
+
+        // reloads keep the index even when the new file omits it
+        universe
+            .replace_equities(build_sample_equities(), &mut rng)
+            .expect("replace");
+        assert_eq!(universe.equities().len(), 4);
+        assert_eq!(universe.beta("EQ0"), Some(1.5));
+    }
+
+    #[test]
+    fn market_betas_must_name_universe_symbols() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let market = MarketFactor {
+            index: None,
+            betas: HashMap::from([
+                ("EQ0".parse().unwrap(), 1.2),
+                ("NAENG000".parse().unwrap(), 1.5),
+            ]),
+        };
+        let Err(err) = StockUniverse::new(
+            build_sample_equities(),
+            &SectorCorrelation::default(),
+            &mut rng,
+        )
+        .expect("universe")
+        .with_market_factor(market, &mut rng) else {
+            panic!("a beta for a symbol outside the universe should be rejected");
+        };
+        assert!(err.to_string().contains("NAENG000"), "{err}");
+    }
+
     #[test]
     fn rebuild_restarts_correlation_structure() {
         let mut rng = StdRng::seed_from_u64(123);
-        let mut universe = StockUniverse::new(build_sample_equities(), &mut rng).expect("universe");
+        let mut universe = StockUniverse::new(
+            build_sample_equities(),
+            &SectorCorrelation::default(),
+            &mut rng,
+        )
+        .expect("universe");
         let before = universe.correlation_matrix().clone();
 
         universe.rebuild(&mut rng).expect("rebuild");
@@ -180,4 +946,61 @@ mod tests {
         );
         assert_ne!(before, *after, "rebuild should produce a distinct matrix");
     }
+
+    #[test]
+    fn sector_correlation_raises_cross_sector_correlation() {
+        let sectors = [Sector::Energy, Sector::Materials, Sector::Utilities];
+        let equities: Vec<Equity> = sectors
+            .iter()
+            .flat_map(|sector| {
+                (0..6).map(move |idx| Equity {
+                    symbol: format!("{}{idx}", sector.prefix()).parse().unwrap(),
+                    region: Region::NorthAmerica,
+                    sector: *sector,
+                })
+            })
+            .collect();
+        let sector_correlation =
+            SectorCorrelation::identity().with_pair(Sector::Energy, Sector::Materials, 0.9);
+
+        let mut rng = StdRng::seed_from_u64(11);
+        let universe =
+            StockUniverse::new(equities, &sector_correlation, &mut rng).expect("universe");
+        let corr = universe.correlation_matrix();
+        assert!(Cholesky::new(corr.clone()).is_some(), "matrix must be SPD");
+
+        let average_between = |a: Sector, b: Sector| {
+            let equities = universe.equities();
+            let mut total = 0.0;
+            let mut count = 0usize;
+            for (i, left) in equities.iter().enumerate() {
+                for (j, right) in equities.iter().enumerate() {
+                    if left.sector == a && right.sector == b {
+                        total += corr[(i, j)];
+                        count += 1;
+                    }
+                }
+            }
+            total / count as f64
+        };
+
+        let correlated = average_between(Sector::Energy, Sector::Materials);
+        let uncorrelated = average_between(Sector::Energy, Sector::Utilities);
+        assert!(
+            correlated > uncorrelated,
+            "expected correlated sectors ({correlated:.3}) above uncorrelated ({uncorrelated:.3})"
+        );
+    }
+
+    #[test]
+    fn invalid_sector_correlation_is_rejected() {
+        let sector_correlation = SectorCorrelation::identity()
+            .with_pair(Sector::Energy, Sector::Materials, 0.95)
+            .with_pair(Sector::Energy, Sector::Utilities, 0.95)
+            .with_pair(Sector::Materials, Sector::Utilities, -0.95);
+
+        let mut rng = StdRng::seed_from_u64(5);
+        let result = StockUniverse::new(build_sample_equities(), &sector_correlation, &mut rng);
+        assert!(result.is_err(), "non-PSD sector matrix should be rejected");
+    }
 }
@@ -1,53 +1,116 @@
-use anyhow::{Context, Result};
+use std::io;
+use std::path::Path;
+
+use anyhow::Result;
 use clap::Args;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::net::UnixStream;
 
+use serde_json::json;
+
+use crate::cli::print_status;
 use crate::constants::SOCKET_PATH;
-use crate::tick::Tick;
+use crate::logging;
+use crate::model::Symbol;
+use crate::reconnect::{self, ReconnectArgs};
+use crate::tick::{self, Tick, TickValidator};
 
 #[derive(Debug, Args, Clone)]
 pub struct TailArgs {
     /// Filter ticks to a single symbol (e.g. AAPL)
     #[arg(short, long)]
-    pub symbol: Option<String>,
+    pub symbol: Option<Symbol>,
 
     /// Stop after printing this many ticks
     #[arg(short, long)]
     pub limit: Option<usize>,
+
+    /// Print each tick as a raw JSON line instead of a formatted row
+    #[arg(long)]
+    pub json: bool,
+
+    /// Suppress informational banners so stdout only carries ticks
+    #[arg(short, long)]
+    pub quiet: bool,
+
+    #[command(flatten)]
+    pub reconnect: ReconnectArgs,
 }
 
 pub async fn run(args: TailArgs) -> Result<()> {
-    let stream = UnixStream::connect(SOCKET_PATH).await.with_context(|| {
-        format!(
-            "failed to connect to socket {:?}; run `cargo run -- run` first",
-            SOCKET_PATH
-        )
-    })?;
-
-    let mut lines = BufReader::new(stream).lines();
+    let policy = args.reconnect.policy()?;
     let mut printed = 0usize;
-    println!("Connected to {SOCKET_PATH}; streaming ticks...");
 
+    loop {
+        let stream = reconnect::connect_unix(Path::new(SOCKET_PATH), policy.as_ref()).await?;
+        print_status(
+            args.quiet,
+            &format!("Connected to {SOCKET_PATH}; streaming ticks..."),
+        );
+
+        match stream_ticks(stream, &args, &mut printed).await {
+            Ok(true) => return Ok(()),
+            Ok(false) if policy.is_none() => return Ok(()),
+            Ok(false) => print_status(args.quiet, "Stream closed; reconnecting..."),
+            Err(err) if policy.is_some() => logging::warn(
+                "tail.stream_error",
+                "Socket read failed; reconnecting",
+                json!({ "error": err.to_string() }),
+            ),
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+/// Print ticks until the stream ends or the limit is reached; returns `true` once the limit is hit.
+async fn stream_ticks(
+    stream: UnixStream,
+    args: &TailArgs,
+    printed: &mut usize,
+) -> io::Result<bool> {
+    let mut lines = BufReader::new(stream).lines();
+    let mut validator = TickValidator::default();
     while let Some(line) = lines.next_line().await? {
-        let tick: Tick = serde_json::from_str(&line)?;
+        let tick: Tick = match serde_json::from_str(&line) {
+            Ok(tick) => tick,
+            Err(err) => {
+                logging::warn(
+                    "tail.invalid_tick",
+                    "Skipping malformed tick",
+                    json!({ "error": err.to_string() }),
+                );
+                continue;
+            }
+        };
+        if let Err(err) = validator.check(&tick, tick::now_ms()) {
+            logging::warn(
+                "tail.invalid_tick",
+                "Skipping malformed tick",
+                json!({ "symbol": tick.symbol, "error": err.to_string() }),
+            );
+            continue;
+        }
         if let Some(ref filter) = args.symbol {
             if filter != &tick.symbol {
                 continue;
             }
         }
 
-        println!(
-            "{:>16} | {:>12} | {:>8.4} | {:>18} | {:>22}",
-            tick.timestamp_ms, tick.symbol, tick.price, tick.region, tick.sector
-        );
-        printed += 1;
+        if args.json {
+            println!("{line}");
+        } else {
+            println!(
+                "{:>16} | {:>12} | {:>8.4} | {:>18} | {:>22}",
+                tick.timestamp_ms, tick.symbol, tick.price, tick.region, tick.sector
+            );
+        }
+        *printed += 1;
 
         if let Some(limit) = args.limit {
-            if printed >= limit {
-                break;
+            if *printed >= limit {
+                return Ok(true);
             }
         }
     }
-    Ok(())
+    Ok(false)
 }
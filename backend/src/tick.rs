@@ -1,12 +1,13 @@
-use serde::{Deserialize, Serialize};
+// Tick wire types live in `market-core` so the frontend deserializes exactly what the simulator emits.
+pub use market_core::{CompactTick, Tick, TickError, TickValidator};
 
-use crate::model::{Region, Sector};
+/// Wall-clock time in epoch milliseconds, the reference [`TickValidator`]
+/// checks incoming timestamps against.
+pub fn now_ms() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Tick {
-    pub symbol: String,
-    pub price: f64,
-    pub timestamp_ms: u128,
-    pub region: Region,
-    pub sector: Sector,
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time went backwards");
+    u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
 }
@@ -0,0 +1,15 @@
+/// Crate version, suffixed with the short git hash when built from a checkout
+/// (e.g. `0.1.0+ca48687`).
+pub fn version() -> &'static str {
+    env!("MARKET_DATA_BUILD_VERSION")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_starts_with_crate_version() {
+        assert!(version().starts_with(env!("CARGO_PKG_VERSION")));
+    }
+}
@@ -0,0 +1,50 @@
+use std::process::Command;
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/ticks.jsonl");
+
+fn chart(args: &[&str]) -> (bool, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-market-data"))
+        .args(["chart", "--quiet", "--file"])
+        .args(args)
+        .output()
+        .expect("invoke rust-market-data chart");
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    )
+}
+
+#[test]
+fn chart_reads_a_recorded_file_without_a_running_simulator() {
+    let (success, stdout, stderr) = chart(&[FIXTURE, "--symbol", "NATECH000", "--annotate"]);
+    assert!(success, "chart --file failed: {stderr}");
+
+    assert!(
+        stdout.contains("Price range: 99.2500 → 101.5000"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("High: 101.5000 at +0.50s"), "{stdout}");
+    assert!(stdout.contains("Low:  99.2500 at +1.00s"), "{stdout}");
+    assert!(stdout.contains("Last: 100.7500 at +2.00s"), "{stdout}");
+}
+
+#[test]
+fn chart_file_without_matching_ticks_is_an_error() {
+    let (success, _, stderr) = chart(&[FIXTURE, "--symbol", "NAHLT009"]);
+    assert!(!success);
+    assert!(stderr.contains("holds no ticks"), "{stderr}");
+}
+
+#[test]
+fn chart_file_rejects_live_socket_flags() {
+    for flag in [
+        &["--duration-secs", "5"][..],
+        &["--reconnect"],
+        &["--reconnect-max-attempts", "3"],
+    ] {
+        let (success, _, stderr) = chart(&[&[FIXTURE][..], flag].concat());
+        assert!(!success, "{flag:?} was accepted");
+        assert!(stderr.contains("cannot be used with"), "{stderr}");
+    }
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use rust_market_data::{
+    constants::SOCKET_PATH,
+    simulator::{self, SimulatorConfig},
+    tick::Tick,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn quiet_json_tail_prints_only_ticks() {
+    let config = SimulatorConfig {
+        enable_gateway: false,
+        tick_interval: Duration::from_millis(4),
+        max_ticks: None,
+        ..SimulatorConfig::default()
+    };
+    let simulator_task = tokio::spawn(async move {
+        let _ = simulator::run_with_config(config).await;
+    });
+
+    // readiness: the simulator accepts connections on its socket
+    drop(common::connect_unix(Path::new(SOCKET_PATH)).await);
+
+    let binary = env!("CARGO_BIN_EXE_rust-market-data");
+    let output = tokio::task::spawn_blocking(move || {
+        Command::new(binary)
+            .args(["tail", "--quiet", "--json", "--limit", "5"])
+            .output()
+            .expect("invoke rust-market-data tail")
+    })
+    .await
+    .expect("tail task");
+
+    simulator_task.abort();
+    let _ = simulator_task.await;
+    let _ = std::fs::remove_file(SOCKET_PATH);
+
+    assert!(output.status.success(), "tail command should succeed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 5, "expected exactly five tick lines");
+    for line in lines {
+        let tick: Tick = serde_json::from_str(line).expect("stdout line should be tick JSON");
+        assert!(tick.validate().is_ok(), "tick should be well formed");
+    }
+}
@@ -0,0 +1,179 @@
+//! Helpers shared by the integration tests; each test crate uses a subset.
+#![allow(dead_code)]
+
+use std::{
+    io::{ErrorKind, Read},
+    net::{SocketAddr, TcpStream},
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::UnixStream,
+};
+use tokio_tungstenite::{tungstenite::Error as WsError, MaybeTlsStream, WebSocketStream};
+
+pub type Client = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Connect once the gateway is listening, returning handshake failures.
+pub async fn try_connect(url: &str) -> Result<Client, WsError> {
+    let mut attempts = 0usize;
+    loop {
+        match tokio_tungstenite::connect_async(url).await {
+            Ok((stream, _)) => return Ok(stream),
+            Err(WsError::Io(err))
+                if err.kind() == ErrorKind::ConnectionRefused && attempts < 20 =>
+            {
+                attempts += 1;
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Connect once the gateway is listening.
+pub async fn connect(url: &str) -> Client {
+    try_connect(url)
+        .await
+        .unwrap_or_else(|err| panic!("connect websocket: {err:?}"))
+}
+
+/// Connect once the simulator has bound its Unix socket.
+pub async fn connect_unix(path: &Path) -> UnixStream {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        match UnixStream::connect(path).await {
+            Ok(stream) => return stream,
+            Err(err) if Instant::now() >= deadline => panic!("connect socket: {err}"),
+            Err(_) => tokio::time::sleep(Duration::from_millis(20)).await,
+        }
+    }
+}
+
+/// Issue a bare HTTP/1.1 GET and return the status code and JSON body.
+pub async fn get_json(addr: SocketAddr, path: &str) -> (u16, serde_json::Value) {
+    let mut attempts = 0usize;
+    let mut stream = loop {
+        match tokio::net::TcpStream::connect(addr).await {
+            Ok(stream) => break stream,
+            Err(err) if err.kind() == ErrorKind::ConnectionRefused && attempts < 20 => {
+                attempts += 1;
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            Err(err) => panic!("connect gateway: {err:?}"),
+        }
+    };
+    let request = format!("GET {path} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n");
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .expect("send request");
+    let mut raw = String::new();
+    stream
+        .read_to_string(&mut raw)
+        .await
+        .expect("read response");
+
+    let (head, body) = raw.split_once("\r\n\r\n").expect("response head");
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .expect("status code");
+    let body = serde_json::from_str(body).expect("json body");
+    (status, body)
+}
+
+/// Poll `/readyz` until the gateway reports lost batches, as it does once a
+/// client that stopped reading lags the broadcast.
+pub async fn wait_until_lagging(addr: SocketAddr) {
+    tokio::time::timeout(Duration::from_secs(20), async {
+        while get_json(addr, "/readyz").await.1["status"] == "healthy" {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("gateway should report the lagging client");
+}
+
+/// A `rust-market-data run` child process, so signals reach it rather than
+/// the test harness.
+pub struct Simulator {
+    child: Child,
+    stdout: JoinHandle<String>,
+    config_dir: PathBuf,
+}
+
+impl Simulator {
+    /// Run the binary with `config` as its TOML config file; `name` keeps
+    /// concurrent tests' config files apart.
+    pub fn spawn(name: &str, config: &str) -> Self {
+        let config_dir =
+            std::env::temp_dir().join(format!("simulator-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&config_dir).unwrap();
+        let path = config_dir.join("simulator.toml");
+        std::fs::write(&path, config).unwrap();
+
+        let mut child = Command::new(env!("CARGO_BIN_EXE_rust-market-data"))
+            .args(["run", "--config", path.to_str().unwrap()])
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("spawn rust-market-data run");
+        // drain the logs as they come so a full pipe never stalls the child
+        let mut pipe = child.stdout.take().expect("child stdout");
+        let stdout = thread::spawn(move || {
+            let mut logs = String::new();
+            pipe.read_to_string(&mut logs).expect("read child stdout");
+            logs
+        });
+        Self {
+            child,
+            stdout,
+            config_dir,
+        }
+    }
+
+    /// Block until the gateway accepts connections; the signal handlers are
+    /// installed before it starts listening.
+    pub fn wait_for_gateway(&self, addr: SocketAddr) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while TcpStream::connect(addr).is_err() {
+            assert!(
+                Instant::now() < deadline,
+                "gateway at {addr} never accepted connections"
+            );
+            thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    /// Send `signal` (e.g. `-TERM`) to the child.
+    pub fn signal(&self, signal: &str) {
+        let status = Command::new("kill")
+            .args([signal, &self.child.id().to_string()])
+            .status()
+            .expect("invoke kill");
+        assert!(status.success(), "kill {signal} should succeed");
+    }
+
+    /// Wait for the process to exit, returning its status and everything it logged.
+    pub fn wait(mut self) -> (ExitStatus, String) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let status = loop {
+            if let Some(status) = self.child.try_wait().expect("poll child") {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = self.child.kill();
+                panic!("simulator did not stop within 10s");
+            }
+            thread::sleep(Duration::from_millis(50));
+        };
+        let logs = self.stdout.join().expect("stdout reader");
+        std::fs::remove_dir_all(&self.config_dir).ok();
+        (status, logs)
+    }
+}
@@ -0,0 +1,178 @@
+use std::{collections::HashMap, path::PathBuf, process::Command as Process, time::Duration};
+
+use clap::Parser;
+use rust_market_data::{
+    cli::{Cli, Command},
+    model::{Region, Sector},
+    simulator::{
+        Aggregation, ClientLagPolicy, ConnectionRateLimit, EmissionOrder, HealthThresholds,
+        SectorCorrelation, SimulatorConfig,
+    },
+};
+
+fn example_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("simulator.example.toml")
+}
+
+fn expected_example() -> SimulatorConfig {
+    SimulatorConfig {
+        tick_interval: Duration::from_millis(10),
+        correlation_refresh: Duration::from_secs(300),
+        seed: Some(42),
+        regions: Some(vec![Region::NorthAmerica, Region::Europe]),
+        max_correlation: Some(0.99),
+        emission_order: EmissionOrder::ByRegion,
+        client_lag_policy: ClientLagPolicy::SnapshotResync,
+        gateway_addr: "127.0.0.1:9001".parse().unwrap(),
+        gateway_throttle: Duration::from_millis(500),
+        gateway_aggregation: Aggregation::Twap,
+        wire_decimals: Some(4),
+        region_sessions: HashMap::from([
+            (Region::NorthAmerica, (870, 1260)),
+            (Region::Europe, (480, 990)),
+        ]),
+        health_thresholds: HealthThresholds {
+            degraded_per_sec: 2.0,
+            unhealthy_per_sec: 50.0,
+        },
+        gateway_connection_limit: Some(ConnectionRateLimit {
+            max_connections: 20,
+            window: Duration::from_secs(60),
+        }),
+        sector_correlation: SectorCorrelation::identity()
+            .with_pair(Sector::Technology, Sector::ConsumerDiscretionary, 0.4)
+            .with_pair(Sector::Energy, Sector::Materials, 0.3),
+        ..SimulatorConfig::default()
+    }
+}
+
+fn run_config(args: &[&str]) -> anyhow::Result<SimulatorConfig> {
+    let cli = Cli::try_parse_from(["rust-market-data", "run"].iter().chain(args))?;
+    let Command::Run(args) = cli.command() else {
+        panic!("expected the run command");
+    };
+    args.into_config()
+}
+
+#[test]
+fn example_config_file_loads_with_defaults_for_missing_keys() {
+    let config = SimulatorConfig::from_toml_file(&example_path()).expect("load example config");
+    assert_eq!(config, expected_example());
+}
+
+#[test]
+fn command_line_flags_override_the_config_file() {
+    let path = example_path();
+    let path = path.to_str().unwrap();
+
+    assert_eq!(
+        run_config(&["--config", path]).expect("config"),
+        expected_example()
+    );
+    let overridden =
+        run_config(&["--config", path, "--seed", "7", "--tail-n", "3"]).expect("config");
+    assert_eq!(
+        overridden,
+        SimulatorConfig {
+            seed: Some(7),
+            socket_backlog: 3,
+            ..expected_example()
+        }
+    );
+    assert_eq!(run_config(&[]).expect("config"), SimulatorConfig::default());
+}
+
+#[test]
+fn configs_round_trip_through_json() {
+    for config in [SimulatorConfig::default(), expected_example()] {
+        let json = serde_json::to_string(&config).expect("serialize config");
+        let parsed: SimulatorConfig = serde_json::from_str(&json).expect("deserialize config");
+        assert_eq!(parsed, config, "{json}");
+    }
+}
+
+#[test]
+fn startup_log_records_the_effective_config() {
+    let dir = std::env::temp_dir().join(format!("config-log-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("short.toml");
+    std::fs::write(
+        &path,
+        "enable_socket = false\nenable_gateway = false\ntick_interval = \"1ms\"\nmax_ticks = 20\ngateway_aggregation = \"mean\"\n",
+    )
+    .unwrap();
+
+    let output = Process::new(env!("CARGO_BIN_EXE_rust-market-data"))
+        .args(["run", "--config", path.to_str().unwrap(), "--seed", "5"])
+        .output()
+        .expect("invoke rust-market-data run");
+    std::fs::remove_dir_all(&dir).ok();
+    assert!(output.status.success(), "{output:?}");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let logged = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find(|entry| entry["event"] == "app.config")
+        .expect("app.config log line");
+    let logged: SimulatorConfig =
+        serde_json::from_value(logged["metadata"].clone()).expect("logged config");
+    assert_eq!(
+        logged,
+        SimulatorConfig {
+            enable_socket: false,
+            enable_gateway: false,
+            tick_interval: Duration::from_millis(1),
+            max_ticks: Some(20),
+            gateway_aggregation: Aggregation::Mean,
+            seed: Some(5),
+            ..SimulatorConfig::default()
+        }
+    );
+}
+
+#[test]
+fn unknown_keys_and_bad_values_are_rejected() {
+    let dir = std::env::temp_dir().join(format!("config-file-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for (name, contents) in [
+        ("typo.toml", "tick_intervall = \"10ms\"\n"),
+        ("duration.toml", "tick_interval = \"ten\"\n"),
+        ("enum.toml", "gateway_aggregation = \"median\"\n"),
+        ("session.toml", "[region_sessions]\neurope = [480, 1440]\n"),
+        (
+            "empty-session.toml",
+            "[region_sessions]\neurope = [480, 480]\n",
+        ),
+        ("weights.toml", "[index_weights]\n\"NA TECH\" = 2.0\n"),
+        ("betas.toml", "[market_betas]\n\"\" = 1.5\n"),
+        (
+            "health.toml",
+            "[health_thresholds]\ndegraded_per_sec = 60.0\nunhealthy_per_sec = 50.0\n",
+        ),
+    ] {
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        let err = SimulatorConfig::from_toml_file(&path).expect_err(name);
+        assert!(format!("{err:#}").contains(name), "{err:#}");
+    }
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn zero_gateway_queue_depth_is_a_configuration_error() {
+    let config = SimulatorConfig {
+        enable_socket: false,
+        enable_gateway: false,
+        gateway_queue_depth: 0,
+        max_ticks: Some(10),
+        ..SimulatorConfig::default()
+    };
+    let err = rust_market_data::simulator::run_with_config(config)
+        .await
+        .expect_err("zero queue depth");
+    assert!(
+        format!("{err:#}").contains("gateway_queue_depth must be at least 1"),
+        "{err:#}"
+    );
+}
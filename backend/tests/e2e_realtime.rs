@@ -1,8 +1,12 @@
+mod common;
+
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::time::Duration;
 
+use common::connect;
 use futures_util::StreamExt;
 use rust_market_data::{
+    constants::TICK_BATCH_VERSION,
     simulator::{self, SimulatorConfig},
     tick::Tick,
 };
@@ -16,8 +20,19 @@ struct TickBatchPayload {
     ticks: Vec<Tick>,
 }
 
-async fn start_simulator() -> JoinHandle<()> {
-    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9124);
+#[derive(Deserialize)]
+struct MetaPayload {
+    #[serde(rename = "type")]
+    kind: String,
+    version: u32,
+    throttle_ms: u64,
+    symbols: Vec<serde_json::Value>,
+    regions: Vec<String>,
+    sectors: Vec<String>,
+}
+
+async fn start_simulator(port: u16) -> JoinHandle<()> {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
     let config = SimulatorConfig {
         enable_socket: false,
         gateway_addr: addr,
@@ -33,24 +48,34 @@ async fn start_simulator() -> JoinHandle<()> {
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
-async fn websocket_stream_emits_batches() {
-    let handle = start_simulator().await;
+async fn websocket_stream_starts_with_meta_frame() {
+    let handle = start_simulator(9126).await;
+    let mut ws = connect("ws://127.0.0.1:9126/ws").await;
 
-    let connect_addr = "ws://127.0.0.1:9124/ws";
-    let (mut ws, _) = loop {
-        match tokio_tungstenite::connect_async(connect_addr).await {
-            Ok(conn) => break conn,
-            Err(err) => {
-                if let tokio_tungstenite::tungstenite::Error::Io(io) = &err {
-                    if matches!(io.kind(), std::io::ErrorKind::ConnectionRefused) {
-                        tokio::time::sleep(Duration::from_millis(100)).await;
-                        continue;
-                    }
-                }
-                panic!("failed to connect to gateway: {err}");
-            }
-        }
+    let first = tokio::time::timeout(Duration::from_secs(5), ws.next())
+        .await
+        .expect("websocket message timeout")
+        .expect("stream ended before meta frame")
+        .expect("websocket error");
+    let Message::Text(payload) = first else {
+        panic!("expected text meta frame, got {first:?}");
     };
+    let meta: MetaPayload = serde_json::from_str(&payload).expect("valid meta payload");
+    assert_eq!(meta.kind, "meta");
+    assert_eq!(meta.version, TICK_BATCH_VERSION, "unexpected meta version");
+    assert_eq!(meta.throttle_ms, 500);
+    assert_eq!(meta.symbols.len(), 500, "expected the full universe");
+    assert_eq!(meta.regions.len(), 5);
+    assert_eq!(meta.sectors.len(), 10);
+
+    let _ = ws.close(None).await;
+    handle.abort();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn websocket_stream_emits_batches() {
+    let handle = start_simulator(9124).await;
+    let mut ws = connect("ws://127.0.0.1:9124/ws").await;
 
     let mut total_batches = 0usize;
     let mut total_ticks = 0usize;
@@ -62,9 +87,15 @@ async fn websocket_stream_emits_batches() {
 
         match maybe_message {
             Some(Ok(Message::Text(payload))) => {
-                let batch: TickBatchPayload =
-                    serde_json::from_str(&payload).expect("valid payload");
-                assert_eq!(batch.version, 1, "unexpected batch version");
+                let frame: serde_json::Value = serde_json::from_str(&payload).expect("valid frame");
+                if frame["type"] == "meta" {
+                    continue;
+                }
+                let batch: TickBatchPayload = serde_json::from_value(frame).expect("valid payload");
+                assert_eq!(
+                    batch.version, TICK_BATCH_VERSION,
+                    "unexpected batch version"
+                );
                 assert!(!batch.ticks.is_empty(), "empty batch received");
                 total_batches += 1;
                 total_ticks += batch.ticks.len();
@@ -0,0 +1,44 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use rust_market_data::simulator::{self, ShutdownReason, SimulatorConfig};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn embedded_simulator_exposes_latest_snapshot() {
+    let config = SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9132),
+        gateway_throttle: Duration::from_millis(100),
+        tick_interval: Duration::from_millis(4),
+        max_ticks: None,
+        ..SimulatorConfig::default()
+    };
+    let handle = simulator::spawn(config);
+
+    let snapshot = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let snapshot = handle.snapshot();
+            if !snapshot.is_empty() {
+                break snapshot;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("no batch reached the snapshot");
+
+    assert!(snapshot
+        .windows(2)
+        .all(|pair| pair[0].symbol < pair[1].symbol));
+    assert!(snapshot.iter().all(|tick| tick.validate().is_ok()));
+    assert!(!handle.sector_indices().is_empty());
+
+    handle.shutdown();
+    let reason = tokio::time::timeout(Duration::from_secs(5), handle.join())
+        .await
+        .expect("simulator should stop")
+        .expect("simulator run");
+    assert_eq!(reason, ShutdownReason::Terminated);
+}
@@ -0,0 +1,49 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use rust_market_data::simulator::{self, BindRetry, SimulatorConfig};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn gateway_binds_once_a_lingering_listener_releases_the_port() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9134);
+    // stands in for the previous instance still holding the port
+    let occupant = std::net::TcpListener::bind(addr).expect("occupy gateway port");
+
+    let handle = simulator::spawn(SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: addr,
+        tick_interval: Duration::from_millis(4),
+        max_ticks: None,
+        gateway_bind_retry: BindRetry {
+            max_attempts: 30,
+            base_delay: Duration::from_millis(50),
+            max_delay: Duration::from_millis(200),
+        },
+        ..SimulatorConfig::default()
+    });
+
+    // The gateway binds alongside the dispatcher, so a published snapshot
+    // means the first bind has already hit the occupied port.
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while handle.snapshot().is_empty() {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("simulator should publish a snapshot");
+    drop(occupant);
+
+    let url = format!("ws://{addr}/ws");
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while tokio_tungstenite::connect_async(&url).await.is_err() {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("gateway should bind after the port is released");
+
+    handle.shutdown();
+    handle.join().await.expect("simulator run");
+}
@@ -0,0 +1,70 @@
+//! Run with `cargo test -p rust-market-data --features chaos --test gateway_chaos`.
+#![cfg(feature = "chaos")]
+
+mod common;
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::{Duration, Instant},
+};
+
+use common::connect;
+use futures_util::StreamExt;
+use rust_market_data::simulator::{self, ChaosConfig, SimulatorConfig};
+use tokio_tungstenite::tungstenite::{protocol::frame::coding::CloseCode, Message};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn connections_are_delayed_and_dropped_on_schedule() {
+    const DROP_EVERY: Duration = Duration::from_millis(400);
+    const DELAY: Duration = Duration::from_millis(150);
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9139);
+    let handle = simulator::spawn(SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: addr,
+        gateway_throttle: Duration::from_millis(50),
+        tick_interval: Duration::from_millis(4),
+        max_ticks: None,
+        chaos: Some(ChaosConfig {
+            drop_every: Some(DROP_EVERY),
+            delay: Some(DELAY),
+        }),
+        ..SimulatorConfig::default()
+    });
+
+    let url = format!("ws://{addr}/ws");
+    for connection in 0..3 {
+        let started = Instant::now();
+        let mut ws = connect(&url).await;
+        assert!(
+            started.elapsed() >= DELAY,
+            "connection {connection} completed its handshake after {:?}",
+            started.elapsed()
+        );
+
+        let opened = Instant::now();
+        let close = tokio::time::timeout(Duration::from_secs(5), async {
+            while let Some(message) = ws.next().await {
+                match message {
+                    Ok(Message::Close(frame)) => return frame,
+                    Ok(_) => continue,
+                    Err(err) => panic!("websocket error before close frame: {err:?}"),
+                }
+            }
+            None
+        })
+        .await
+        .expect("connection dropped within the timeout")
+        .expect("close frame");
+        let lifetime = opened.elapsed();
+
+        assert_eq!(close.code, CloseCode::Restart);
+        assert!(
+            lifetime >= DROP_EVERY - Duration::from_millis(50)
+                && lifetime < DROP_EVERY + Duration::from_millis(300),
+            "connection {connection} lived {lifetime:?}, expected about {DROP_EVERY:?}"
+        );
+    }
+
+    handle.shutdown();
+    handle.join().await.expect("simulator run");
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use common::connect;
+use futures_util::StreamExt;
+use rust_market_data::{
+    model::{Region, Sector},
+    simulator::{self, SimulatorConfig},
+};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Allowed distance between a batch arriving and the nearest whole second.
+const BOUNDARY_TOLERANCE_MS: u128 = 150;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn aligned_batches_arrive_on_second_boundaries() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9128);
+    let config = SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: addr,
+        gateway_throttle: Duration::from_secs(1),
+        align_batches_to_clock: true,
+        // a light generator, so batch timing is not at the mercy of a busy CPU
+        tick_interval: Duration::from_millis(20),
+        regions: Some(vec![Region::Europe]),
+        sectors: Some(vec![Sector::Energy]),
+        max_ticks: None,
+        ..SimulatorConfig::default()
+    };
+
+    let simulator_task = tokio::spawn(async move {
+        simulator::run_with_config(config)
+            .await
+            .expect("simulator run");
+    });
+
+    let mut ws_stream = connect(&format!("ws://{addr}/ws")).await;
+
+    let mut arrivals_ms = Vec::new();
+    while arrivals_ms.len() < 3 {
+        let message = tokio::time::timeout(Duration::from_secs(5), ws_stream.next())
+            .await
+            .expect("batch within timeout")
+            .expect("open stream")
+            .expect("websocket message");
+        let Message::Text(payload) = message else {
+            continue;
+        };
+        let frame: serde_json::Value = serde_json::from_str(&payload).expect("gateway frame");
+        if frame["type"] == "batch" {
+            arrivals_ms.push(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("clock after epoch")
+                    .as_millis(),
+            );
+        }
+    }
+
+    for arrival in &arrivals_ms {
+        let offset = arrival % 1_000;
+        let distance = offset.min(1_000 - offset);
+        assert!(
+            distance <= BOUNDARY_TOLERANCE_MS,
+            "batch arrived {distance}ms from a second boundary (arrivals: {arrivals_ms:?})"
+        );
+    }
+
+    let _ = ws_stream.close(None).await;
+    simulator_task.abort();
+    let _ = simulator_task.await;
+}
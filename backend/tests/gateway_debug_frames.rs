@@ -0,0 +1,78 @@
+mod common;
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use common::{connect, Client};
+use futures_util::StreamExt;
+use rust_market_data::simulator::{self, SimulatorConfig};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Collect the `type` of every frame received within `window`.
+async fn frame_types(client: &mut Client, window: Duration) -> Vec<String> {
+    let mut types = Vec::new();
+    let deadline = tokio::time::Instant::now() + window;
+    while let Ok(Some(message)) = tokio::time::timeout_at(deadline, client.next()).await {
+        if let Message::Text(payload) = message.expect("websocket message") {
+            let frame: serde_json::Value = serde_json::from_str(&payload).expect("gateway frame");
+            if frame["type"] == "debug" {
+                assert!(frame["batch_count"].is_u64(), "debug frame: {frame}");
+                assert!(frame["avg_symbols"].is_f64(), "debug frame: {frame}");
+            }
+            types.push(frame["type"].as_str().unwrap_or_default().to_string());
+        }
+    }
+    types
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn debug_frames_only_reach_opted_in_clients() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9129);
+    let config = SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: addr,
+        gateway_throttle: Duration::from_millis(200),
+        tick_interval: Duration::from_millis(4),
+        max_ticks: None,
+        ..SimulatorConfig::default()
+    };
+
+    let simulator_task = tokio::spawn(async move {
+        simulator::run_with_config(config)
+            .await
+            .expect("simulator run");
+    });
+
+    let mut debug_client = connect(&format!("ws://{addr}/ws?debug=1")).await;
+    let mut plain_client = connect(&format!("ws://{addr}/ws")).await;
+
+    let window = Duration::from_millis(2_500);
+    let (debug_types, plain_types) = tokio::join!(
+        frame_types(&mut debug_client, window),
+        frame_types(&mut plain_client, window)
+    );
+
+    assert!(
+        debug_types.iter().any(|kind| kind == "debug"),
+        "debug client saw {debug_types:?}"
+    );
+    assert!(
+        debug_types.iter().any(|kind| kind == "batch"),
+        "debug frames are interleaved with data: {debug_types:?}"
+    );
+    assert!(
+        plain_types.iter().any(|kind| kind == "batch"),
+        "plain client saw {plain_types:?}"
+    );
+    assert!(
+        plain_types.iter().all(|kind| kind != "debug"),
+        "plain client must not receive debug frames: {plain_types:?}"
+    );
+
+    let _ = debug_client.close(None).await;
+    let _ = plain_client.close(None).await;
+    simulator_task.abort();
+    let _ = simulator_task.await;
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use common::{connect, Client, Simulator};
+
+use futures_util::StreamExt;
+use rust_market_data::simulator::{self, Regime, SimulatorConfig};
+use tokio_tungstenite::tungstenite::Message;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn heartbeats_keep_their_cadence_while_paused() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9133);
+    // a child process, so the pause signal does not reach the test harness
+    let simulator = Simulator::spawn(
+        "heartbeat",
+        &format!(
+            "enable_socket = false\ngateway_addr = \"{addr}\"\ngateway_throttle = \"100ms\"\n\
+             tick_interval = \"4ms\"\nheartbeat_enabled = true\nheartbeat_interval = \"200ms\"\n"
+        ),
+    );
+    simulator.wait_for_gateway(addr);
+    let mut client = connect(&format!("ws://{addr}/ws")).await;
+
+    // SIGUSR1 pauses tick generation; the handler is installed before the gateway listens
+    simulator.signal("-USR1");
+    // let batches already in flight drain before measuring
+    let settled = tokio::time::Instant::now() + Duration::from_millis(500);
+    while let Ok(Some(_)) = tokio::time::timeout_at(settled, client.next()).await {}
+
+    let window = Duration::from_millis(2_000);
+    let deadline = tokio::time::Instant::now() + window;
+    let mut heartbeats = Vec::new();
+    let mut newest_ticks = Vec::new();
+    while let Ok(Some(message)) = tokio::time::timeout_at(deadline, client.next()).await {
+        let Message::Text(payload) = message.expect("websocket message") else {
+            continue;
+        };
+        let frame: serde_json::Value = serde_json::from_str(&payload).expect("gateway frame");
+        match frame["type"].as_str() {
+            Some("heartbeat") => {
+                assert_eq!(frame["regime"], "calm", "heartbeat: {frame}");
+                assert!(frame["open_regions"].is_null(), "heartbeat: {frame}");
+                heartbeats.push(frame["uptime_secs"].as_f64().expect("uptime"));
+            }
+            Some("batch") => newest_ticks.push(
+                frame["ticks"]
+                    .as_array()
+                    .expect("batch ticks")
+                    .iter()
+                    .filter_map(|tick| tick["timestamp_ms"].as_u64())
+                    .max(),
+            ),
+            _ => {}
+        }
+    }
+
+    // the gateway keeps re-sending the latest ticks, but none of them are new
+    assert!(
+        newest_ticks.windows(2).all(|pair| pair[0] == pair[1]),
+        "paused generator should publish no new ticks: {newest_ticks:?}"
+    );
+    // ~10 heartbeats fit in the window at a 200ms cadence
+    assert!(
+        (8..=12).contains(&heartbeats.len()),
+        "expected a heartbeat every 200ms, got {}",
+        heartbeats.len()
+    );
+    for pair in heartbeats.windows(2) {
+        let gap = pair[1] - pair[0];
+        assert!(
+            (0.1..0.3).contains(&gap),
+            "heartbeats should be ~200ms apart, saw {gap:.3}s"
+        );
+    }
+
+    let _ = client.close(None).await;
+    simulator.signal("-TERM");
+    let (status, _) = tokio::task::spawn_blocking(move || simulator.wait())
+        .await
+        .expect("wait task");
+    assert!(status.success(), "{status}");
+}
+
+/// Regime reported by the next heartbeat frame.
+async fn next_heartbeat_regime(client: &mut Client) -> String {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(2), client.next())
+            .await
+            .expect("heartbeat within 2s")
+            .expect("stream open")
+            .expect("websocket message");
+        let Message::Text(payload) = message else {
+            continue;
+        };
+        let frame: serde_json::Value = serde_json::from_str(&payload).expect("gateway frame");
+        if frame["type"] == "heartbeat" {
+            return frame["regime"].as_str().expect("regime").to_string();
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn heartbeats_report_the_live_regime() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9142);
+    let handle = simulator::spawn(SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: addr,
+        tick_interval: Duration::from_millis(4),
+        max_ticks: None,
+        heartbeat_enabled: true,
+        heartbeat_interval: Duration::from_millis(100),
+        ..SimulatorConfig::default()
+    });
+    let mut client = connect(&format!("ws://{addr}/ws")).await;
+
+    assert_eq!(next_heartbeat_regime(&mut client).await, "calm");
+    handle.set_regime(Regime::Stressed);
+    assert_eq!(handle.regime(), Regime::Stressed);
+    assert_eq!(next_heartbeat_regime(&mut client).await, "stressed");
+
+    let _ = client.close(None).await;
+    handle.shutdown();
+    handle.join().await.expect("simulator run");
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use common::{get_json, try_connect};
+use rust_market_data::simulator::{self, SimulatorConfig};
+use tokio_tungstenite::tungstenite::{http::StatusCode, Error as WsError};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn snapshot_errors_are_json_envelopes() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9138);
+    let handle = simulator::spawn(SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: addr,
+        // no batch is dispatched during the test, so the snapshot stays empty
+        gateway_throttle: Duration::from_secs(600),
+        max_ticks: None,
+        ..SimulatorConfig::default()
+    });
+
+    let (status, body) = get_json(addr, "/snapshot").await;
+    assert_eq!(status, 503, "{body}");
+    assert_eq!(body["code"], 503);
+    assert!(body["error"].as_str().unwrap().contains("no gateway batch"));
+
+    let (status, body) = get_json(addr, "/snapshot?region=mars").await;
+    assert_eq!(status, 400, "{body}");
+    assert_eq!(body["code"], 400);
+    let message = body["error"].as_str().expect("error message");
+    assert!(message.contains("\"mars\""), "{message}");
+    assert!(message.contains("europe"), "{message}");
+
+    let (status, body) = get_json(addr, "/snapshot?sector=energy&region=asia_pacific").await;
+    assert_eq!(
+        status, 503,
+        "valid filters reach the readiness check: {body}"
+    );
+
+    // a repeated parameter fails to deserialize, like any malformed query
+    match try_connect(&format!("ws://{addr}/ws?debug=1&debug=0")).await {
+        Err(WsError::Http(response)) => {
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let body: serde_json::Value =
+                serde_json::from_slice(response.body().as_deref().expect("error body"))
+                    .expect("json error envelope");
+            assert_eq!(body["code"], 400);
+            let message = body["error"].as_str().expect("error message");
+            assert!(message.contains("duplicate field"), "{message}");
+        }
+        Ok(_) => panic!("a malformed websocket query should be rejected"),
+        Err(err) => panic!("unexpected websocket error: {err:?}"),
+    }
+
+    handle.shutdown();
+    handle.join().await.expect("simulator run");
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use common::{connect, Client};
+use futures_util::{SinkExt, StreamExt};
+use rust_market_data::simulator::{self, SimulatorConfig};
+use tokio_tungstenite::tungstenite::{protocol::frame::coding::CloseCode, Message};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn oversized_client_message_closes_connection() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9130);
+    let config = SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: addr,
+        gateway_throttle: Duration::from_millis(100),
+        max_inbound_message_bytes: 1024,
+        tick_interval: Duration::from_millis(4),
+        max_ticks: None,
+        ..SimulatorConfig::default()
+    };
+
+    let simulator_task = tokio::spawn(async move {
+        simulator::run_with_config(config)
+            .await
+            .expect("simulator run");
+    });
+
+    let url = format!("ws://{addr}/ws");
+    let mut ws = connect(&url).await;
+
+    // Messages within the limit are tolerated.
+    ws.send(Message::Text("x".repeat(512)))
+        .await
+        .expect("send small message");
+    ws.send(Message::Text("x".repeat(4096)))
+        .await
+        .expect("send oversized message");
+
+    assert_policy_close(&mut ws).await;
+
+    simulator_task.abort();
+    let _ = simulator_task.await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn oversized_trades_client_message_closes_connection() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9146);
+    let handle = simulator::spawn(SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: addr,
+        max_inbound_message_bytes: 1024,
+        tick_interval: Duration::from_millis(4),
+        max_ticks: None,
+        trade_rate: 2.0,
+        ..SimulatorConfig::default()
+    });
+
+    let mut ws = connect(&format!("ws://{addr}/ws/trades")).await;
+    ws.send(Message::Text("x".repeat(4096)))
+        .await
+        .expect("send oversized message");
+    assert_policy_close(&mut ws).await;
+
+    handle.shutdown();
+    handle.join().await.expect("simulator run");
+}
+
+/// Read until the gateway's close frame and check it reports the size limit.
+async fn assert_policy_close(ws: &mut Client) {
+    let close = tokio::time::timeout(Duration::from_secs(5), async {
+        while let Some(message) = ws.next().await {
+            match message {
+                Ok(Message::Close(frame)) => return frame,
+                Ok(_) => continue,
+                Err(err) => panic!("websocket error before close frame: {err:?}"),
+            }
+        }
+        panic!("stream ended without a close frame");
+    })
+    .await
+    .expect("oversized message should close the socket");
+
+    let frame = close.expect("close frame payload");
+    assert_eq!(frame.code, CloseCode::Policy);
+    assert_eq!(frame.reason, "message too large");
+}
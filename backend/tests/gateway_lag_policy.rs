@@ -0,0 +1,128 @@
+mod common;
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use common::{connect, wait_until_lagging};
+use futures_util::StreamExt;
+use market_core::{GatewayMessage, WireMessage};
+use rust_market_data::{
+    model::default_equities,
+    simulator::{self, ClientLagPolicy, SimulatorConfig},
+};
+use tokio_tungstenite::tungstenite::{protocol::frame::coding::CloseCode, Message};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn close_on_lag_disconnects_slow_reader() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9127);
+    let config = SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: addr,
+        gateway_throttle: Duration::from_millis(1),
+        gateway_queue_depth: 1,
+        client_lag_policy: ClientLagPolicy::CloseOnLag,
+        // slow enough that the generator leaves CPU for the gateway to stream
+        // the full universe at the 1ms throttle and fill the socket buffers
+        tick_interval: Duration::from_millis(20),
+        max_ticks: None,
+        ..SimulatorConfig::default()
+    };
+
+    let simulator_task = tokio::spawn(async move {
+        simulator::run_with_config(config)
+            .await
+            .expect("simulator run");
+    });
+
+    let url = format!("ws://{addr}/ws");
+    let mut ws = connect(&url).await;
+
+    // Stop reading until socket buffers fill and the broadcast lags.
+    wait_until_lagging(addr).await;
+
+    let close = tokio::time::timeout(Duration::from_secs(20), async {
+        while let Some(message) = ws.next().await {
+            match message {
+                Ok(Message::Close(frame)) => return frame,
+                Ok(_) => continue,
+                Err(err) => panic!("websocket error before close frame: {err:?}"),
+            }
+        }
+        panic!("stream ended without a close frame");
+    })
+    .await
+    .expect("lagging client should be closed");
+
+    let frame = close.expect("close frame payload");
+    assert_eq!(frame.code, CloseCode::Again);
+    assert_eq!(frame.reason, "lagged");
+
+    simulator_task.abort();
+    let _ = simulator_task.await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn snapshot_resync_sends_lagging_reader_the_latest_snapshot() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9143);
+    let config = SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: addr,
+        gateway_throttle: Duration::from_millis(1),
+        gateway_queue_depth: 1,
+        client_lag_policy: ClientLagPolicy::SnapshotResync,
+        // same load as the close-on-lag test so the stalled reader really lags
+        tick_interval: Duration::from_millis(20),
+        max_ticks: None,
+        ..SimulatorConfig::default()
+    };
+    let universe = default_equities().len();
+
+    let simulator_task = tokio::spawn(async move {
+        simulator::run_with_config(config)
+            .await
+            .expect("simulator run");
+    });
+
+    let url = format!("ws://{addr}/ws");
+    let mut ws = connect(&url).await;
+
+    // Stop reading until socket buffers fill and the broadcast lags.
+    wait_until_lagging(addr).await;
+    let resumed_ms = now_ms();
+
+    // Frames buffered before the lag are stale; the resync brings the reader
+    // back to the full universe as of after the stall, on the same connection.
+    tokio::time::timeout(Duration::from_secs(20), async {
+        while let Some(message) = ws.next().await {
+            let payload = match message {
+                Ok(Message::Text(payload)) => payload,
+                Ok(Message::Close(frame)) => panic!("resync policy closed the client: {frame:?}"),
+                Ok(_) => continue,
+                Err(err) => panic!("websocket error while lagging: {err:?}"),
+            };
+            let frame: WireMessage = serde_json::from_str(&payload).expect("gateway frame");
+            let GatewayMessage::Batch(batch) = frame.into() else {
+                continue;
+            };
+            let newest_ms = batch.ticks.iter().map(|tick| tick.timestamp_ms).max();
+            if batch.ticks.len() == universe && newest_ms >= Some(resumed_ms) {
+                return;
+            }
+        }
+        panic!("stream ended before the resync snapshot");
+    })
+    .await
+    .expect("lagging client should be resynced");
+
+    simulator_task.abort();
+    let _ = simulator_task.await;
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock after epoch")
+        .as_millis() as u64
+}
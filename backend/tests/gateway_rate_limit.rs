@@ -0,0 +1,70 @@
+mod common;
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use common::connect;
+use rust_market_data::simulator::{self, ConnectionRateLimit, SimulatorConfig};
+use tokio_tungstenite::tungstenite::{http::StatusCode, Error as WsError};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn gateway_rejects_clients_exceeding_connection_rate() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9125);
+    let config = SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: addr,
+        gateway_connection_limit: Some(ConnectionRateLimit {
+            max_connections: 2,
+            window: Duration::from_secs(60),
+        }),
+        tick_interval: Duration::from_millis(4),
+        max_ticks: None,
+        ..SimulatorConfig::default()
+    };
+
+    let simulator_task = tokio::spawn(async move {
+        simulator::run_with_config(config)
+            .await
+            .expect("simulator run");
+    });
+
+    let url = format!("ws://{addr}/ws");
+    let mut open = vec![connect(&url).await];
+
+    // malformed queries are refused before they use up the allowance
+    for _ in 0..3 {
+        match tokio_tungstenite::connect_async(format!("{url}?debug=1&debug=0")).await {
+            Err(WsError::Http(response)) => {
+                assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            }
+            Ok(_) => panic!("malformed query should be refused"),
+            Err(err) => panic!("unexpected websocket error: {err:?}"),
+        }
+    }
+
+    let (ws, _) = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .expect("second connection within limit");
+    open.push(ws);
+
+    match tokio_tungstenite::connect_async(url.as_str()).await {
+        Err(WsError::Http(response)) => {
+            assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+            let body: serde_json::Value =
+                serde_json::from_slice(response.body().as_deref().expect("error body"))
+                    .expect("json error envelope");
+            assert_eq!(body["code"], 429);
+            assert_eq!(body["error"], "too many connection attempts");
+        }
+        Ok(_) => panic!("third connection should be rate limited"),
+        Err(err) => panic!("unexpected websocket error: {err:?}"),
+    }
+
+    for mut ws in open {
+        let _ = ws.close(None).await;
+    }
+    simulator_task.abort();
+    let _ = simulator_task.await;
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use common::connect;
+use futures_util::{SinkExt, StreamExt};
+use rust_market_data::simulator::{self, SimulatorConfig};
+use tokio_tungstenite::tungstenite::Message;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn stalled_reader_is_evicted_after_the_send_timeout() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9136);
+    let config = SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: addr,
+        gateway_throttle: Duration::from_millis(1),
+        // slow enough that the generator leaves CPU for the gateway to stream
+        // the full universe at the 1ms throttle and fill the socket buffers
+        tick_interval: Duration::from_millis(20),
+        client_send_timeout: Some(Duration::from_millis(200)),
+        max_ticks: None,
+        ..SimulatorConfig::default()
+    };
+    let handle = simulator::spawn(config);
+
+    let url = format!("ws://{addr}/ws");
+    let mut ws = connect(&url).await;
+    let first = ws.next().await.expect("stream open").expect("first frame");
+    assert!(first.is_text(), "client received frames before stalling");
+
+    // Stop reading so the socket buffers fill and the gateway's sends stall.
+    // Once the gateway drops the connection it refuses our pings, so keep
+    // writing until one fails instead of reading what was buffered.
+    tokio::time::timeout(Duration::from_secs(20), async {
+        while ws.send(Message::Ping(Vec::new())).await.is_ok() {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("stalled client should be evicted");
+
+    handle.shutdown();
+    handle.join().await.expect("simulator run");
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use std::{
+    collections::HashSet,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use common::{try_connect, Client};
+use futures_util::StreamExt;
+use market_core::{GatewayMessage, NoticeCode, WireMessage};
+use rust_market_data::simulator::{self, SimulatorConfig, SymbolOverflow};
+use tokio_tungstenite::tungstenite::{http::StatusCode, Error as WsError, Message};
+
+const REQUESTED: &str = "NATECH000,NATECH001,BOGUS,NATECH002";
+
+fn config(port: u16, overflow: SymbolOverflow) -> SimulatorConfig {
+    SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port),
+        gateway_throttle: Duration::from_millis(100),
+        tick_interval: Duration::from_millis(4),
+        max_ticks: None,
+        max_client_symbols: Some(2),
+        client_symbol_overflow: overflow,
+        ..SimulatorConfig::default()
+    }
+}
+
+async fn next_frame(client: &mut Client) -> GatewayMessage {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), client.next())
+            .await
+            .expect("frame within the timeout")
+            .expect("websocket open")
+            .expect("websocket message");
+        if let Message::Text(payload) = message {
+            let frame: WireMessage = serde_json::from_str(&payload).expect("gateway frame");
+            return frame.into();
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn oversized_subscriptions_are_truncated_with_notices() {
+    let config = config(9140, SymbolOverflow::Truncate);
+    let addr = config.gateway_addr;
+    let simulator_task = tokio::spawn(simulator::run_with_config(config));
+
+    let mut client = try_connect(&format!("ws://{addr}/ws?symbols={REQUESTED}"))
+        .await
+        .expect("connect websocket");
+    assert!(matches!(
+        next_frame(&mut client).await,
+        GatewayMessage::Meta(_)
+    ));
+    let notices: Vec<(NoticeCode, Vec<String>)> =
+        [next_frame(&mut client).await, next_frame(&mut client).await]
+            .into_iter()
+            .map(|frame| match frame {
+                GatewayMessage::Notice(notice) => (notice.code, notice.symbols),
+                other => panic!("expected a notice, got {other:?}"),
+            })
+            .collect();
+    assert_eq!(
+        notices,
+        vec![
+            (NoticeCode::UnknownSymbols, vec!["BOGUS".to_string()]),
+            (
+                NoticeCode::SubscriptionTruncated,
+                vec!["NATECH002".to_string()]
+            ),
+        ]
+    );
+
+    let subscribed: HashSet<&str> = HashSet::from(["NATECH000", "NATECH001"]);
+    let mut seen = HashSet::new();
+    for _ in 0..5 {
+        let GatewayMessage::Batch(batch) = next_frame(&mut client).await else {
+            continue;
+        };
+        for tick in batch.ticks {
+            assert!(subscribed.contains(tick.symbol.as_str()), "{}", tick.symbol);
+            seen.insert(tick.symbol.into_string());
+        }
+    }
+    assert!(!seen.is_empty(), "subscribed symbols should still stream");
+
+    let _ = client.close(None).await;
+    simulator_task.abort();
+    let _ = simulator_task.await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn oversized_subscriptions_are_rejected_when_configured() {
+    let config = config(9141, SymbolOverflow::Reject);
+    let addr = config.gateway_addr;
+    let simulator_task = tokio::spawn(simulator::run_with_config(config));
+
+    match try_connect(&format!("ws://{addr}/ws?symbols={REQUESTED}")).await {
+        Err(WsError::Http(response)) => {
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let body: serde_json::Value =
+                serde_json::from_slice(response.body().as_deref().expect("error body"))
+                    .expect("json error envelope");
+            assert_eq!(body["code"], 400);
+            assert_eq!(
+                body["error"],
+                "subscription names 3 symbols; the limit is 2"
+            );
+        }
+        Ok(_) => panic!("a subscription over the limit should be rejected"),
+        Err(err) => panic!("unexpected websocket error: {err:?}"),
+    }
+    // unknown symbols do not count towards the limit
+    let mut client = try_connect(&format!("ws://{addr}/ws?symbols=NATECH000,BOGUS,NATECH001"))
+        .await
+        .expect("subscription within the limit");
+    assert!(matches!(
+        next_frame(&mut client).await,
+        GatewayMessage::Meta(_)
+    ));
+
+    let _ = client.close(None).await;
+    simulator_task.abort();
+    let _ = simulator_task.await;
+}
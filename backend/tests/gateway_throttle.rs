@@ -1,17 +1,20 @@
+mod common;
+
 use std::{
     collections::{HashMap, HashSet},
-    io::ErrorKind,
     net::{IpAddr, Ipv4Addr, SocketAddr},
     time::{Duration, Instant},
 };
 
+use common::connect;
 use futures_util::StreamExt;
 use rust_market_data::{
+    constants::TICK_BATCH_VERSION,
     simulator::{self, SimulatorConfig},
     tick::Tick,
 };
 use serde::Deserialize;
-use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::tungstenite::Message;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn gateway_batches_symbols_once_per_interval() {
@@ -31,20 +34,7 @@ async fn gateway_batches_symbols_once_per_interval() {
             .expect("simulator run");
     });
 
-    let mut attempts = 0usize;
-    let (mut ws_stream, _) = loop {
-        match tokio_tungstenite::connect_async(format!("ws://{addr}/ws")).await {
-            Ok(conn) => break conn,
-            Err(WsError::Io(err))
-                if err.kind() == ErrorKind::ConnectionRefused && attempts < 20 =>
-            {
-                attempts += 1;
-                tokio::time::sleep(Duration::from_millis(100)).await;
-                continue;
-            }
-            Err(err) => panic!("connect websocket: {err:?}"),
-        }
-    };
+    let mut ws_stream = connect(&format!("ws://{addr}/ws")).await;
 
     let mut frames: Vec<(Instant, Vec<Tick>)> = Vec::new();
     let deadline = Instant::now() + Duration::from_secs(8);
@@ -53,10 +43,15 @@ async fn gateway_batches_symbols_once_per_interval() {
         if let Some(message) = ws_stream.next().await {
             let message = message.expect("websocket message");
             if let Message::Text(payload) = message {
+                let frame: serde_json::Value =
+                    serde_json::from_str(&payload).expect("gateway frame");
+                if frame["type"] == "meta" {
+                    continue;
+                }
                 let batch: TickBatchPayload =
-                    serde_json::from_str(&payload).expect("tick payload batch");
+                    serde_json::from_value(frame).expect("tick payload batch");
                 assert_eq!(
-                    batch.version, 1,
+                    batch.version, TICK_BATCH_VERSION,
                     "unexpected batch version {}",
                     batch.version
                 );
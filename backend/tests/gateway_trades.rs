@@ -0,0 +1,59 @@
+mod common;
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use common::connect;
+use futures_util::StreamExt;
+use market_core::{GatewayMessage, Side, WireMessage};
+use rust_market_data::simulator::{self, SimulatorConfig};
+use tokio_tungstenite::tungstenite::Message;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn trade_prints_fall_within_the_prevailing_spread() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9135);
+    let config = SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: addr,
+        tick_interval: Duration::from_millis(4),
+        max_ticks: None,
+        trade_rate: 2.0,
+        ..SimulatorConfig::default()
+    };
+    let spread_bps = config.base_spread_bps;
+    let handle = simulator::spawn(config);
+    let mut client = connect(&format!("ws://{addr}/ws/trades")).await;
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(2);
+    let mut trades = Vec::new();
+    while let Ok(Some(message)) = tokio::time::timeout_at(deadline, client.next()).await {
+        let Message::Text(payload) = message.expect("websocket message") else {
+            continue;
+        };
+        let frame: WireMessage = serde_json::from_str(&payload).expect("gateway frame");
+        match GatewayMessage::from(frame) {
+            GatewayMessage::Trades(frame) => trades.extend(frame.trades),
+            other => panic!("trades route sent a non-trade frame: {other:?}"),
+        }
+    }
+
+    // 500 symbols at 2 prints/s each should produce hundreds of prints
+    assert!(trades.len() > 100, "only {} trades", trades.len());
+    for trade in &trades {
+        assert!(trade.within_spread(), "{trade:?}");
+        let mid = (trade.bid + trade.ask) / 2.0;
+        let quoted_bps = (trade.ask - trade.bid) / mid * 10_000.0;
+        assert!((quoted_bps - spread_bps).abs() < 1e-6, "{trade:?}");
+        assert!(trade.size > 0);
+    }
+    assert!(trades.iter().any(|trade| trade.aggressor_side == Side::Buy));
+    assert!(trades
+        .iter()
+        .any(|trade| trade.aggressor_side == Side::Sell));
+
+    let _ = client.close(None).await;
+    handle.shutdown();
+    handle.join().await.expect("simulator run");
+}
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use rust_market_data::simulator::{self, SimulatorConfig};
 
@@ -17,7 +17,7 @@ async fn simulator_generates_ticks_without_socket() {
         .expect("collect ticks");
 
     assert!(ticks.len() >= 32, "expected to capture at least 32 ticks");
-    let mut last_ts = 0u128;
+    let mut last_ts = 0u64;
     for tick in ticks {
         assert!(!tick.symbol.is_empty(), "symbol should not be empty");
         assert!(
@@ -31,3 +31,171 @@ async fn simulator_generates_ticks_without_socket() {
         last_ts = tick.timestamp_ms;
     }
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn flat_start_disperses_along_correlation_structure() {
+    const STEPS: usize = 200;
+    let symbols = rust_market_data::model::default_equities().len();
+    let config = SimulatorConfig {
+        tick_interval: Duration::from_millis(1),
+        initial_price: Some(100.0),
+        enable_socket: false,
+        ..SimulatorConfig::default()
+    };
+
+    let ticks = simulator::testkit::collect_ticks(config, symbols * STEPS)
+        .await
+        .expect("collect ticks");
+
+    // the first batch is one small shock away from the shared starting price
+    for tick in &ticks[..symbols] {
+        assert!(
+            (tick.price - 100.0).abs() < 2.0,
+            "{} started at {}",
+            tick.symbol,
+            tick.price
+        );
+    }
+
+    let returns = |index: usize| -> Vec<f64> {
+        let path: Vec<f64> = std::iter::once(100.0)
+            .chain(ticks.iter().skip(index).step_by(symbols).map(|t| t.price))
+            .collect();
+        path.windows(2).map(|w| w[1] / w[0] - 1.0).collect()
+    };
+    let correlation = |a: &[f64], b: &[f64]| -> f64 {
+        let mean = |x: &[f64]| x.iter().sum::<f64>() / x.len() as f64;
+        let (ma, mb) = (mean(a), mean(b));
+        let cov: f64 = a.iter().zip(b).map(|(x, y)| (x - ma) * (y - mb)).sum();
+        let va: f64 = a.iter().map(|x| (x - ma).powi(2)).sum();
+        let vb: f64 = b.iter().map(|y| (y - mb).powi(2)).sum();
+        cov / (va * vb).sqrt()
+    };
+
+    // default universe order: region → sector → replica, so 0 and 1 share a bucket
+    let same_bucket = correlation(&returns(0), &returns(1));
+    let cross_bucket = correlation(&returns(0), &returns(symbols - 1));
+    assert!(
+        same_bucket > cross_bucket,
+        "same-bucket correlation {same_bucket:.3} should exceed cross-bucket {cross_bucket:.3}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn round_number_bias_clusters_prices_near_round_levels() {
+    const STEPS: usize = 50;
+    let symbols = rust_market_data::model::default_equities().len();
+    let near_round_share = |ticks: &[rust_market_data::tick::Tick]| -> f64 {
+        let near = ticks
+            .iter()
+            .filter(|tick| ((tick.price * 100.0).round() as i64) % 5 == 0)
+            .count();
+        near as f64 / ticks.len() as f64
+    };
+    let collect = |round_number_bias: Option<f64>| {
+        simulator::testkit::collect_ticks(
+            SimulatorConfig {
+                tick_interval: Duration::from_millis(1),
+                enable_socket: false,
+                round_number_bias,
+                ..SimulatorConfig::default()
+            },
+            symbols * STEPS,
+        )
+    };
+
+    let unbiased = collect(None).await.expect("collect unbiased ticks");
+    let biased = collect(Some(0.8)).await.expect("collect biased ticks");
+
+    // without a bias roughly one price in five lands on a five-cent level
+    let baseline = near_round_share(&unbiased);
+    let clustered = near_round_share(&biased);
+    assert!(
+        clustered > baseline + 0.3,
+        "biased share {clustered:.3} should clearly exceed unbiased share {baseline:.3}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn emitted_returns_follow_the_configured_correlation() {
+    use rust_market_data::model::{default_equities, Region, Sector};
+
+    const STEPS: usize = 2_000;
+    const TOLERANCE: f64 = 0.08;
+    let symbols_in = |sector: Sector| -> Vec<String> {
+        default_equities()
+            .into_iter()
+            .filter(|equity| equity.region == Region::Europe && equity.sector == sector)
+            .map(|equity| equity.symbol.as_str().to_string())
+            .collect()
+    };
+    let energy = symbols_in(Sector::Energy);
+    let technology = symbols_in(Sector::Technology);
+    let config = SimulatorConfig {
+        tick_interval: Duration::from_millis(1),
+        // keep the initial matrix for the whole run
+        correlation_refresh: Duration::from_secs(3600),
+        regions: Some(vec![Region::Europe]),
+        sectors: Some(vec![Sector::Energy, Sector::Technology]),
+        seed: Some(7),
+        enable_socket: false,
+        ..SimulatorConfig::default()
+    };
+
+    let pairs = [
+        (&energy[0], &energy[1]),
+        (&technology[0], &technology[1]),
+        (&energy[0], &technology[0]),
+    ];
+    let expected: Vec<f64> = pairs
+        .iter()
+        .map(|(a, b)| simulator::testkit::configured_correlation(&config, a, b))
+        .collect::<Result<_, _>>()
+        .expect("configured correlation");
+    let ticks =
+        simulator::testkit::collect_ticks(config, (energy.len() + technology.len()) * (STEPS + 1))
+            .await
+            .expect("collect ticks");
+
+    for ((a, b), expected) in pairs.iter().zip(expected) {
+        let empirical = simulator::testkit::empirical_return_correlation(&ticks, a, b);
+        assert!(
+            (empirical - expected).abs() < TOLERANCE,
+            "{a}/{b}: empirical {empirical:.3} vs configured {expected:.3}"
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn high_beta_symbols_track_the_market_index_more_closely() {
+    use rust_market_data::model::{default_equities, Region, Sector, Symbol};
+
+    const STEPS: usize = 1_000;
+    let energy: Vec<Symbol> = default_equities()
+        .into_iter()
+        .filter(|equity| equity.region == Region::Europe && equity.sector == Sector::Energy)
+        .map(|equity| equity.symbol)
+        .collect();
+    let (high, low) = (&energy[0], &energy[1]);
+    let config = SimulatorConfig {
+        tick_interval: Duration::from_millis(1),
+        correlation_refresh: Duration::from_secs(3600),
+        regions: Some(vec![Region::Europe]),
+        sectors: Some(vec![Sector::Energy]),
+        market_index: Some("MKT".parse().unwrap()),
+        market_betas: HashMap::from([(high.clone(), 1.5), (low.clone(), 0.1)]),
+        seed: Some(11),
+        ..SimulatorConfig::default()
+    };
+
+    let ticks = simulator::testkit::collect_ticks(config, (energy.len() + 1) * (STEPS + 1))
+        .await
+        .expect("collect ticks");
+    assert!(ticks.iter().any(|tick| tick.symbol == "MKT"));
+    let high_corr = simulator::testkit::empirical_return_correlation(&ticks, high, "MKT");
+    let low_corr = simulator::testkit::empirical_return_correlation(&ticks, low, "MKT");
+    assert!(
+        high_corr > low_corr + 0.3,
+        "high beta {high_corr:.3} vs low beta {low_corr:.3}"
+    );
+}
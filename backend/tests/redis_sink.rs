@@ -0,0 +1,62 @@
+//! Needs a running Redis: `REDIS_URL=redis://127.0.0.1/ cargo test -p rust-market-data --features redis --test redis_sink`.
+#![cfg(feature = "redis")]
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use futures_util::StreamExt;
+use market_core::{GatewayMessage, WireMessage};
+use rust_market_data::{
+    logging,
+    simulator::{self, SimulatorConfig},
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn gateway_batches_are_published_to_redis() {
+    let Ok(url) = std::env::var("REDIS_URL") else {
+        logging::info_simple(
+            "test.skipped",
+            "REDIS_URL not set; skipping redis sink test",
+        );
+        return;
+    };
+    let channel = format!("market_ticks_test_{}", std::process::id());
+
+    let client = redis::Client::open(url.as_str()).expect("redis url");
+    let mut pubsub = client.get_async_pubsub().await.expect("connect to redis");
+    pubsub.subscribe(&channel).await.expect("subscribe");
+
+    let config = SimulatorConfig {
+        enable_socket: false,
+        gateway_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9137),
+        gateway_throttle: Duration::from_millis(50),
+        tick_interval: Duration::from_millis(4),
+        max_ticks: None,
+        redis_url: Some(url),
+        redis_channel: channel,
+        ..SimulatorConfig::default()
+    };
+    let handle = simulator::spawn(config);
+
+    let mut messages = pubsub.on_message();
+    let mut batches = 0usize;
+    while batches < 3 {
+        let message = tokio::time::timeout(Duration::from_secs(10), messages.next())
+            .await
+            .expect("batch published within the timeout")
+            .expect("pubsub stream open");
+        let payload: String = message.get_payload().expect("text payload");
+        let frame: WireMessage = serde_json::from_str(&payload).expect("gateway frame");
+        match GatewayMessage::from(frame) {
+            GatewayMessage::Batch(batch) => assert!(!batch.ticks.is_empty()),
+            other => panic!("redis received a non-batch frame: {other:?}"),
+        }
+        batches += 1;
+    }
+
+    drop(messages);
+    handle.shutdown();
+    handle.join().await.expect("simulator run");
+}
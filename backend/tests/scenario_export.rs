@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use rust_market_data::simulator::{self, SimulatorConfig};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn exported_bundle_reproduces_initial_ticks() {
+    let out_dir = std::env::temp_dir().join(format!("rmd-scenario-{}", std::process::id()));
+    let base = SimulatorConfig {
+        tick_interval: Duration::from_millis(2),
+        seed: Some(2024),
+        ..SimulatorConfig::default()
+    };
+
+    let bundle = simulator::export_bundle(&base, &out_dir).expect("export bundle");
+    assert_eq!(bundle.seed, 2024);
+    let recorded = std::fs::read_to_string(&bundle.seed_path).expect("seed file");
+    assert_eq!(recorded.trim(), "2024");
+
+    let generated = simulator::testkit::collect_ticks(base.clone(), 1_000)
+        .await
+        .expect("ticks from seed");
+    let imported = simulator::testkit::collect_ticks(
+        SimulatorConfig {
+            universe_path: Some(bundle.universe_path.clone()),
+            correlation_path: Some(bundle.correlation_path.clone()),
+            ..base
+        },
+        1_000,
+    )
+    .await
+    .expect("ticks from exported bundle");
+
+    let key = |ticks: &[rust_market_data::tick::Tick]| {
+        ticks
+            .iter()
+            .map(|tick| (tick.symbol.clone(), tick.price))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(key(&generated), key(&imported));
+
+    let _ = std::fs::remove_dir_all(&out_dir);
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use std::{net::SocketAddr, process::ExitStatus};
+
+use common::Simulator;
+
+/// Wait for `simulator` to exit, returning its status and the reason logged with `app.stop`.
+fn stop_reason(simulator: Simulator) -> (ExitStatus, String) {
+    let (status, logs) = simulator.wait();
+    let stop = logs
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find(|entry| entry["event"] == "app.stop")
+        .unwrap_or_else(|| panic!("no app.stop log line in:\n{logs}"));
+    let reason = stop["metadata"]["reason"]
+        .as_str()
+        .expect("logged reason")
+        .to_string();
+    (status, reason)
+}
+
+fn stop_with_signal(name: &str, port: u16, signal: &str) -> (ExitStatus, String) {
+    let addr: SocketAddr = format!("127.0.0.1:{port}").parse().unwrap();
+    let simulator = Simulator::spawn(
+        name,
+        &format!("enable_socket = false\ngateway_addr = \"{addr}\"\ntick_interval = \"4ms\"\n"),
+    );
+    simulator.wait_for_gateway(addr);
+    simulator.signal(signal);
+    stop_reason(simulator)
+}
+
+#[test]
+fn tick_budget_exits_cleanly() {
+    let simulator = Simulator::spawn(
+        "budget",
+        "enable_socket = false\nenable_gateway = false\ntick_interval = \"1ms\"\nmax_ticks = 1000\n",
+    );
+    let (status, reason) = stop_reason(simulator);
+    assert_eq!(reason, "tick_budget");
+    assert_eq!(status.code(), Some(0));
+}
+
+#[test]
+fn sigterm_exits_cleanly() {
+    let (status, reason) = stop_with_signal("term", 9131, "-TERM");
+    assert_eq!(reason, "terminated");
+    assert_eq!(status.code(), Some(0));
+}
+
+#[test]
+fn sigint_exits_with_the_interrupt_code() {
+    let (status, reason) = stop_with_signal("int", 9144, "-INT");
+    assert_eq!(reason, "interrupted");
+    assert_eq!(status.code(), Some(130));
+}
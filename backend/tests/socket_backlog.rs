@@ -0,0 +1,115 @@
+mod common;
+
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use common::connect_unix;
+use rust_market_data::{
+    model::{default_equities, Region, Sector},
+    simulator::{self, SimulatorConfig},
+    tick::Tick,
+};
+use tokio::{
+    io::{AsyncBufReadExt, BufReader, Lines},
+    net::UnixStream,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn late_subscribers_receive_the_backlog_first() {
+    const BACKLOG: usize = 3;
+    let symbols = default_equities()
+        .into_iter()
+        .filter(|equity| equity.region == Region::Europe && equity.sector == Sector::Energy)
+        .count();
+    let socket_path = std::env::temp_dir().join(format!("backlog-{}.sock", std::process::id()));
+    let handle = simulator::spawn(SimulatorConfig {
+        socket_path: socket_path.clone(),
+        socket_backlog: BACKLOG,
+        enable_gateway: false,
+        regions: Some(vec![Region::Europe]),
+        sectors: Some(vec![Sector::Energy]),
+        tick_interval: Duration::from_millis(20),
+        max_ticks: None,
+        ..SimulatorConfig::default()
+    });
+
+    // a probe that has seen BACKLOG ticks of every symbol proves the backlog
+    // is full: the server drains them into it before the next accept
+    let mut probe = BufReader::new(connect_unix(&socket_path).await).lines();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    while seen.len() < symbols || seen.values().any(|&count| count < BACKLOG) {
+        let tick = next_tick(&mut probe).await;
+        *seen.entry(tick.symbol.as_str().to_string()).or_default() += 1;
+    }
+    drop(probe);
+
+    let mut lines = BufReader::new(connect_unix(&socket_path).await).lines();
+    let mut ticks = Vec::new();
+    let mut first_received_ms = None;
+    while ticks.len() < symbols * BACKLOG + 1 {
+        let tick = next_tick(&mut lines).await;
+        first_received_ms.get_or_insert_with(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("clock after epoch")
+                .as_millis() as u64
+        });
+        ticks.push(tick);
+    }
+    // generated ticks are stamped up to one millisecond per symbol past the iteration start
+    let replayed_by = first_received_ms.unwrap() + symbols as u64;
+
+    let live = ticks.pop().expect("first live tick");
+    let mut per_symbol: HashMap<&str, usize> = HashMap::new();
+    for tick in &ticks {
+        *per_symbol.entry(tick.symbol.as_str()).or_default() += 1;
+        assert!(
+            tick.timestamp_ms <= replayed_by,
+            "backlog was generated before the first line arrived"
+        );
+    }
+    assert_eq!(per_symbol.len(), symbols);
+    assert!(
+        per_symbol.values().all(|&count| count == BACKLOG),
+        "each symbol replays its last {BACKLOG} ticks: {per_symbol:?}"
+    );
+    assert!(
+        ticks
+            .windows(2)
+            .all(|pair| pair[0].timestamp_ms <= pair[1].timestamp_ms),
+        "backlog replays in arrival order"
+    );
+    assert!(live.timestamp_ms >= ticks.last().unwrap().timestamp_ms);
+
+    // the live stream picks up after the replay without repeating a tick
+    let mut last_ms: HashMap<String, u64> = HashMap::new();
+    for tick in &ticks {
+        last_ms.insert(tick.symbol.as_str().to_string(), tick.timestamp_ms);
+    }
+    let mut live_ticks = vec![live];
+    while live_ticks.len() < symbols * 2 {
+        live_ticks.push(next_tick(&mut lines).await);
+    }
+    for tick in live_ticks {
+        let previous = last_ms.insert(tick.symbol.as_str().to_string(), tick.timestamp_ms);
+        assert!(
+            previous.is_none_or(|previous| tick.timestamp_ms > previous),
+            "{} repeated or reordered after the replay",
+            tick.symbol
+        );
+    }
+
+    handle.shutdown();
+    handle.join().await.expect("simulator run");
+}
+
+async fn next_tick(lines: &mut Lines<BufReader<UnixStream>>) -> Tick {
+    let line = tokio::time::timeout(Duration::from_secs(2), lines.next_line())
+        .await
+        .expect("tick within timeout")
+        .expect("read line")
+        .expect("line before EOF");
+    serde_json::from_str(&line).expect("tick line")
+}
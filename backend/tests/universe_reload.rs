@@ -0,0 +1,112 @@
+mod common;
+
+use std::{
+    collections::HashSet,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::Path,
+    time::Duration,
+};
+
+use common::{connect, get_json, Client, Simulator};
+
+use futures_util::StreamExt;
+use serde_json::{json, Value};
+use tokio_tungstenite::tungstenite::Message;
+
+fn write_universe(path: &Path, symbols: &[&str]) {
+    let equities: Vec<Value> = symbols
+        .iter()
+        .map(|symbol| json!({ "symbol": symbol, "region": "europe", "sector": "technology" }))
+        .collect();
+    std::fs::write(path, serde_json::to_string(&equities).unwrap()).unwrap();
+}
+
+/// Next frame of `kind`, as JSON.
+async fn next_frame(client: &mut Client, kind: &str) -> Value {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(2), client.next())
+            .await
+            .unwrap_or_else(|_| panic!("{kind} frame within 2s"))
+            .expect("stream open")
+            .expect("websocket message");
+        let Message::Text(payload) = message else {
+            continue;
+        };
+        let frame: Value = serde_json::from_str(&payload).expect("gateway frame");
+        if frame["type"] == kind {
+            return frame;
+        }
+    }
+}
+
+fn symbols(ticks: &Value) -> HashSet<String> {
+    ticks
+        .as_array()
+        .expect("tick array")
+        .iter()
+        .map(|tick| tick["symbol"].as_str().expect("symbol").to_string())
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn reload_drops_removed_symbols_from_batches_and_snapshot() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9145);
+    let path =
+        std::env::temp_dir().join(format!("rmd-reload-universe-{}.json", std::process::id()));
+    write_universe(&path, &["EUTEC001", "EUTEC002", "EUTEC003"]);
+    // a child process, so the reload signal does not reach the test harness
+    let simulator = Simulator::spawn(
+        "universe-reload",
+        &format!(
+            "enable_socket = false\ngateway_addr = \"{addr}\"\ngateway_throttle = \"100ms\"\n\
+             tick_interval = \"4ms\"\nuniverse_path = \"{}\"\n",
+            path.display()
+        ),
+    );
+    simulator.wait_for_gateway(addr);
+    let mut client = connect(&format!("ws://{addr}/ws")).await;
+    while !symbols(&next_frame(&mut client, "batch").await["ticks"]).contains("EUTEC001") {}
+
+    write_universe(&path, &["EUTEC002", "EUTEC003", "EUTEC004"]);
+    simulator.signal("-HUP");
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    while !symbols(&next_frame(&mut client, "batch").await["ticks"]).contains("EUTEC004") {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "reloaded symbol never appeared in a batch"
+        );
+    }
+    // ticks generated before the reload must not bring the removed symbol back
+    for _ in 0..5 {
+        let batch = symbols(&next_frame(&mut client, "batch").await["ticks"]);
+        assert!(
+            !batch.contains("EUTEC001"),
+            "removed symbol in batch: {batch:?}"
+        );
+        assert!(
+            batch.contains("EUTEC004"),
+            "added symbol missing: {batch:?}"
+        );
+    }
+
+    let (status, body) = get_json(addr, "/snapshot").await;
+    assert_eq!(status, 200, "{body}");
+    let snapshot = symbols(&body);
+    assert!(!snapshot.contains("EUTEC001"), "{snapshot:?}");
+    assert!(snapshot.contains("EUTEC004"), "{snapshot:?}");
+
+    let mut late = connect(&format!("ws://{addr}/ws")).await;
+    let meta = next_frame(&mut late, "meta").await;
+    let listed = symbols(&meta["symbols"]);
+    assert!(!listed.contains("EUTEC001"), "{meta}");
+    assert!(listed.contains("EUTEC004"), "{meta}");
+
+    let _ = client.close(None).await;
+    let _ = late.close(None).await;
+    simulator.signal("-TERM");
+    let (status, _) = tokio::task::spawn_blocking(move || simulator.wait())
+        .await
+        .expect("wait task");
+    let _ = std::fs::remove_file(&path);
+    assert!(status.success(), "{status}");
+}
@@ -1,23 +1,44 @@
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 
-use leptos::*;
+use leptos::{ev, event_target_value, *};
 
 use crate::{
-    StreamStatus, TickStore,
+    RetryHandle, StreamStatus, TickStore,
     ticks::types::{Region, Sector, Tick},
+    ticks::websocket::show_unreachable_banner,
 };
 
 #[cfg(target_arch = "wasm32")]
 use std::rc::Rc;
 
 #[cfg(target_arch = "wasm32")]
-use crate::connect_with_retry;
+use crate::{
+    connect_with_retry,
+    ticks::raw_feed::{pretty_frame, push_bounded},
+    ticks::websocket::{BACKOFF_RESET_AFTER, RawFrameCallback, failed_attempts_after},
+};
 
 use super::{
-    filters::FiltersPanel, history_chart::HistoryChart, summary::SummaryPanel,
-    tick_table::TickTable,
+    filters::{FilterPreset, FiltersPanel},
+    history_chart::{FULL_VIEW, HistoryChart, ViewRange},
+    raw_feed::RawFeedPanel,
+    summary::SummaryPanel,
+    symbol_detail::SymbolDetailPanel,
+    tick_table::{TableView, TickTable},
 };
 
+/// Raw frames retained for the `?debug=1` feed panel.
+#[cfg(target_arch = "wasm32")]
+const RAW_FEED_CAPACITY: usize = 20;
+
+/// Distinct symbols the tick store tracks before evicting the least recently
+/// updated, so a runaway feed cannot exhaust the tab's memory.
+const MAX_TRACKED_SYMBOLS: usize = 5_000;
+
+/// Consecutive failed connection attempts before the "unable to reach market
+/// data" banner replaces the reconnecting badge as the main signal.
+pub const UNREACHABLE_BANNER_ATTEMPTS: u32 = 5;
+
 #[derive(Clone, Copy)]
 pub struct TickStoreSignal(pub RwSignal<TickStore>);
 
@@ -30,6 +51,18 @@ pub struct FilterState {
     pub sectors: RwSignal<HashSet<Sector>>,
 }
 
+/// Saved filter presets, persisted to localStorage.
+#[derive(Clone, Copy)]
+pub struct FilterPresetsSignal(pub RwSignal<Vec<FilterPreset>>);
+
+/// Sort, row limit, and page of the quote table.
+#[derive(Clone, Copy)]
+pub struct TableViewSignal(pub RwSignal<TableView>);
+
+/// Zoomed time window of the history chart.
+#[derive(Clone, Copy)]
+pub struct ChartViewSignal(pub RwSignal<ViewRange>);
+
 #[derive(Clone, Copy)]
 pub struct ConnectionStatusSignal(pub RwSignal<StreamStatus>);
 
@@ -76,23 +109,151 @@ impl std::str::FromStr for Theme {
 #[derive(Clone, Copy)]
 pub struct ThemeSignal(pub RwSignal<Theme>);
 
+/// localStorage key remembering the chosen [`LayoutMode`] across reloads.
+#[cfg(target_arch = "wasm32")]
+const LAYOUT_STORAGE_KEY: &str = "dashboard.layout";
+
+/// localStorage key holding the JSON-encoded [`FilterPreset`] list.
+#[cfg(target_arch = "wasm32")]
+const PRESETS_STORAGE_KEY: &str = "dashboard.filter_presets";
+
+/// Which of the quote table and history chart the dashboard shows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LayoutMode {
+    #[default]
+    Split,
+    TableOnly,
+    ChartOnly,
+}
+
+impl LayoutMode {
+    pub const ALL: [LayoutMode; 3] = [
+        LayoutMode::Split,
+        LayoutMode::TableOnly,
+        LayoutMode::ChartOnly,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LayoutMode::Split => "split",
+            LayoutMode::TableOnly => "table",
+            LayoutMode::ChartOnly => "chart",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LayoutMode::Split => "Split",
+            LayoutMode::TableOnly => "Table only",
+            LayoutMode::ChartOnly => "Chart only",
+        }
+    }
+}
+
+impl std::str::FromStr for LayoutMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "split" => Ok(LayoutMode::Split),
+            "table" => Ok(LayoutMode::TableOnly),
+            "chart" => Ok(LayoutMode::ChartOnly),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Panels rendered for a [`LayoutMode`]; filters and symbol details always stay visible.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VisiblePanels {
+    pub summary: bool,
+    pub table: bool,
+    pub chart: bool,
+}
+
+pub fn visible_panels(mode: LayoutMode) -> VisiblePanels {
+    match mode {
+        LayoutMode::Split => VisiblePanels {
+            summary: true,
+            table: true,
+            chart: true,
+        },
+        LayoutMode::TableOnly => VisiblePanels {
+            summary: true,
+            table: true,
+            chart: false,
+        },
+        LayoutMode::ChartOnly => VisiblePanels {
+            summary: false,
+            table: false,
+            chart: true,
+        },
+    }
+}
+
+/// Return selection, filters, table sort and paging, and chart zoom to their
+/// defaults, leaving the buffered tick data intact.
+pub fn reset_view(
+    selected: SelectedSymbolSignal,
+    filters: &FilterState,
+    table: TableViewSignal,
+    chart: ChartViewSignal,
+) {
+    selected.0.set(None);
+    filters.regions.set(HashSet::new());
+    filters.sectors.set(HashSet::new());
+    table.0.set(TableView::default());
+    chart.0.set(FULL_VIEW);
+}
+
 /// Top-level dashboard wrapper providing shared application state via context.
+///
+/// `unreachable_after` sets how many failed connection attempts in a row show
+/// the unreachable banner (0 disables it). `summary_refresh_ms` recomputes the
+/// summary panel on that interval instead of on every batch.
 #[component]
-pub fn Dashboard() -> impl IntoView {
-    let tick_store = create_rw_signal(TickStore::new(2_048));
-    seed_demo_data(&tick_store);
+pub fn Dashboard(
+    #[prop(default = UNREACHABLE_BANNER_ATTEMPTS)] unreachable_after: u32,
+    #[prop(optional_no_strip)] summary_refresh_ms: Option<u64>,
+) -> impl IntoView {
+    let tick_store = create_rw_signal(
+        TickStore::new(2_048)
+            .with_unfocused_history(64)
+            .with_max_symbols(MAX_TRACKED_SYMBOLS),
+    );
+    let query = page_query();
+    if query_flag(&query, "demo") {
+        seed_demo_data(&tick_store);
+    }
+    let debug = query_flag(&query, "debug");
+    let raw_frames = create_rw_signal(VecDeque::<String>::new());
 
     let selected_symbol = create_rw_signal(None::<String>);
+    create_effect(move |_| {
+        let focus = selected_symbol.get();
+        tick_store.update(|store| store.set_focus(focus));
+    });
     let selected_regions = create_rw_signal(HashSet::<Region>::new());
     let selected_sectors = create_rw_signal(HashSet::<Sector>::new());
     let connection_status = create_rw_signal(StreamStatus::Idle);
+    let failed_attempts = create_rw_signal(0u32);
+    let retry_handle = store_value(None::<RetryHandle>);
+    let chart_view = create_rw_signal(FULL_VIEW);
+    let table_view = create_rw_signal(TableView::default());
     let theme = create_rw_signal(Theme::Dark);
+    let layout = create_rw_signal(stored_layout().unwrap_or_default());
+    let presets = create_rw_signal(stored_presets());
 
     #[cfg(target_arch = "wasm32")]
     {
         let store_for_ws = tick_store;
         let status_for_ws = connection_status;
-        leptos::create_effect(move |_| init_live_updates(store_for_ws, status_for_ws));
+        let raw_for_ws = debug.then_some(raw_frames);
+        leptos::create_effect(move |_| {
+            let handle =
+                init_live_updates(store_for_ws, status_for_ws, failed_attempts, raw_for_ws);
+            retry_handle.set_value(Some(handle));
+        });
 
         let theme_signal = theme;
         leptos::create_effect(move |_| {
@@ -103,66 +264,214 @@ pub fn Dashboard() -> impl IntoView {
                 }
             }
         });
+
+        let layout_signal = layout;
+        leptos::create_effect(move |_| {
+            let layout = layout_signal.get();
+            if let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok().flatten())
+            {
+                let _ = storage.set_item(LAYOUT_STORAGE_KEY, layout.as_str());
+            }
+        });
+
+        leptos::create_effect(move |_| {
+            let Ok(encoded) = presets.with(serde_json::to_string) else {
+                return;
+            };
+            if let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok().flatten())
+            {
+                let _ = storage.set_item(PRESETS_STORAGE_KEY, &encoded);
+            }
+        });
     }
 
     provide_context(TickStoreSignal(tick_store));
     provide_context(SelectedSymbolSignal(selected_symbol));
-    provide_context(FilterState {
+    let filters = FilterState {
         regions: selected_regions,
         sectors: selected_sectors,
-    });
+    };
+    provide_context(filters.clone());
+    provide_context(FilterPresetsSignal(presets));
     provide_context(ConnectionStatusSignal(connection_status));
+    provide_context(ChartViewSignal(chart_view));
+    provide_context(TableViewSignal(table_view));
     provide_context(ThemeSignal(theme));
 
+    let panels = create_memo(move |_| visible_panels(layout.get()));
+    let on_layout_change = move |ev: ev::Event| {
+        if let Ok(mode) = event_target_value(&ev).parse::<LayoutMode>() {
+            layout.set(mode);
+        }
+    };
+    let on_retry_now = move |_| {
+        failed_attempts.set(0);
+        retry_handle.with_value(|handle| {
+            if let Some(handle) = handle {
+                handle.retry_now();
+            }
+        });
+    };
+    let on_reset = move |_| {
+        reset_view(
+            SelectedSymbolSignal(selected_symbol),
+            &filters,
+            TableViewSignal(table_view),
+            ChartViewSignal(chart_view),
+        )
+    };
+
     view! {
         <div class="dashboard">
             <header class="dashboard__header">
-                <h1>"Rust Market Dashboard"</h1>
-                <p>"Live view of the last traded price for each symbol."</p>
+                <div>
+                    <h1>"Rust Market Dashboard"</h1>
+                    <p>"Live view of the last traded price for each symbol."</p>
+                </div>
+                <div class="dashboard__controls">
+                    <button class="dashboard__reset" on:click=on_reset>
+                        "Reset view"
+                    </button>
+                    <label class="dashboard__layout">
+                        <span>"Layout"</span>
+                        <select
+                            class="theme-select"
+                            on:change=on_layout_change
+                            prop:value=move || layout.get().as_str()
+                        >
+                            <For
+                                each=move || LayoutMode::ALL.into_iter()
+                                key=|mode| mode.as_str()
+                                children=move |mode: LayoutMode| {
+                                    view! { <option value=mode.as_str()>{mode.label()}</option> }
+                                }
+                            />
+                        </select>
+                    </label>
+                </div>
             </header>
-            <section class="dashboard__body">
-                <div class="dashboard__main">
-                    <SummaryPanel />
-                    <TickTable />
+            <Show when=move || show_unreachable_banner(failed_attempts.get(), unreachable_after)>
+                <div class="connection-banner" role="alert">
+                    <span>"Unable to reach market data — retrying in background"</span>
+                    <button class="connection-banner__retry" on:click=on_retry_now>
+                        "Retry now"
+                    </button>
                 </div>
+            </Show>
+            <section class=move || {
+                if panels.with(|panels| panels.table) {
+                    "dashboard__body"
+                } else {
+                    "dashboard__body dashboard__body--full"
+                }
+            }>
+                <Show when=move || panels.with(|panels| panels.table)>
+                    <div class="dashboard__main">
+                        <Show when=move || panels.with(|panels| panels.summary)>
+                            <SummaryPanel refresh_ms=summary_refresh_ms />
+                        </Show>
+                        <TickTable />
+                    </div>
+                </Show>
                 <aside class="dashboard__sidebar">
                     <FiltersPanel />
-                    <HistoryChart />
+                    <Show when=move || panels.with(|panels| panels.chart)>
+                        <HistoryChart />
+                    </Show>
+                    <SymbolDetailPanel />
+                    {debug.then(|| view! { <RawFeedPanel frames=raw_frames /> })}
                 </aside>
             </section>
         </div>
     }
 }
 
+/// Opt-in page flags such as `?demo=1` (demo ticks) or `?debug=1` (raw feed panel).
+fn query_flag(query: &str, flag: &str) -> bool {
+    query
+        .trim_start_matches('?')
+        .split('&')
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+        .any(|(key, value)| key == flag && matches!(value, "1" | "true"))
+}
+
+fn page_query() -> String {
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::window()
+            .and_then(|window| window.location().search().ok())
+            .unwrap_or_default()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        String::new()
+    }
+}
+
+/// Layout saved by a previous visit, if any.
+fn stored_layout() -> Option<LayoutMode> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .and_then(|storage| storage.get_item(LAYOUT_STORAGE_KEY).ok().flatten())
+            .and_then(|value| value.parse().ok())
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        None
+    }
+}
+
+/// Filter presets saved by a previous visit; an unreadable list is ignored.
+fn stored_presets() -> Vec<FilterPreset> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .and_then(|storage| storage.get_item(PRESETS_STORAGE_KEY).ok().flatten())
+            .and_then(|value| serde_json::from_str(&value).ok())
+            .unwrap_or_default()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Vec::new()
+    }
+}
+
 fn seed_demo_data(tick_store: &RwSignal<TickStore>) {
     let seed_ticks = [
         Tick {
-            symbol: "NATECH007".into(),
+            symbol: "NATECH007".parse().unwrap(),
             price: 134.2875,
             timestamp_ms: 1_716_400_005_123,
             region: Region::NorthAmerica,
             sector: Sector::Technology,
+            prev_close: None,
         },
         Tick {
-            symbol: "EUIND002".into(),
+            symbol: "EUIND002".parse().unwrap(),
             price: 98.4401,
             timestamp_ms: 1_716_400_005_456,
             region: Region::Europe,
             sector: Sector::Industrials,
+            prev_close: None,
         },
         Tick {
-            symbol: "APHLT009".into(),
+            symbol: "APHLT009".parse().unwrap(),
             price: 154.9983,
             timestamp_ms: 1_716_400_005_789,
             region: Region::AsiaPacific,
             sector: Sector::Healthcare,
+            prev_close: None,
         },
         Tick {
-            symbol: "SAENG001".into(),
+            symbol: "SAENG001".parse().unwrap(),
             price: 134.7864,
             timestamp_ms: 1_716_400_005_999,
             region: Region::SouthAmerica,
             sector: Sector::Energy,
+            prev_close: None,
         },
     ];
 
@@ -174,7 +483,12 @@ fn seed_demo_data(tick_store: &RwSignal<TickStore>) {
 }
 
 #[cfg(target_arch = "wasm32")]
-fn init_live_updates(tick_store: RwSignal<TickStore>, status: RwSignal<StreamStatus>) {
+fn init_live_updates(
+    tick_store: RwSignal<TickStore>,
+    status: RwSignal<StreamStatus>,
+    failed_attempts: RwSignal<u32>,
+    raw_frames: Option<RwSignal<VecDeque<String>>>,
+) -> RetryHandle {
     let store_for_cb = tick_store;
     let on_tick = Rc::new(move |ticks: Vec<Tick>| {
         store_for_cb.update(|store| store.ingest_batch(ticks));
@@ -183,10 +497,26 @@ fn init_live_updates(tick_store: RwSignal<TickStore>, status: RwSignal<StreamSta
     let status_for_cb = status;
     let on_status = Rc::new(move |state: StreamStatus| {
         status_for_cb.set(state);
+        failed_attempts.update(|failed| *failed = failed_attempts_after(*failed, state));
+    });
+
+    let on_raw = raw_frames.map(|frames| {
+        Rc::new(move |raw: &str| {
+            let frame = pretty_frame(raw);
+            frames.update(|buffer| push_bounded(buffer, frame, RAW_FEED_CAPACITY));
+        }) as RawFrameCallback
     });
 
     let url = resolve_gateway_url();
-    connect_with_retry(url, on_tick, on_status);
+    let accept_late_ticks = tick_store.with_untracked(TickStore::orders_late_ticks);
+    connect_with_retry(
+        url,
+        on_tick,
+        on_status,
+        on_raw,
+        BACKOFF_RESET_AFTER,
+        accept_late_ticks,
+    )
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -199,3 +529,87 @@ fn resolve_gateway_url() -> String {
 
     format!("ws://{host}:9001/ws")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::tick_table::RowSort;
+
+    #[test]
+    fn demo_seed_is_opt_in() {
+        assert!(!query_flag("", "demo"));
+        assert!(!query_flag("?symbol=AAA", "demo"));
+        assert!(!query_flag("?demo=0", "demo"));
+        assert!(!query_flag("?demo", "demo"));
+        assert!(!query_flag("?nodemo=1", "demo"));
+        assert!(query_flag("?demo=1", "demo"));
+        assert!(query_flag("?symbol=AAA&demo=true", "demo"));
+        assert!(query_flag("demo=1", "demo"));
+    }
+
+    #[test]
+    fn debug_flag_is_independent_of_demo() {
+        assert!(query_flag("?demo=1&debug=1", "debug"));
+        assert!(!query_flag("?demo=1", "debug"));
+    }
+
+    #[test]
+    fn reset_view_restores_defaults_and_keeps_data() {
+        let runtime = create_runtime();
+        let store = create_rw_signal(TickStore::new(16));
+        store.update(|store| {
+            store.ingest(Tick {
+                symbol: "AAA".parse().unwrap(),
+                price: 10.0,
+                timestamp_ms: 1,
+                region: Region::Europe,
+                sector: Sector::Energy,
+                prev_close: None,
+            })
+        });
+        let selected = SelectedSymbolSignal(create_rw_signal(Some("AAA".to_string())));
+        let filters = FilterState {
+            regions: create_rw_signal(HashSet::from([Region::Europe])),
+            sectors: create_rw_signal(HashSet::from([Sector::Energy])),
+        };
+        let table = TableViewSignal(create_rw_signal(TableView {
+            sort: RowSort::Change,
+            limit: Some(10),
+            page: 2,
+            page_size: Some(25),
+        }));
+        let chart = ChartViewSignal(create_rw_signal((0.25, 0.5)));
+
+        reset_view(selected, &filters, table, chart);
+
+        assert_eq!(selected.0.get(), None);
+        assert!(filters.regions.with(HashSet::is_empty));
+        assert!(filters.sectors.with(HashSet::is_empty));
+        assert_eq!(table.0.get(), TableView::default());
+        assert_eq!(chart.0.get(), FULL_VIEW);
+        assert_eq!(store.with(|store| store.latest().len()), 1);
+        runtime.dispose();
+    }
+
+    #[test]
+    fn layout_modes_choose_visible_panels() {
+        let split = visible_panels(LayoutMode::Split);
+        assert!(split.summary && split.table && split.chart);
+
+        let table = visible_panels(LayoutMode::TableOnly);
+        assert!(table.summary && table.table && !table.chart);
+
+        let chart = visible_panels(LayoutMode::ChartOnly);
+        assert!(!chart.summary && !chart.table && chart.chart);
+
+        assert_eq!(LayoutMode::default(), LayoutMode::Split);
+    }
+
+    #[test]
+    fn layout_mode_round_trips_through_storage_value() {
+        for mode in LayoutMode::ALL {
+            assert_eq!(mode.as_str().parse::<LayoutMode>(), Ok(mode));
+        }
+        assert!("grid".parse::<LayoutMode>().is_err());
+    }
+}
@@ -1,20 +1,128 @@
 use std::collections::HashSet;
 
-use leptos::{ev, event_target_checked, *};
+use leptos::{ev, event_target_checked, event_target_value, *};
+use serde::{Deserialize, Serialize};
 
 use crate::ticks::{
     format::{region_label, sector_label},
     types::{Region, Sector},
 };
 
-use super::dashboard::FilterState;
+use super::dashboard::{FilterPresetsSignal, FilterState};
+
+/// A named region/sector selection saved by the user.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilterPreset {
+    pub name: String,
+    pub regions: Vec<Region>,
+    pub sectors: Vec<Sector>,
+}
+
+impl FilterPreset {
+    /// Capture a selection, listing regions and sectors in their canonical order.
+    pub fn new(name: &str, regions: &HashSet<Region>, sectors: &HashSet<Sector>) -> Self {
+        Self {
+            name: name.to_string(),
+            regions: Region::ALL
+                .into_iter()
+                .filter(|region| regions.contains(region))
+                .collect(),
+            sectors: Sector::ALL
+                .into_iter()
+                .filter(|sector| sectors.contains(sector))
+                .collect(),
+        }
+    }
+}
+
+/// Save the selection under `name` (trimmed), replacing a preset with the same
+/// name in place. Returns `false` without changes when the name is blank.
+pub fn save_preset(
+    presets: &mut Vec<FilterPreset>,
+    name: &str,
+    regions: &HashSet<Region>,
+    sectors: &HashSet<Sector>,
+) -> bool {
+    let name = name.trim();
+    if name.is_empty() {
+        return false;
+    }
+    let preset = FilterPreset::new(name, regions, sectors);
+    match presets.iter_mut().find(|existing| existing.name == name) {
+        Some(existing) => *existing = preset,
+        None => presets.push(preset),
+    }
+    true
+}
+
+/// Region and sector sets that replace the current filters when `name` is applied.
+pub fn apply_preset(
+    presets: &[FilterPreset],
+    name: &str,
+) -> Option<(HashSet<Region>, HashSet<Sector>)> {
+    presets
+        .iter()
+        .find(|preset| preset.name == name)
+        .map(|preset| {
+            (
+                preset.regions.iter().copied().collect(),
+                preset.sectors.iter().copied().collect(),
+            )
+        })
+}
+
+/// Remove the preset called `name`, returning whether one existed.
+pub fn delete_preset(presets: &mut Vec<FilterPreset>, name: &str) -> bool {
+    let before = presets.len();
+    presets.retain(|preset| preset.name != name);
+    presets.len() != before
+}
 
 #[component]
 pub fn FiltersPanel() -> impl IntoView {
     let filters = use_context::<FilterState>().expect("filter state context missing");
+    let presets = use_context::<FilterPresetsSignal>()
+        .expect("filter presets context missing")
+        .0;
     let filters_for_regions = filters.clone();
     let filters_for_sectors = filters.clone();
     let filters_for_clear = filters.clone();
+    let filters_for_save = filters.clone();
+    let filters_for_apply = filters.clone();
+
+    let preset_name = create_rw_signal(String::new());
+    let active_preset = create_rw_signal(String::new());
+
+    let on_save = move |_| {
+        let name = preset_name.get_untracked();
+        let regions = filters_for_save.regions.get_untracked();
+        let sectors = filters_for_save.sectors.get_untracked();
+        let mut saved = false;
+        presets.update(|list| saved = save_preset(list, &name, &regions, &sectors));
+        if saved {
+            active_preset.set(name.trim().to_string());
+            preset_name.set(String::new());
+        }
+    };
+    let on_apply = move |ev: ev::Event| {
+        let name = event_target_value(&ev);
+        active_preset.set(name.clone());
+        if let Some((regions, sectors)) = presets.with_untracked(|list| apply_preset(list, &name)) {
+            // one reactive update, so views never see the new regions with the old sectors
+            batch(|| {
+                filters_for_apply.regions.set(regions);
+                filters_for_apply.sectors.set(sectors);
+            });
+        }
+    };
+    let on_delete = move |_| {
+        let name = active_preset.get_untracked();
+        let mut deleted = false;
+        presets.update(|list| deleted = delete_preset(list, &name));
+        if deleted {
+            active_preset.set(String::new());
+        }
+    };
 
     let region_list: Vec<Region> = Region::ALL.into_iter().collect();
     let sector_list: Vec<Sector> = Sector::ALL.into_iter().collect();
@@ -22,6 +130,48 @@ pub fn FiltersPanel() -> impl IntoView {
     view! {
         <section class="filters-panel">
             <h2>"Filters"</h2>
+            <div class="filters-panel__group">
+                <h3>"Presets"</h3>
+                <div class="filters-panel__presets">
+                    <select
+                        class="theme-select"
+                        on:change=on_apply
+                        prop:value=move || active_preset.get()
+                    >
+                        <option value="">"Choose a preset"</option>
+                        <For
+                            each=move || presets.get().into_iter()
+                            key=|preset| preset.name.clone()
+                            children=move |preset: FilterPreset| {
+                                view! { <option value=preset.name.clone()>{preset.name}</option> }
+                            }
+                        />
+                    </select>
+                    <button
+                        class="filters-panel__clear"
+                        on:click=on_delete
+                        disabled=move || active_preset.with(String::is_empty)
+                    >
+                        "Delete"
+                    </button>
+                </div>
+                <div class="filters-panel__presets">
+                    <input
+                        class="filters-panel__preset-name"
+                        type="text"
+                        placeholder="Preset name"
+                        prop:value=move || preset_name.get()
+                        on:input=move |ev| preset_name.set(event_target_value(&ev))
+                    />
+                    <button
+                        class="filters-panel__clear"
+                        on:click=on_save
+                        disabled=move || preset_name.with(|name| name.trim().is_empty())
+                    >
+                        "Save current"
+                    </button>
+                </div>
+            </div>
             <div class="filters-panel__group">
                 <h3>"Regions"</h3>
                 <div class="filters-panel__options">
@@ -95,3 +245,70 @@ pub fn FiltersPanel() -> impl IntoView {
         </section>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selection() -> (HashSet<Region>, HashSet<Sector>) {
+        (
+            HashSet::from([Region::Europe, Region::NorthAmerica]),
+            HashSet::from([Sector::Energy]),
+        )
+    }
+
+    #[test]
+    fn saving_replaces_presets_by_name() {
+        let (regions, sectors) = selection();
+        let mut presets = Vec::new();
+        assert!(save_preset(
+            &mut presets,
+            " Energy desk ",
+            &regions,
+            &sectors
+        ));
+        assert!(!save_preset(&mut presets, "   ", &regions, &sectors));
+        assert_eq!(presets.len(), 1);
+        assert_eq!(presets[0].name, "Energy desk");
+        assert_eq!(
+            presets[0].regions,
+            vec![Region::NorthAmerica, Region::Europe],
+            "canonical order regardless of set iteration"
+        );
+
+        assert!(save_preset(
+            &mut presets,
+            "Energy desk",
+            &HashSet::new(),
+            &sectors
+        ));
+        assert_eq!(presets.len(), 1);
+        assert!(presets[0].regions.is_empty());
+    }
+
+    #[test]
+    fn applying_returns_both_sets_and_deleting_removes() {
+        let (regions, sectors) = selection();
+        let mut presets = Vec::new();
+        save_preset(&mut presets, "A", &regions, &sectors);
+        save_preset(&mut presets, "B", &HashSet::new(), &HashSet::new());
+
+        assert_eq!(apply_preset(&presets, "A"), Some((regions, sectors)));
+        assert_eq!(apply_preset(&presets, "missing"), None);
+
+        assert!(delete_preset(&mut presets, "A"));
+        assert!(!delete_preset(&mut presets, "A"));
+        assert_eq!(presets.len(), 1);
+        assert_eq!(presets[0].name, "B");
+    }
+
+    #[test]
+    fn presets_round_trip_through_json() {
+        let (regions, sectors) = selection();
+        let presets = vec![FilterPreset::new("A", &regions, &sectors)];
+        let json = serde_json::to_string(&presets).unwrap();
+        assert!(json.contains("\"north_america\""));
+        let decoded: Vec<FilterPreset> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, presets);
+    }
+}
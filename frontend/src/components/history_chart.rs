@@ -1,14 +1,34 @@
-use leptos::*;
+use leptos::{ev, *};
+use market_core::lttb;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::JsCast;
 
-use crate::{StreamStatus, ticks::types::HistoryPoint};
+use crate::{
+    StreamStatus,
+    ticks::{format::status_message, types::HistoryPoint},
+};
 
-use super::dashboard::{ConnectionStatusSignal, SelectedSymbolSignal, TickStoreSignal};
+use super::dashboard::{
+    ChartViewSignal, ConnectionStatusSignal, SelectedSymbolSignal, TickStoreSignal,
+};
 
 const CHART_WIDTH: f64 = 620.0;
 const CHART_HEIGHT: f64 = 260.0;
+/// Narrowest zoom, as a fraction of the buffered time span.
+const MIN_VIEW_SPAN: f64 = 0.02;
+/// Span multiplier per wheel notch.
+const WHEEL_ZOOM_FACTOR: f64 = 1.2;
+/// Default cap on points drawn per chart; the store keeps the full history.
+pub const MAX_RENDERED_POINTS: usize = 300;
 
+/// Visible time window as `(start, end)` fractions of the buffered history span.
+pub type ViewRange = (f64, f64);
+
+pub const FULL_VIEW: ViewRange = (0.0, 1.0);
+
+/// Price history of the selected symbol, downsampled to at most `max_points` rendered points.
 #[component]
-pub fn HistoryChart() -> impl IntoView {
+pub fn HistoryChart(#[prop(default = MAX_RENDERED_POINTS)] max_points: usize) -> impl IntoView {
     let tick_store = use_context::<TickStoreSignal>().expect("tick store context missing");
     let selected_symbol =
         use_context::<SelectedSymbolSignal>().expect("selected symbol context missing");
@@ -28,6 +48,39 @@ pub fn HistoryChart() -> impl IntoView {
 
     let status =
         use_context::<ConnectionStatusSignal>().expect("connection status context missing");
+    let view_range = use_context::<ChartViewSignal>()
+        .expect("chart view context missing")
+        .0;
+    // pointer x and view range where the current drag started
+    let drag_origin = create_rw_signal(None::<(f64, ViewRange)>);
+
+    let on_wheel = move |ev: ev::WheelEvent| {
+        ev.prevent_default();
+        if let Some((anchor, _)) = pointer_position(&ev) {
+            let factor = if ev.delta_y() > 0.0 {
+                WHEEL_ZOOM_FACTOR
+            } else {
+                WHEEL_ZOOM_FACTOR.recip()
+            };
+            view_range.update(|view| *view = zoom_view(*view, anchor, factor));
+        }
+    };
+    let on_mouse_down = move |ev: ev::MouseEvent| {
+        drag_origin.set(Some((ev.client_x() as f64, view_range.get_untracked())));
+    };
+    let on_mouse_move = move |ev: ev::MouseEvent| {
+        let Some((origin_x, origin_view)) = drag_origin.get_untracked() else {
+            return;
+        };
+        if let Some((_, width)) = pointer_position(&ev) {
+            let dragged = (origin_x - ev.client_x() as f64) / width;
+            view_range.set(pan_view(
+                origin_view,
+                dragged * (origin_view.1 - origin_view.0),
+            ));
+        }
+    };
+    let on_drag_end = move |_: ev::MouseEvent| drag_origin.set(None);
 
     view! {
         <section class="history-chart">
@@ -38,11 +91,8 @@ pub fn HistoryChart() -> impl IntoView {
                     history_state.get().map(|(symbol, history)| {
                         if history.is_empty() {
                             let message = match status.0.get() {
-                                StreamStatus::Connecting => "Connecting to market data...".to_string(),
-                                StreamStatus::Reconnecting { .. } => "Reconnecting to the gateway...".to_string(),
-                                StreamStatus::Failed => "Connection lost. Attempting to reconnect...".to_string(),
                                 StreamStatus::Connected => format!("Waiting for live data for {symbol}..."),
-                                StreamStatus::Idle => "Waiting for connection...".to_string(),
+                                other => status_message(other),
                             };
                             view! { <p>{message}</p> }
                         } else {
@@ -53,7 +103,7 @@ pub fn HistoryChart() -> impl IntoView {
             >
                 {move || {
                     history_state.get().and_then(|(symbol, history)| {
-                        compute_chart_geometry(&history, CHART_WIDTH, CHART_HEIGHT).map(|geometry| {
+                        compute_chart_geometry(&history, CHART_WIDTH, CHART_HEIGHT, Some(view_range.get()), max_points).map(|geometry| {
                             view! {
                                 <div class="history-chart__content">
                                     <header class="history-chart__header">
@@ -65,6 +115,12 @@ pub fn HistoryChart() -> impl IntoView {
                                         height=CHART_HEIGHT
                                         viewBox=format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT)
                                         class="history-chart__svg"
+                                        on:wheel:undelegated=on_wheel
+                                        on:mousedown=on_mouse_down
+                                        on:mousemove=on_mouse_move
+                                        on:mouseup=on_drag_end
+                                        on:mouseleave=on_drag_end
+                                        on:dblclick=move |_| view_range.set(FULL_VIEW)
                                     >
                                         <defs>
                                             <linearGradient id="priceFill" x1="0" x2="0" y1="0" y2="1">
@@ -103,15 +159,86 @@ struct ChartGeometry {
     max_price: f64,
 }
 
+/// Pointer position over the chart as a fraction of its rendered width, plus that width.
+fn pointer_position(ev: &web_sys::MouseEvent) -> Option<(f64, f64)> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let element = ev.current_target()?.dyn_into::<web_sys::Element>().ok()?;
+        let rect = element.get_bounding_client_rect();
+        (rect.width() > 0.0).then(|| {
+            let fraction = (ev.client_x() as f64 - rect.left()) / rect.width();
+            (fraction.clamp(0.0, 1.0), rect.width())
+        })
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = ev;
+        None
+    }
+}
+
+/// Scale the view span by `factor`, keeping the point at `anchor` (0–1 across the chart) fixed.
+fn zoom_view(view: ViewRange, anchor: f64, factor: f64) -> ViewRange {
+    let (start, end) = view;
+    let span = ((end - start) * factor).clamp(MIN_VIEW_SPAN, 1.0);
+    let anchor = anchor.clamp(0.0, 1.0);
+    let pivot = start + anchor * (end - start);
+    clamp_view(pivot - anchor * span, span)
+}
+
+/// Shift the view by `delta` (a fraction of the full span) without leaving the buffered data.
+fn pan_view(view: ViewRange, delta: f64) -> ViewRange {
+    clamp_view(view.0 + delta, view.1 - view.0)
+}
+
+fn clamp_view(start: f64, span: f64) -> ViewRange {
+    let start = start.clamp(0.0, 1.0 - span);
+    (start, start + span)
+}
+
+/// Points whose timestamps fall in `[from_ts, to_ts]`, plus the nearest point
+/// on either side so the line runs to the chart edges when zoomed in.
+fn points_in_range(history: &[HistoryPoint], from_ts: f64, to_ts: f64) -> &[HistoryPoint] {
+    let first = history
+        .partition_point(|point| (point.timestamp_ms as f64) < from_ts)
+        .saturating_sub(1);
+    let last = history
+        .partition_point(|point| (point.timestamp_ms as f64) <= to_ts)
+        .saturating_add(1)
+        .min(history.len());
+    &history[first..last.max(first)]
+}
+
+/// Reduce `history` to at most `max_points` with LTTB, keeping both endpoints.
+fn downsample(history: &[HistoryPoint], max_points: usize) -> Vec<HistoryPoint> {
+    lttb(history, max_points, |point| {
+        (point.timestamp_ms as f64, point.price)
+    })
+}
+
+/// Map the history within `view` (the full span when `None`) onto a `width` × `height` chart,
+/// downsampling the visible points to at most `max_points` (`0` draws every point).
 fn compute_chart_geometry(
     history: &[HistoryPoint],
     width: f64,
     height: f64,
+    view: Option<ViewRange>,
+    max_points: usize,
 ) -> Option<ChartGeometry> {
     if history.len() < 2 || width <= 0.0 || height <= 0.0 {
         return None;
     }
 
+    let first_ts = history.first()?.timestamp_ms as f64;
+    let full_span = (history.last()?.timestamp_ms as f64 - first_ts).max(1.0);
+    let (start, end) = view.unwrap_or(FULL_VIEW);
+    let min_ts = first_ts + start * full_span;
+    let max_ts = first_ts + end * full_span;
+    let history = downsample(points_in_range(history, min_ts, max_ts), max_points);
+    if history.len() < 2 {
+        return None;
+    }
+
     let min_price = history
         .iter()
         .map(|point| point.price)
@@ -128,21 +255,27 @@ fn compute_chart_geometry(
         return None;
     }
 
-    let min_ts = history.first()?.timestamp_ms as f64;
-    let max_ts = history.last()?.timestamp_ms as f64;
     let ts_span = (max_ts - min_ts).max(1.0);
     let price_span = (max_price - min_price).max(1e-9);
+    let x_of = |point: &HistoryPoint| ((point.timestamp_ms as f64 - min_ts) / ts_span) * width;
 
     let points_vec: Vec<String> = history
         .iter()
         .map(|point| {
-            let x = ((point.timestamp_ms as f64 - min_ts) / ts_span) * width;
+            let x = x_of(point);
             let y = height - ((point.price - min_price) / price_span) * height;
             format!("{:.2},{:.2}", x, y)
         })
         .collect();
     let points = points_vec.join(" ");
-    let area_points = format!("{} {:.2},{:.2} 0,{:.2}", points, width, height, height);
+    let area_points = format!(
+        "{} {:.2},{:.2} {:.2},{:.2}",
+        points,
+        x_of(history.last()?),
+        height,
+        x_of(history.first()?),
+        height
+    );
 
     Some(ChartGeometry {
         points,
@@ -173,12 +306,89 @@ mod tests {
             },
         ];
 
-        let geometry = compute_chart_geometry(&history, 100.0, 50.0).expect("geometry");
+        let geometry = compute_chart_geometry(&history, 100.0, 50.0, None, 0).expect("geometry");
         assert!(geometry.points.contains(','));
         assert!(geometry.max_price > geometry.min_price);
         assert!(geometry.area_points.contains("100.00,50.00"));
     }
 
+    fn ramp(len: u64) -> Vec<HistoryPoint> {
+        (0..len)
+            .map(|i| HistoryPoint {
+                timestamp_ms: i * 10,
+                price: 100.0 + i as f64,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn points_in_range_keeps_one_neighbour_each_side() {
+        let history = ramp(11);
+        let visible = points_in_range(&history, 35.0, 65.0);
+        let timestamps: Vec<u64> = visible.iter().map(|point| point.timestamp_ms).collect();
+        assert_eq!(timestamps, vec![30, 40, 50, 60, 70]);
+        assert_eq!(points_in_range(&history, 0.0, 100.0).len(), 11);
+    }
+
+    #[test]
+    fn zoomed_geometry_maps_the_view_onto_the_full_width() {
+        let history = ramp(11);
+        // middle fifth of a 0..100ms span: 40ms..60ms
+        let geometry =
+            compute_chart_geometry(&history, 100.0, 50.0, Some((0.4, 0.6)), 0).expect("geometry");
+        assert_eq!(
+            geometry.points,
+            "-50.00,50.00 0.00,37.50 50.00,25.00 100.00,12.50 150.00,0.00"
+        );
+        assert_eq!(geometry.min_price, 103.0);
+        assert_eq!(geometry.max_price, 107.0);
+        assert_eq!(
+            compute_chart_geometry(&history, 100.0, 50.0, Some(FULL_VIEW), 0),
+            compute_chart_geometry(&history, 100.0, 50.0, None, 0)
+        );
+    }
+
+    #[test]
+    fn downsampling_caps_rendered_points_and_keeps_endpoints() {
+        let history: Vec<HistoryPoint> = (0..2_048u64)
+            .map(|i| HistoryPoint {
+                timestamp_ms: i,
+                price: 100.0 + (i as f64 / 40.0).sin(),
+            })
+            .collect();
+
+        let sampled = downsample(&history, MAX_RENDERED_POINTS);
+        assert_eq!(sampled.len(), MAX_RENDERED_POINTS);
+        assert_eq!(sampled.first(), history.first());
+        assert_eq!(sampled.last(), history.last());
+
+        let geometry = compute_chart_geometry(&history, 100.0, 50.0, None, MAX_RENDERED_POINTS)
+            .expect("geometry");
+        let points: Vec<&str> = geometry.points.split(' ').collect();
+        assert_eq!(points.len(), MAX_RENDERED_POINTS);
+        assert!(points[0].starts_with("0.00,"));
+        assert!(points[points.len() - 1].starts_with("100.00,"));
+    }
+
+    #[test]
+    fn zoom_keeps_the_anchor_fixed_and_stays_in_bounds() {
+        let zoomed = zoom_view(FULL_VIEW, 0.25, 0.5);
+        assert_eq!(zoomed, (0.125, 0.625));
+        // the anchored point sits at the same fraction of the view before and after
+        assert_eq!(zoomed.0 + 0.25 * (zoomed.1 - zoomed.0), 0.25);
+
+        assert_eq!(zoom_view((0.8, 1.0), 1.0, 10.0), FULL_VIEW);
+        let narrow = zoom_view((0.5, 0.51), 0.5, 0.1);
+        assert!((narrow.1 - narrow.0 - MIN_VIEW_SPAN).abs() < 1e-12);
+    }
+
+    #[test]
+    fn pan_stops_at_the_buffered_edges() {
+        assert_eq!(pan_view((0.25, 0.5), 0.25), (0.5, 0.75));
+        assert_eq!(pan_view((0.25, 0.5), -1.0), (0.0, 0.25));
+        assert_eq!(pan_view((0.25, 0.5), 1.0), (0.75, 1.0));
+    }
+
     #[test]
     fn compute_chart_geometry_rejects_insufficient_data() {
         let history = vec![HistoryPoint {
@@ -186,6 +396,6 @@ mod tests {
             price: 10.0,
         }];
 
-        assert!(compute_chart_geometry(&history, 100.0, 50.0).is_none());
+        assert!(compute_chart_geometry(&history, 100.0, 50.0, None, 0).is_none());
     }
 }
@@ -1,5 +1,7 @@
 pub mod dashboard;
 pub mod filters;
 pub mod history_chart;
+pub mod raw_feed;
 pub mod summary;
+pub mod symbol_detail;
 pub mod tick_table;
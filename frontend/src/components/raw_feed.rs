@@ -0,0 +1,25 @@
+use std::collections::VecDeque;
+
+use leptos::*;
+
+/// Collapsible list of the most recent raw gateway frames, newest first.
+#[component]
+pub fn RawFeedPanel(frames: RwSignal<VecDeque<String>>) -> impl IntoView {
+    view! {
+        <details class="raw-feed">
+            <summary>
+                {move || format!("Raw feed ({} frames)", frames.with(VecDeque::len))}
+            </summary>
+            <ol class="raw-feed__frames">
+                {move || {
+                    frames
+                        .get()
+                        .into_iter()
+                        .rev()
+                        .map(|frame| view! { <li><pre>{frame}</pre></li> })
+                        .collect_view()
+                }}
+            </ol>
+        </details>
+    }
+}
@@ -1,28 +1,72 @@
 use std::str::FromStr;
+use std::time::Duration;
 
 use leptos::event_target_value;
 use leptos::{ev, *};
+use market_core::{Breadth, Movers};
 
-use crate::StreamStatus;
+use crate::{TickStore, ticks::format::status_badge};
 
 use super::dashboard::{ConnectionStatusSignal, Theme, ThemeSignal, TickStoreSignal};
 
 const MOVERS_COUNT: usize = 3;
 
+/// Market-wide figures shown by the summary panel.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Summary {
+    pub total: usize,
+    pub advancers: Movers,
+    pub decliners: Movers,
+    pub breadth: Breadth,
+}
+
+/// Snapshot the store into a [`Summary`] with the top `movers` each way.
+pub fn summarize(store: &TickStore, movers: usize) -> Summary {
+    let (advancers, decliners) = store.movers(movers);
+    Summary {
+        total: store.latest().len(),
+        advancers,
+        decliners,
+        breadth: store.breadth(),
+    }
+}
+
+/// Header with connection status, symbol count, breadth, and top movers.
+///
+/// By default the summary is recomputed on every store change. With
+/// `refresh_ms` it is recomputed on that timer instead, which bounds the cost
+/// of ranking movers across large universes to once per interval.
 #[component]
-pub fn SummaryPanel() -> impl IntoView {
+pub fn SummaryPanel(#[prop(optional_no_strip)] refresh_ms: Option<u64>) -> impl IntoView {
     let tick_store = use_context::<TickStoreSignal>().expect("tick store context missing");
     let connection =
         use_context::<ConnectionStatusSignal>().expect("connection status context missing");
     let theme_signal = use_context::<ThemeSignal>().expect("theme signal context missing");
 
-    let summary = create_memo(move |_| {
-        tick_store.0.with(|store| {
-            let total = store.latest().len();
-            let (advancers, decliners) = store.movers(MOVERS_COUNT);
-            (total, advancers, decliners)
-        })
-    });
+    let summary: Signal<Summary> = match refresh_ms {
+        Some(refresh_ms) => {
+            let latest = create_rw_signal(
+                tick_store
+                    .0
+                    .with_untracked(|store| summarize(store, MOVERS_COUNT)),
+            );
+            let refresh = move || {
+                latest.set(
+                    tick_store
+                        .0
+                        .with_untracked(|store| summarize(store, MOVERS_COUNT)),
+                )
+            };
+            match set_interval_with_handle(refresh, Duration::from_millis(refresh_ms.max(1))) {
+                Ok(handle) => on_cleanup(move || handle.clear()),
+                Err(err) => log::warn!("summary refresh timer failed: {err:?}"),
+            }
+            latest.into()
+        }
+        None => {
+            create_memo(move |_| tick_store.0.with(|store| summarize(store, MOVERS_COUNT))).into()
+        }
+    };
 
     let theme_select_value = move || theme_signal.0.get().as_str().to_string();
 
@@ -36,26 +80,26 @@ pub fn SummaryPanel() -> impl IntoView {
         }
     };
 
-    let status_badge = move || match connection.0.get() {
-        StreamStatus::Connecting => ("status--connecting", "Connecting"),
-        StreamStatus::Connected => ("status--connected", "Live"),
-        StreamStatus::Reconnecting { .. } => ("status--reconnecting", "Reconnecting"),
-        StreamStatus::Failed => ("status--failed", "Disconnected"),
-        StreamStatus::Idle => ("status--idle", "Idle"),
-    };
-
     view! {
         <section class="summary-panel">
             <header class="summary-panel__header">
                 <div class="summary-panel__status">
                     {move || {
-                        let (class, label) = status_badge();
+                        let (class, label) = status_badge(connection.0.get());
                         view! { <span class=format!("status-badge {class}")>{label}</span> }
                     }}
                     <span class="summary-panel__total">
+                        {move || summary.with(|summary| format!("{} Symbols", summary.total))}
+                    </span>
+                    <span class="summary-panel__breadth">
                         {move || {
-                            let (total, _, _) = summary.get();
-                            format!("{} Symbols", total)
+                            summary.with(|summary| {
+                                let breadth = summary.breadth;
+                                format!(
+                                    "{} up / {} down / {} flat",
+                                    breadth.advancing, breadth.declining, breadth.unchanged
+                                )
+                            })
                         }}
                     </span>
                 </div>
@@ -77,11 +121,11 @@ pub fn SummaryPanel() -> impl IntoView {
             <div class="summary-panel__body">
                 <div>
                     <h3>"Top Advancers"</h3>
-                    <SummaryList items=move || summary.get().1.clone() empty_label="Waiting for data" />
+                    <SummaryList items=move || summary.with(|summary| summary.advancers.clone()) empty_label="Waiting for data" />
                 </div>
                 <div>
                     <h3>"Top Decliners"</h3>
-                    <SummaryList items=move || summary.get().2.clone() empty_label="Waiting for data" />
+                    <SummaryList items=move || summary.with(|summary| summary.decliners.clone()) empty_label="Waiting for data" />
                 </div>
             </div>
         </section>
@@ -122,3 +166,48 @@ where
         </ul>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticks::types::{Region, Sector, Tick};
+
+    fn tick(symbol: &str, price: f64, timestamp_ms: u64) -> Tick {
+        Tick {
+            symbol: symbol.parse().unwrap(),
+            price,
+            timestamp_ms,
+            region: Region::Europe,
+            sector: Sector::Energy,
+            prev_close: None,
+        }
+    }
+
+    #[test]
+    fn summary_ranks_movers_and_counts_breadth() {
+        let mut store = TickStore::new(8);
+        for (symbol, first, last) in [
+            ("AAA", 100.0, 110.0),
+            ("BBB", 100.0, 90.0),
+            ("CCC", 100.0, 100.0),
+            ("DDD", 100.0, 105.0),
+        ] {
+            store.ingest(tick(symbol, first, 1));
+            store.ingest(tick(symbol, last, 2));
+        }
+
+        let summary = summarize(&store, 1);
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.advancers, vec![("AAA".to_string(), 10.0)]);
+        assert_eq!(summary.decliners, vec![("BBB".to_string(), -10.0)]);
+        assert_eq!(
+            summary.breadth,
+            Breadth {
+                advancing: 2,
+                declining: 1,
+                unchanged: 1,
+            }
+        );
+        assert_eq!(summarize(&TickStore::new(8), 3), Summary::default());
+    }
+}
@@ -0,0 +1,153 @@
+use leptos::*;
+use market_core::Ohlc;
+
+use crate::{
+    TickStore,
+    ticks::format::{region_label, sector_label},
+};
+
+use super::dashboard::{SelectedSymbolSignal, TickStoreSignal};
+
+/// Aggregated statistics for a single symbol, rendered by the detail panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolDetail {
+    pub symbol: String,
+    pub region: &'static str,
+    pub sector: &'static str,
+    pub latest_price: f64,
+    pub change_pct: Option<f64>,
+    pub high: f64,
+    pub low: f64,
+    pub updates: u64,
+    pub age_ms: u64,
+}
+
+/// Compose the store's per-symbol accessors into a single detail snapshot.
+pub fn symbol_detail(store: &TickStore, symbol: &str, now_ms: u64) -> Option<SymbolDetail> {
+    let latest = store.latest().get(symbol)?;
+    let range = store
+        .history_for(symbol)
+        .and_then(|history| Ohlc::from_prices(history.iter().map(|point| point.price)))
+        .unwrap_or_else(|| Ohlc::new(latest.price));
+
+    Some(SymbolDetail {
+        symbol: symbol.to_string(),
+        region: region_label(latest.region),
+        sector: sector_label(latest.sector),
+        latest_price: latest.price,
+        change_pct: store.change_for(symbol),
+        high: range.high,
+        low: range.low,
+        updates: store.update_count(symbol),
+        age_ms: store.age_ms(symbol, now_ms).unwrap_or(0),
+    })
+}
+
+#[component]
+pub fn SymbolDetailPanel() -> impl IntoView {
+    let tick_store = use_context::<TickStoreSignal>().expect("tick store context missing");
+    let selected_symbol =
+        use_context::<SelectedSymbolSignal>().expect("selected symbol context missing");
+
+    let detail = create_memo(move |_| {
+        selected_symbol.0.get().and_then(|symbol| {
+            tick_store
+                .0
+                .with(|store| symbol_detail(store, &symbol, current_time_ms()))
+        })
+    });
+
+    view! {
+        <section class="symbol-detail">
+            <h2>"Symbol Detail"</h2>
+            {move || match detail.get() {
+                Some(detail) => {
+                    let change = detail
+                        .change_pct
+                        .map(|change| format!("{change:+.2}%"))
+                        .unwrap_or_else(|| "n/a".to_string());
+                    let positive = detail.change_pct.is_some_and(|change| change >= 0.0);
+                    view! {
+                        <dl class="symbol-detail__stats">
+                            <dt>"Symbol"</dt>
+                            <dd>{detail.symbol.clone()}</dd>
+                            <dt>"Region / Sector"</dt>
+                            <dd>{format!("{} / {}", detail.region, detail.sector)}</dd>
+                            <dt>"Latest"</dt>
+                            <dd>{format!("{:.4}", detail.latest_price)}</dd>
+                            <dt>"Change"</dt>
+                            <dd class:positive=positive class:negative=!positive>{change}</dd>
+                            <dt>"High / Low"</dt>
+                            <dd>{format!("{:.4} / {:.4}", detail.high, detail.low)}</dd>
+                            <dt>"Updates"</dt>
+                            <dd>{detail.updates}</dd>
+                            <dt>"Last update"</dt>
+                            <dd>{format!("{} ms ago", detail.age_ms)}</dd>
+                        </dl>
+                    }
+                    .into_view()
+                }
+                None => view! {
+                    <p class="symbol-detail__empty">"Select a symbol to view its statistics."</p>
+                }
+                .into_view(),
+            }}
+        </section>
+    }
+}
+
+fn current_time_ms() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now() as u64
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticks::types::{Region, Sector, Tick};
+
+    fn tick(price: f64, timestamp_ms: u64) -> Tick {
+        Tick {
+            symbol: "AAA".parse().unwrap(),
+            price,
+            timestamp_ms,
+            region: Region::Europe,
+            sector: Sector::Energy,
+            prev_close: None,
+        }
+    }
+
+    #[test]
+    fn symbol_detail_aggregates_store_statistics() {
+        let mut store = TickStore::new(8);
+        store.ingest(tick(10.0, 1_000));
+        store.ingest(tick(12.0, 1_100));
+        store.ingest(tick(11.0, 1_200));
+
+        let detail = symbol_detail(&store, "AAA", 1_500).expect("detail");
+        assert_eq!(detail.latest_price, 11.0);
+        assert_eq!(detail.high, 12.0);
+        assert_eq!(detail.low, 10.0);
+        assert_eq!(detail.updates, 3);
+        assert_eq!(detail.age_ms, 300);
+        assert_eq!(detail.region, "Europe");
+        assert!((detail.change_pct.unwrap() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn symbol_detail_is_none_for_unknown_symbol() {
+        let store = TickStore::new(8);
+        assert!(symbol_detail(&store, "AAA", 0).is_none());
+    }
+}
@@ -1,19 +1,163 @@
+use std::cmp::{Ordering, Reverse};
 use std::collections::HashSet;
 
 use leptos::*;
 
 use crate::{
-    StreamStatus, TickStore,
+    TickStore,
     ticks::{
-        format::{region_label, sector_label},
+        format::{region_label, sector_label, status_message},
         types::{Region, Sector, Tick},
     },
 };
 
 use super::dashboard::{
-    ConnectionStatusSignal, FilterState, SelectedSymbolSignal, TickStoreSignal,
+    ConnectionStatusSignal, FilterState, SelectedSymbolSignal, TableViewSignal, TickStoreSignal,
 };
 
+/// Row limits and page sizes offered by the table controls, besides "All".
+pub const ROW_COUNT_OPTIONS: [usize; 3] = [25, 50, 100];
+
+/// Order rows are ranked in before the table limits and pages them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RowSort {
+    /// First-seen order.
+    #[default]
+    Feed,
+    /// Most ticks received first; the feed carries no traded volume.
+    Activity,
+    /// Largest absolute change over retained history first.
+    Change,
+    Alphabetical,
+}
+
+impl RowSort {
+    pub const ALL: [RowSort; 4] = [
+        RowSort::Feed,
+        RowSort::Activity,
+        RowSort::Change,
+        RowSort::Alphabetical,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RowSort::Feed => "feed",
+            RowSort::Activity => "activity",
+            RowSort::Change => "change",
+            RowSort::Alphabetical => "alphabetical",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RowSort::Feed => "Feed order",
+            RowSort::Activity => "Most active",
+            RowSort::Change => "Biggest movers",
+            RowSort::Alphabetical => "A–Z",
+        }
+    }
+}
+
+impl std::str::FromStr for RowSort {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "feed" => Ok(RowSort::Feed),
+            "activity" => Ok(RowSort::Activity),
+            "change" => Ok(RowSort::Change),
+            "alphabetical" => Ok(RowSort::Alphabetical),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Sort, row limit, and page of the quote table. The default shows every row on one page.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TableView {
+    pub sort: RowSort,
+    /// Keep only the top rows after sorting; `None` keeps all.
+    pub limit: Option<usize>,
+    /// Zero-based page, clamped to the last page when rendering.
+    pub page: usize,
+    /// Rows per page; `None` shows everything on one page.
+    pub page_size: Option<usize>,
+}
+
+/// A filtered symbol with the statistics the table can rank by.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowStats {
+    pub tick: Tick,
+    pub updates: u64,
+    pub change: Option<f64>,
+}
+
+/// Rows visible on the current page.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TablePage {
+    pub rows: Vec<Tick>,
+    /// Page actually shown, after clamping.
+    pub page: usize,
+    /// Total pages, at least 1.
+    pub pages: usize,
+}
+
+/// Sort `rows`, keep the top `limit`, and slice out `page` of `page_size` rows.
+///
+/// Ties keep feed order. Symbols without a change sort after every mover, and
+/// a page past the end shows the last page instead of nothing.
+pub fn page_rows(
+    mut rows: Vec<RowStats>,
+    sort: RowSort,
+    limit: Option<usize>,
+    page: usize,
+    page_size: Option<usize>,
+) -> TablePage {
+    match sort {
+        RowSort::Feed => {}
+        RowSort::Activity => rows.sort_by_key(|row| Reverse(row.updates)),
+        RowSort::Change => {
+            let magnitude = |row: &RowStats| row.change.map_or(f64::NEG_INFINITY, f64::abs);
+            rows.sort_by(|a, b| {
+                magnitude(b)
+                    .partial_cmp(&magnitude(a))
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+        RowSort::Alphabetical => {
+            rows.sort_by(|a, b| a.tick.symbol.as_str().cmp(b.tick.symbol.as_str()))
+        }
+    }
+    if let Some(limit) = limit {
+        rows.truncate(limit);
+    }
+
+    let page_size = page_size
+        .filter(|size| *size > 0)
+        .unwrap_or_else(|| rows.len().max(1));
+    let pages = rows.len().div_ceil(page_size).max(1);
+    let page = page.min(pages - 1);
+    TablePage {
+        rows: rows
+            .into_iter()
+            .skip(page * page_size)
+            .take(page_size)
+            .map(|row| row.tick)
+            .collect(),
+        page,
+        pages,
+    }
+}
+
+/// `<select>` value for a row count; `"all"` stands for no limit.
+fn row_count_value(count: Option<usize>) -> String {
+    count.map_or_else(|| "all".to_string(), |count| count.to_string())
+}
+
+fn parse_row_count(value: &str) -> Option<usize> {
+    value.parse().ok().filter(|count| *count > 0)
+}
+
 #[component]
 pub fn TickTable() -> impl IntoView {
     let tick_store = use_context::<TickStoreSignal>().expect("tick store context missing");
@@ -22,6 +166,7 @@ pub fn TickTable() -> impl IntoView {
     let filters = use_context::<FilterState>().expect("filter state context missing");
     let connection =
         use_context::<ConnectionStatusSignal>().expect("connection status context missing");
+    let table_view = use_context::<TableViewSignal>().expect("table view context missing");
     let store_signal = tick_store.0;
 
     let rows = create_memo(move |_| {
@@ -37,36 +182,110 @@ pub fn TickTable() -> impl IntoView {
                 .latest()
                 .values()
                 .filter(|tick| matches_filters(&selected_regions, &selected_sectors, tick))
-                .cloned()
-                .collect::<Vec<Tick>>()
+                .map(|tick| RowStats {
+                    tick: tick.clone(),
+                    updates: store.update_count(&tick.symbol),
+                    change: store.change_for(&tick.symbol),
+                })
+                .collect::<Vec<RowStats>>()
         })
     });
+    let page = create_memo(move |_| {
+        let view = table_view.0.get();
+        page_rows(rows.get(), view.sort, view.limit, view.page, view.page_size)
+    });
+
+    let on_sort = move |ev: ev::Event| {
+        if let Ok(sort) = event_target_value(&ev).parse::<RowSort>() {
+            table_view.0.update(|view| {
+                view.sort = sort;
+                view.page = 0;
+            });
+        }
+    };
+    let on_limit = move |ev: ev::Event| {
+        let limit = parse_row_count(&event_target_value(&ev));
+        table_view.0.update(|view| {
+            view.limit = limit;
+            view.page = 0;
+        });
+    };
+    let on_page_size = move |ev: ev::Event| {
+        let page_size = parse_row_count(&event_target_value(&ev));
+        table_view.0.update(|view| {
+            view.page_size = page_size;
+            view.page = 0;
+        });
+    };
+    let on_prev = move |_| {
+        let current = page.with_untracked(|page| page.page);
+        table_view
+            .0
+            .update(|view| view.page = current.saturating_sub(1));
+    };
+    let on_next = move |_| {
+        let current = page.with_untracked(|page| page.page);
+        table_view.0.update(|view| view.page = current + 1);
+    };
+    let count_options = move || {
+        ROW_COUNT_OPTIONS
+            .into_iter()
+            .map(|count| view! { <option value=count.to_string()>{count}</option> })
+            .collect_view()
+    };
 
     view! {
         <section class="tick-table">
             <h2>"Live Quotes"</h2>
+            <div class="tick-table__controls">
+                <label>
+                    <span>"Sort"</span>
+                    <select
+                        class="theme-select"
+                        on:change=on_sort
+                        prop:value=move || table_view.0.get().sort.as_str()
+                    >
+                        <For
+                            each=move || RowSort::ALL.into_iter()
+                            key=|sort| sort.as_str()
+                            children=move |sort: RowSort| {
+                                view! { <option value=sort.as_str()>{sort.label()}</option> }
+                            }
+                        />
+                    </select>
+                </label>
+                <label>
+                    <span>"Show"</span>
+                    <select
+                        class="theme-select"
+                        on:change=on_limit
+                        prop:value=move || row_count_value(table_view.0.get().limit)
+                    >
+                        <option value="all">"All"</option>
+                        {count_options}
+                    </select>
+                </label>
+                <label>
+                    <span>"Per page"</span>
+                    <select
+                        class="theme-select"
+                        on:change=on_page_size
+                        prop:value=move || row_count_value(table_view.0.get().page_size)
+                    >
+                        <option value="all">"All"</option>
+                        {count_options}
+                    </select>
+                </label>
+            </div>
             <Show
                 when=move || !rows.get().is_empty()
                 fallback=move || {
                     let regions = filters.regions.get();
                     let sectors = filters.sectors.get();
-                    let status = connection.0.get();
                     let message = if regions.is_empty() && sectors.is_empty() {
                         "Select a region and sector to display symbols.".to_string()
                     } else {
-                        match status {
-                            StreamStatus::Connecting => "Connecting to market data...".to_string(),
-                            StreamStatus::Reconnecting { .. } => {
-                                "Reconnecting to the gateway...".to_string()
-                            }
-                            StreamStatus::Failed => {
-                                "Connection lost. Attempting to reconnect...".to_string()
-                            }
-                            StreamStatus::Connected => {
-                                "Waiting for symbols matching your filters.".to_string()
-                            }
-                            StreamStatus::Idle => "Waiting for connection...".to_string(),
-                        }
+                        status_message(connection.0.get())
                     };
 
                     view! { <p class="tick-table__empty">{message}</p> }
@@ -83,12 +302,12 @@ pub fn TickTable() -> impl IntoView {
                     </thead>
                     <tbody>
                         <For
-                            each=move || rows.get()
+                            each=move || page.get().rows
                             key=|tick| tick.symbol.clone()
                             children=move |tick: Tick| {
                                 let store_for_row = store_signal;
                                 let selected = selected_symbol;
-                                let symbol_display = tick.symbol.clone();
+                                let symbol_display = tick.symbol.to_string();
                                 let symbol_for_click = symbol_display.clone();
                                 let symbol_for_selection = symbol_display.clone();
 
@@ -118,6 +337,25 @@ pub fn TickTable() -> impl IntoView {
                         />
                     </tbody>
                 </table>
+                <Show when=move || page.with(|page| page.pages > 1)>
+                    <div class="tick-table__pager">
+                        <button
+                            on:click=on_prev
+                            disabled=move || page.with(|page| page.page == 0)
+                        >
+                            "Prev"
+                        </button>
+                        <span>
+                            {move || page.with(|page| format!("Page {} of {}", page.page + 1, page.pages))}
+                        </span>
+                        <button
+                            on:click=on_next
+                            disabled=move || page.with(|page| page.page + 1 >= page.pages)
+                        >
+                            "Next"
+                        </button>
+                    </div>
+                </Show>
             </Show>
         </section>
     }
@@ -137,7 +375,7 @@ fn price_signal(store: RwSignal<TickStore>, symbol: String, fallback: f64) -> Me
         store.with(|state| {
             state
                 .latest()
-                .get(&symbol)
+                .get(symbol.as_str())
                 .map(|tick| format!("{:.4}", tick.price))
                 .unwrap_or_else(|| format!("{fallback:.4}"))
         })
@@ -149,7 +387,7 @@ fn region_signal(store: RwSignal<TickStore>, symbol: String, fallback: Region) -
         store.with(|state| {
             state
                 .latest()
-                .get(&symbol)
+                .get(symbol.as_str())
                 .map(|tick| region_label(tick.region).to_string())
                 .unwrap_or_else(|| region_label(fallback).to_string())
         })
@@ -161,7 +399,7 @@ fn sector_signal(store: RwSignal<TickStore>, symbol: String, fallback: Sector) -
         store.with(|state| {
             state
                 .latest()
-                .get(&symbol)
+                .get(symbol.as_str())
                 .map(|tick| sector_label(tick.sector).to_string())
                 .unwrap_or_else(|| sector_label(fallback).to_string())
         })
@@ -178,11 +416,12 @@ mod tests {
         let mut regions = HashSet::new();
         let mut sectors = HashSet::new();
         let tick = Tick {
-            symbol: "AAA".into(),
+            symbol: "AAA".parse().unwrap(),
             price: 10.0,
             timestamp_ms: 1,
             region: Region::NorthAmerica,
             sector: Sector::Technology,
+            prev_close: None,
         };
 
         assert!(!matches_filters(&regions, &sectors, &tick));
@@ -198,6 +437,90 @@ mod tests {
         assert!(!matches_filters(&regions, &sectors, &tick));
     }
 
+    fn stats(symbol: &str, updates: u64, change: Option<f64>) -> RowStats {
+        RowStats {
+            tick: Tick {
+                symbol: symbol.parse().unwrap(),
+                price: 10.0,
+                timestamp_ms: 1,
+                region: Region::Europe,
+                sector: Sector::Energy,
+                prev_close: None,
+            },
+            updates,
+            change,
+        }
+    }
+
+    fn symbols(page: &TablePage) -> Vec<&str> {
+        page.rows.iter().map(|tick| tick.symbol.as_str()).collect()
+    }
+
+    fn sample_rows() -> Vec<RowStats> {
+        vec![
+            stats("CCC", 5, Some(1.0)),
+            stats("AAA", 9, None),
+            stats("DDD", 9, Some(-4.0)),
+            stats("BBB", 1, Some(2.5)),
+        ]
+    }
+
+    #[test]
+    fn default_view_shows_every_row_in_feed_order() {
+        let view = TableView::default();
+        let page = page_rows(
+            sample_rows(),
+            view.sort,
+            view.limit,
+            view.page,
+            view.page_size,
+        );
+        assert_eq!(symbols(&page), vec!["CCC", "AAA", "DDD", "BBB"]);
+        assert_eq!((page.page, page.pages), (0, 1));
+
+        let empty = page_rows(Vec::new(), view.sort, view.limit, view.page, view.page_size);
+        assert!(empty.rows.is_empty());
+        assert_eq!((empty.page, empty.pages), (0, 1));
+    }
+
+    #[test]
+    fn sorts_rank_rows_before_the_limit() {
+        let top = |sort| symbols(&page_rows(sample_rows(), sort, Some(2), 0, None)).join(",");
+        // ties keep feed order
+        assert_eq!(top(RowSort::Activity), "AAA,DDD");
+        // symbols without a change rank last
+        assert_eq!(top(RowSort::Change), "DDD,BBB");
+        assert_eq!(top(RowSort::Alphabetical), "AAA,BBB");
+        assert_eq!(top(RowSort::Feed), "CCC,AAA");
+    }
+
+    #[test]
+    fn pages_slice_the_limited_rows_and_clamp() {
+        let rows: Vec<RowStats> = (0..7)
+            .map(|idx| stats(&format!("S{idx}"), 0, None))
+            .collect();
+
+        let second = page_rows(rows.clone(), RowSort::Feed, None, 1, Some(3));
+        assert_eq!(symbols(&second), vec!["S3", "S4", "S5"]);
+        assert_eq!(second.pages, 3);
+
+        let past_end = page_rows(rows.clone(), RowSort::Feed, None, 9, Some(3));
+        assert_eq!(symbols(&past_end), vec!["S6"]);
+        assert_eq!(past_end.page, 2);
+
+        let limited = page_rows(rows, RowSort::Feed, Some(5), 1, Some(3));
+        assert_eq!(symbols(&limited), vec!["S3", "S4"]);
+        assert_eq!(limited.pages, 2);
+    }
+
+    #[test]
+    fn row_counts_round_trip_through_select_values() {
+        assert_eq!(row_count_value(None), "all");
+        assert_eq!(parse_row_count(&row_count_value(Some(50))), Some(50));
+        assert_eq!(parse_row_count("all"), None);
+        assert_eq!(parse_row_count("0"), None);
+    }
+
     #[test]
     fn price_signal_updates_with_store_changes() {
         let runtime = create_runtime();
@@ -206,11 +529,12 @@ mod tests {
 
         store.update(|state| {
             state.ingest(Tick {
-                symbol: symbol.clone(),
+                symbol: symbol.parse().unwrap(),
                 price: 10.0,
                 timestamp_ms: 1,
                 region: Region::NorthAmerica,
                 sector: Sector::Technology,
+                prev_close: None,
             });
         });
 
@@ -219,11 +543,12 @@ mod tests {
 
         store.update(|state| {
             state.ingest(Tick {
-                symbol: symbol.clone(),
+                symbol: symbol.parse().unwrap(),
                 price: 12.5,
                 timestamp_ms: 2,
                 region: Region::NorthAmerica,
                 sector: Sector::Technology,
+                prev_close: None,
             });
         });
 
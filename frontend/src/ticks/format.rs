@@ -1,4 +1,5 @@
 use super::types::{Region, Sector};
+use super::websocket::StreamStatus;
 
 pub fn region_label(region: Region) -> &'static str {
     match region {
@@ -25,6 +26,34 @@ pub fn sector_label(sector: Sector) -> &'static str {
     }
 }
 
+/// Empty-state copy describing the connection, shared by every component that
+/// explains why no data is visible yet.
+pub fn status_message(status: StreamStatus) -> String {
+    match status {
+        StreamStatus::Idle => "Waiting for connection...".to_string(),
+        StreamStatus::Connecting => "Connecting for the first time...".to_string(),
+        StreamStatus::Connected => "Waiting for symbols matching your filters.".to_string(),
+        StreamStatus::Reconnecting { attempt } => {
+            format!("Lost connection, reconnecting (attempt {attempt})...")
+        }
+        StreamStatus::Failed => "Connection lost. Retrying shortly...".to_string(),
+    }
+}
+
+/// CSS modifier class and short label for the connection status badge.
+pub fn status_badge(status: StreamStatus) -> (&'static str, String) {
+    match status {
+        StreamStatus::Idle => ("status--idle", "Idle".to_string()),
+        StreamStatus::Connecting => ("status--connecting", "Connecting".to_string()),
+        StreamStatus::Connected => ("status--connected", "Live".to_string()),
+        StreamStatus::Reconnecting { attempt } => (
+            "status--reconnecting",
+            format!("Reconnecting (attempt {attempt})"),
+        ),
+        StreamStatus::Failed => ("status--failed", "Disconnected".to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -37,4 +66,52 @@ mod tests {
             "Consumer Discretionary"
         );
     }
+
+    #[test]
+    fn status_copy_distinguishes_first_connect_from_reconnect() {
+        assert_eq!(
+            status_message(StreamStatus::Idle),
+            "Waiting for connection..."
+        );
+        assert_eq!(
+            status_message(StreamStatus::Connecting),
+            "Connecting for the first time..."
+        );
+        assert_eq!(
+            status_message(StreamStatus::Connected),
+            "Waiting for symbols matching your filters."
+        );
+        assert_eq!(
+            status_message(StreamStatus::Reconnecting { attempt: 3 }),
+            "Lost connection, reconnecting (attempt 3)..."
+        );
+        assert_eq!(
+            status_message(StreamStatus::Failed),
+            "Connection lost. Retrying shortly..."
+        );
+    }
+
+    #[test]
+    fn status_badge_covers_every_variant() {
+        assert_eq!(
+            status_badge(StreamStatus::Idle),
+            ("status--idle", "Idle".into())
+        );
+        assert_eq!(
+            status_badge(StreamStatus::Connecting),
+            ("status--connecting", "Connecting".into())
+        );
+        assert_eq!(
+            status_badge(StreamStatus::Connected),
+            ("status--connected", "Live".into())
+        );
+        assert_eq!(
+            status_badge(StreamStatus::Reconnecting { attempt: 2 }),
+            ("status--reconnecting", "Reconnecting (attempt 2)".into())
+        );
+        assert_eq!(
+            status_badge(StreamStatus::Failed),
+            ("status--failed", "Disconnected".into())
+        );
+    }
 }
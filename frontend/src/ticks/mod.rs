@@ -1,4 +1,5 @@
 pub mod format;
+pub mod raw_feed;
 pub mod store;
 pub mod types;
 pub mod websocket;
@@ -0,0 +1,49 @@
+use std::collections::VecDeque;
+
+/// Append `item`, dropping the oldest entries so at most `capacity` remain.
+pub fn push_bounded<T>(buffer: &mut VecDeque<T>, item: T, capacity: usize) {
+    if capacity == 0 {
+        buffer.clear();
+        return;
+    }
+    while buffer.len() >= capacity {
+        buffer.pop_front();
+    }
+    buffer.push_back(item);
+}
+
+/// Pretty-print a raw websocket frame, leaving text that is not JSON untouched.
+pub fn pretty_frame(raw: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(raw)
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .unwrap_or_else(|_| raw.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_bounded_keeps_newest_entries() {
+        let mut buffer = VecDeque::new();
+        for frame in 1..=5 {
+            push_bounded(&mut buffer, frame, 3);
+        }
+        assert_eq!(buffer, VecDeque::from([3, 4, 5]));
+
+        push_bounded(&mut buffer, 6, 1);
+        assert_eq!(buffer, VecDeque::from([6]));
+
+        push_bounded(&mut buffer, 7, 0);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn pretty_frame_formats_json_and_keeps_garbage() {
+        assert_eq!(
+            pretty_frame(r#"{"ticks":[1]}"#),
+            "{\n  \"ticks\": [\n    1\n  ]\n}"
+        );
+        assert_eq!(pretty_frame("not json"), "not json");
+    }
+}
@@ -1,39 +1,251 @@
-use std::{
-    cmp::Ordering,
-    collections::{HashMap, VecDeque},
-};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
 use indexmap::IndexMap;
+use market_core::{Breadth, breadth, pct_change, rank_movers};
 
-use super::types::{HistoryPoint, Tick};
+pub use market_core::Movers;
 
-pub type Movers = Vec<(String, f64)>;
+use super::types::{HistoryPoint, Symbol, Tick};
 
 /// In-memory structure keeping the latest tick per symbol and recent history.
+///
+/// Focused symbols keep up to `max_history` points; every other symbol is
+/// trimmed to the shorter unfocused limit. An unfocused limit of zero skips
+/// history bookkeeping for those symbols entirely, so they only update the
+/// latest snapshot (and report no change or movers).
+///
+/// A non-zero minimum history interval decimates bursty feeds: a tick only
+/// becomes a history point once its timestamp is at least that far past the
+/// symbol's last stored point, while `latest` still sees every tick.
+///
+/// With a symbol cap, a tick for a new symbol beyond the cap evicts the
+/// least recently updated symbol (its latest tick, history, and update count),
+/// so a feed announcing endless distinct symbols cannot grow the store.
+///
+/// Ticks older than a symbol's newest history point are appended as received
+/// unless an [`OutOfOrder`] policy keeps history sorted by timestamp.
 #[derive(Clone)]
 pub struct TickStore {
     max_history: usize,
-    latest: IndexMap<String, Tick>,
-    history: HashMap<String, VecDeque<HistoryPoint>>,
+    unfocused_history: usize,
+    min_history_interval_ms: u64,
+    max_symbols: Option<usize>,
+    out_of_order: OutOfOrder,
+    recency: Recency,
+    focused: HashSet<String>,
+    latest: IndexMap<Symbol, Tick>,
+    history: HashMap<Symbol, VecDeque<HistoryPoint>>,
+    updates: HashMap<Symbol, u64>,
+}
+
+/// How history treats a tick older than the symbol's newest point, as
+/// happens when feeds with different latencies interleave.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutOfOrder {
+    /// Append as received; cheapest, but history may step back in time.
+    #[default]
+    Append,
+    /// Insert at its timestamp so history stays sorted.
+    Insert,
+    /// Insert ticks up to `max_skew_ms` older than the newest point at their
+    /// timestamp and drop anything older.
+    DropStale { max_skew_ms: u64 },
+}
+
+/// Add `point` to `history`, keeping it sorted unless `policy` is [`OutOfOrder::Append`].
+fn place_point(history: &mut VecDeque<HistoryPoint>, point: HistoryPoint, policy: OutOfOrder) {
+    let newest = match history.back() {
+        Some(last) if point.timestamp_ms < last.timestamp_ms => last.timestamp_ms,
+        _ => {
+            history.push_back(point);
+            return;
+        }
+    };
+    match policy {
+        OutOfOrder::Append => history.push_back(point),
+        OutOfOrder::DropStale { max_skew_ms } if newest - point.timestamp_ms > max_skew_ms => {}
+        OutOfOrder::Insert | OutOfOrder::DropStale { .. } => {
+            let at = history.partition_point(|stored| stored.timestamp_ms <= point.timestamp_ms);
+            history.insert(at, point);
+        }
+    }
+}
+
+/// Symbols ordered by their last update, maintained only under a symbol cap.
+#[derive(Clone, Default)]
+struct Recency {
+    next: u64,
+    order: BTreeMap<u64, Symbol>,
+    stamps: HashMap<Symbol, u64>,
+}
+
+impl Recency {
+    /// Mark `symbol` as the most recently updated, cloning it only when new.
+    fn touch(&mut self, symbol: &Symbol) {
+        let stamp = self.next;
+        self.next += 1;
+        match self.stamps.get_mut(symbol) {
+            Some(previous) => {
+                if let Some(symbol) = self.order.remove(previous) {
+                    self.order.insert(stamp, symbol);
+                }
+                *previous = stamp;
+            }
+            None => {
+                self.order.insert(stamp, symbol.clone());
+                self.stamps.insert(symbol.clone(), stamp);
+            }
+        }
+    }
+
+    fn pop_oldest(&mut self) -> Option<Symbol> {
+        let (_, symbol) = self.order.pop_first()?;
+        self.stamps.remove(&symbol);
+        Some(symbol)
+    }
+
+    fn clear(&mut self) {
+        self.order.clear();
+        self.stamps.clear();
+    }
 }
 
 impl TickStore {
     pub fn new(max_history: usize) -> Self {
         Self {
             max_history,
+            unfocused_history: max_history,
+            min_history_interval_ms: 0,
+            max_symbols: None,
+            out_of_order: OutOfOrder::Append,
+            recency: Recency::default(),
+            focused: HashSet::new(),
             latest: IndexMap::new(),
             history: HashMap::new(),
+            updates: HashMap::new(),
         }
     }
 
+    /// Limit the history kept for symbols outside the focus set; `0` keeps none.
+    pub fn with_unfocused_history(mut self, limit: usize) -> Self {
+        self.unfocused_history = limit.min(self.max_history);
+        self
+    }
+
+    /// Space stored history points at least `interval_ms` apart in tick time; `0` keeps every tick.
+    /// Late ticks handled by an [`OutOfOrder`] policy are exempt.
+    pub fn with_min_history_interval(mut self, interval_ms: u64) -> Self {
+        self.min_history_interval_ms = interval_ms;
+        self
+    }
+
+    /// Track at most `limit` symbols, evicting the least recently updated one
+    /// when a new symbol arrives at the cap.
+    pub fn with_max_symbols(mut self, limit: usize) -> Self {
+        self.max_symbols = Some(limit.max(1));
+        self
+    }
+
+    /// Handle ticks older than a symbol's newest history point with `policy`.
+    /// Under any policy but [`OutOfOrder::Append`], a late tick also leaves the
+    /// symbol's latest tick untouched.
+    pub fn with_out_of_order(mut self, policy: OutOfOrder) -> Self {
+        self.out_of_order = policy;
+        self
+    }
+
+    /// Whether late ticks are placed by an [`OutOfOrder`] policy, so the feed
+    /// should deliver them rather than reject them.
+    pub fn orders_late_ticks(&self) -> bool {
+        self.out_of_order != OutOfOrder::Append
+    }
+
+    /// Replace the set of focused symbols, trimming any that lost focus.
+    pub fn set_focus<I, S>(&mut self, symbols: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.focused = symbols.into_iter().map(Into::into).collect();
+        for (symbol, entry) in self.history.iter_mut() {
+            if !self.focused.contains(symbol.as_str()) {
+                while entry.len() > self.unfocused_history {
+                    entry.pop_front();
+                }
+            }
+        }
+    }
+
+    pub fn is_focused(&self, symbol: &str) -> bool {
+        self.focused.contains(symbol)
+    }
+
     /// Ingest a single tick, updating the latest price and history buffer.
+    ///
+    /// Known symbols are updated in place, so the symbol is only cloned the
+    /// first time it is seen.
     pub fn ingest(&mut self, tick: Tick) {
-        let symbol = tick.symbol.clone();
-        self.latest.insert(symbol.clone(), tick.clone());
-        let entry = self.history.entry(symbol).or_default();
-        entry.push_back((&tick).into());
-        if entry.len() > self.max_history {
-            entry.pop_front();
+        if let Some(max_symbols) = self.max_symbols {
+            if !self.latest.contains_key(&tick.symbol) {
+                while self.latest.len() >= max_symbols {
+                    let Some(oldest) = self.recency.pop_oldest() else {
+                        break;
+                    };
+                    self.evict(&oldest);
+                }
+            }
+            self.recency.touch(&tick.symbol);
+        }
+
+        let limit = if self.focused.contains(tick.symbol.as_str()) {
+            self.max_history
+        } else {
+            self.unfocused_history
+        };
+
+        match self.updates.get_mut(&tick.symbol) {
+            Some(count) => *count += 1,
+            None => {
+                self.updates.insert(tick.symbol.clone(), 1);
+            }
+        }
+
+        if limit > 0 {
+            let point = HistoryPoint::from(&tick);
+            if let Some(entry) = self.history.get_mut(&tick.symbol) {
+                // a late tick skips the spacing check under an ordered policy,
+                // which then inserts or drops it by its own rules
+                let due = match entry.back() {
+                    Some(last)
+                        if point.timestamp_ms < last.timestamp_ms
+                            && self.out_of_order != OutOfOrder::Append =>
+                    {
+                        true
+                    }
+                    Some(last) => {
+                        point.timestamp_ms.saturating_sub(last.timestamp_ms)
+                            >= self.min_history_interval_ms
+                    }
+                    None => true,
+                };
+                if due {
+                    place_point(entry, point, self.out_of_order);
+                    while entry.len() > limit {
+                        entry.pop_front();
+                    }
+                }
+            } else {
+                self.history
+                    .insert(tick.symbol.clone(), VecDeque::from([point]));
+            }
+        }
+
+        if let Some(latest) = self.latest.get_mut(&tick.symbol) {
+            if self.out_of_order == OutOfOrder::Append || tick.timestamp_ms >= latest.timestamp_ms {
+                *latest = tick;
+            }
+        } else {
+            self.latest.insert(tick.symbol.clone(), tick);
         }
     }
 
@@ -42,16 +254,28 @@ impl TickStore {
     where
         I: IntoIterator<Item = Tick>,
     {
+        let ticks = ticks.into_iter();
+        // the first batches introduce most symbols; reserve once rather than rehashing per insert
+        let expected = ticks.size_hint().0.saturating_sub(self.latest.len());
+        self.latest.reserve(expected);
+        self.updates.reserve(expected);
         for tick in ticks {
             self.ingest(tick);
         }
     }
 
-    pub fn latest(&self) -> &IndexMap<String, Tick> {
+    /// Drop everything held for `symbol`, keeping first-seen order for the rest.
+    fn evict(&mut self, symbol: &str) {
+        self.latest.shift_remove(symbol);
+        self.history.remove(symbol);
+        self.updates.remove(symbol);
+    }
+
+    pub fn latest(&self) -> &IndexMap<Symbol, Tick> {
         &self.latest
     }
 
-    pub fn latest_mut(&mut self) -> &mut IndexMap<String, Tick> {
+    pub fn latest_mut(&mut self) -> &mut IndexMap<Symbol, Tick> {
         &mut self.latest
     }
 
@@ -59,58 +283,68 @@ impl TickStore {
         self.history.get(symbol)
     }
 
+    /// Percentage change between the oldest and newest retained price for a symbol.
+    pub fn change_for(&self, symbol: &str) -> Option<f64> {
+        let history = self.history.get(symbol)?;
+        pct_change(history.front()?.price, history.back()?.price)
+    }
+
+    /// Total number of ticks ingested for a symbol, including trimmed history.
+    pub fn update_count(&self, symbol: &str) -> u64 {
+        self.updates.get(symbol).copied().unwrap_or(0)
+    }
+
+    /// Milliseconds elapsed between the latest tick for a symbol and `now_ms`.
+    pub fn age_ms(&self, symbol: &str, now_ms: u64) -> Option<u64> {
+        self.latest
+            .get(symbol)
+            .map(|tick| now_ms.saturating_sub(tick.timestamp_ms))
+    }
+
     /// Reset the store to an empty state, removing all cached ticks and history.
     pub fn clear(&mut self) {
         self.latest.clear();
         self.history.clear();
+        self.updates.clear();
+        self.recency.clear();
     }
 
-    /// Return the top advancers and decliners by percentage change since their first recorded price.
+    /// Return the top advancers and decliners by percentage change across their retained history.
     pub fn movers(&self, count: usize) -> (Movers, Movers) {
-        if count == 0 || self.latest.is_empty() {
-            return (Vec::new(), Vec::new());
-        }
-
-        let mut changes: Vec<(String, f64)> = self
+        let changes = self
             .latest
-            .iter()
-            .map(|(symbol, _)| {
-                let change = self
-                    .history
-                    .get(symbol)
-                    .and_then(|history| {
-                        let first = history.front()?;
-                        let last = history.back()?;
-                        if first.price > 0.0 {
-                            Some(((last.price - first.price) / first.price) * 100.0)
-                        } else {
-                            None
-                        }
-                    })
-                    .unwrap_or(0.0);
-                (symbol.clone(), change)
-            })
-            .collect();
+            .keys()
+            .map(|symbol| (symbol.to_string(), self.change_for(symbol).unwrap_or(0.0)));
+        rank_movers(changes, count)
+    }
 
-        changes.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+    /// Latest ticks priced within `min..=max`, in first-seen order.
+    pub fn filter_by_price(&self, min: f64, max: f64) -> Vec<&Tick> {
+        self.latest
+            .values()
+            .filter(|tick| (min..=max).contains(&tick.price))
+            .collect()
+    }
 
-        let advancers = changes
-            .iter()
-            .filter(|(_, change)| *change > 0.0)
-            .take(count)
+    /// Symbols whose retained-history change lies within `min_pct..=max_pct`, in first-seen order.
+    pub fn filter_by_change(&self, min_pct: f64, max_pct: f64) -> Vec<Symbol> {
+        self.latest
+            .keys()
+            .filter(|symbol| {
+                self.change_for(symbol)
+                    .is_some_and(|change| (min_pct..=max_pct).contains(&change))
+            })
             .cloned()
-            .collect::<Vec<_>>();
-
-        let mut declines = changes.clone();
-        declines.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
-
-        let decliners = declines
-            .into_iter()
-            .filter(|(_, change)| *change < 0.0)
-            .take(count)
-            .collect::<Vec<_>>();
+            .collect()
+    }
 
-        (advancers, decliners)
+    /// Count symbols up, down, or flat across their retained history.
+    pub fn breadth(&self) -> Breadth {
+        breadth(
+            self.latest
+                .keys()
+                .map(|symbol| self.change_for(symbol).unwrap_or(0.0)),
+        )
     }
 }
 
@@ -120,11 +354,12 @@ mod tests {
 
     fn sample_tick(symbol: &str, price: f64, timestamp_ms: u64) -> Tick {
         Tick {
-            symbol: symbol.to_string(),
+            symbol: symbol.parse().unwrap(),
             price,
             timestamp_ms,
             region: crate::ticks::types::Region::NorthAmerica,
             sector: crate::ticks::types::Sector::Technology,
+            prev_close: None,
         }
     }
 
@@ -152,6 +387,107 @@ mod tests {
         assert_eq!(history.back().unwrap().price, 12.0);
     }
 
+    #[test]
+    fn history_points_respect_the_minimum_interval() {
+        let mut store = TickStore::new(64).with_min_history_interval(10);
+        // a burst every 3ms, as a microsecond tick interval would produce
+        for ts in (0..100).step_by(3) {
+            store.ingest(sample_tick("AAA", ts as f64, ts));
+        }
+
+        let history = store.history_for("AAA").unwrap();
+        let stamps: Vec<u64> = history.iter().map(|point| point.timestamp_ms).collect();
+        assert_eq!(stamps, vec![0, 12, 24, 36, 48, 60, 72, 84, 96]);
+        assert!(stamps.windows(2).all(|pair| pair[1] - pair[0] >= 10));
+        // latest and the update count still see every tick
+        assert_eq!(store.latest().get("AAA").unwrap().timestamp_ms, 99);
+        assert_eq!(store.update_count("AAA"), 34);
+    }
+
+    #[test]
+    fn out_of_order_policies_keep_history_sorted() {
+        let stamps = |store: &TickStore| -> Vec<u64> {
+            store
+                .history_for("AAA")
+                .unwrap()
+                .iter()
+                .map(|point| point.timestamp_ms)
+                .collect()
+        };
+        let ingest = |policy: OutOfOrder| {
+            let mut store = TickStore::new(8).with_out_of_order(policy);
+            for ts in [100, 110, 105, 120, 40, 115] {
+                store.ingest(sample_tick("AAA", ts as f64, ts));
+            }
+            store
+        };
+
+        let appended = ingest(OutOfOrder::Append);
+        assert_eq!(stamps(&appended), vec![100, 110, 105, 120, 40, 115]);
+        assert_eq!(appended.latest()["AAA"].timestamp_ms, 115);
+
+        let inserted = ingest(OutOfOrder::Insert);
+        assert_eq!(stamps(&inserted), vec![40, 100, 105, 110, 115, 120]);
+        assert_eq!(inserted.latest()["AAA"].timestamp_ms, 120);
+        assert_eq!(inserted.update_count("AAA"), 6);
+
+        // 40 is 80ms behind the newest point, beyond the 20ms tolerance
+        let bounded = ingest(OutOfOrder::DropStale { max_skew_ms: 20 });
+        assert_eq!(stamps(&bounded), vec![100, 105, 110, 115, 120]);
+        assert_eq!(bounded.latest()["AAA"].timestamp_ms, 120);
+    }
+
+    #[test]
+    fn late_ticks_bypass_the_minimum_interval_under_an_ordered_policy() {
+        let ingest = |policy: OutOfOrder| {
+            let mut store = TickStore::new(8)
+                .with_min_history_interval(10)
+                .with_out_of_order(policy);
+            for ts in [100, 110, 105, 115, 120, 60] {
+                store.ingest(sample_tick("AAA", ts as f64, ts));
+            }
+            store
+                .history_for("AAA")
+                .unwrap()
+                .iter()
+                .map(|point| point.timestamp_ms)
+                .collect::<Vec<u64>>()
+        };
+
+        // 115 is still thinned; the late 105 and 60 are placed by the policy
+        assert_eq!(ingest(OutOfOrder::Insert), vec![60, 100, 105, 110, 120]);
+        assert_eq!(
+            ingest(OutOfOrder::DropStale { max_skew_ms: 20 }),
+            vec![100, 105, 110, 120]
+        );
+        assert_eq!(ingest(OutOfOrder::Append), vec![100, 110, 120]);
+    }
+
+    #[test]
+    fn symbol_cap_evicts_the_least_recently_updated() {
+        let mut store = TickStore::new(4).with_max_symbols(3);
+        for (idx, symbol) in ["AAA", "BBB", "CCC"].into_iter().enumerate() {
+            store.ingest(sample_tick(symbol, 10.0, idx as u64));
+        }
+        // AAA is refreshed, so BBB becomes the stalest
+        store.ingest(sample_tick("AAA", 11.0, 3));
+        store.ingest(sample_tick("DDD", 12.0, 4));
+
+        let symbols: Vec<&str> = store.latest().keys().map(|s| s.as_str()).collect();
+        assert_eq!(symbols, vec!["AAA", "CCC", "DDD"]);
+        assert!(store.history_for("BBB").is_none());
+        assert_eq!(store.update_count("BBB"), 0);
+        assert_eq!(store.update_count("AAA"), 2);
+
+        // a flood of new symbols keeps only the newest ones
+        store.ingest_batch((0..1_000).map(|idx| sample_tick(&format!("N{idx:04}"), 1.0, 10 + idx)));
+        let symbols: Vec<&str> = store.latest().keys().map(|s| s.as_str()).collect();
+        assert_eq!(symbols, vec!["N0997", "N0998", "N0999"]);
+        assert_eq!(store.history.len(), 3);
+        assert_eq!(store.updates.len(), 3);
+        assert_eq!(store.recency.stamps.len(), 3);
+    }
+
     #[test]
     fn batch_ingest_updates_multiple_symbols() {
         let mut store = TickStore::new(4);
@@ -166,6 +502,100 @@ mod tests {
         assert_eq!(store.latest().get("BBB").unwrap().price, 20.0);
     }
 
+    #[test]
+    fn repeated_large_batches_keep_latest_and_history_consistent() {
+        const SYMBOLS: usize = 500;
+        const BATCHES: u64 = 200;
+        let symbol = |idx: usize| format!("SYM{idx:03}");
+
+        let mut store = TickStore::new(32).with_unfocused_history(4);
+        store.set_focus([symbol(7)]);
+        for batch in 0..BATCHES {
+            store.ingest_batch(
+                (0..SYMBOLS).map(|idx| sample_tick(&symbol(idx), idx as f64 + batch as f64, batch)),
+            );
+        }
+
+        assert_eq!(store.latest().len(), SYMBOLS);
+        // first-seen order is kept across in-place updates
+        assert_eq!(store.latest().keys().next().unwrap(), "SYM000");
+        for idx in 0..SYMBOLS {
+            let name = symbol(idx);
+            let latest = &store.latest()[name.as_str()];
+            assert_eq!(latest.price, idx as f64 + (BATCHES - 1) as f64);
+            assert_eq!(store.update_count(&name), BATCHES);
+
+            let history = store.history_for(&name).unwrap();
+            let expected = if idx == 7 { 32 } else { 4 };
+            assert_eq!(history.len(), expected, "{name}");
+            assert_eq!(history.back().unwrap().price, latest.price);
+            assert_eq!(history.back().unwrap().timestamp_ms, latest.timestamp_ms);
+        }
+    }
+
+    #[test]
+    fn zero_unfocused_history_skips_history_for_unfocused_symbols() {
+        let mut store = TickStore::new(8).with_unfocused_history(0);
+        store.set_focus(["AAA"]);
+        for ts in 1..=3 {
+            store.ingest_batch([
+                sample_tick("AAA", 10.0 + ts as f64, ts),
+                sample_tick("BBB", 20.0 + ts as f64, ts),
+            ]);
+        }
+
+        assert_eq!(store.history_for("AAA").unwrap().len(), 3);
+        assert!(store.history_for("BBB").is_none());
+        assert_eq!(store.latest().get("BBB").unwrap().price, 23.0);
+        assert_eq!(store.update_count("BBB"), 3);
+        assert_eq!(store.change_for("BBB"), None);
+    }
+
+    #[test]
+    fn focused_symbols_retain_longer_history() {
+        let mut store = TickStore::new(8).with_unfocused_history(2);
+        store.set_focus(["AAA"]);
+        for ts in 1..=6 {
+            store.ingest(sample_tick("AAA", 10.0 + ts as f64, ts));
+            store.ingest(sample_tick("BBB", 20.0 + ts as f64, ts));
+        }
+
+        assert_eq!(store.history_for("AAA").unwrap().len(), 6);
+        assert_eq!(store.history_for("BBB").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn losing_focus_trims_history() {
+        let mut store = TickStore::new(8).with_unfocused_history(3);
+        store.set_focus(["AAA"]);
+        for ts in 1..=6 {
+            store.ingest(sample_tick("AAA", 10.0, ts));
+        }
+        assert_eq!(store.history_for("AAA").unwrap().len(), 6);
+
+        store.set_focus(["BBB"]);
+        assert!(!store.is_focused("AAA"));
+        let history = store.history_for("AAA").unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.front().unwrap().timestamp_ms, 4);
+    }
+
+    #[test]
+    fn change_age_and_update_count_track_symbol() {
+        let mut store = TickStore::new(2);
+        store.ingest(sample_tick("AAA", 10.0, 100));
+        store.ingest(sample_tick("AAA", 12.0, 200));
+        store.ingest(sample_tick("AAA", 15.0, 300));
+
+        let change = store.change_for("AAA").unwrap();
+        assert!((change - 25.0).abs() < 1e-9, "change over retained window");
+        assert_eq!(store.update_count("AAA"), 3);
+        assert_eq!(store.age_ms("AAA", 1_000), Some(700));
+        assert_eq!(store.age_ms("AAA", 50), Some(0));
+        assert_eq!(store.change_for("ZZZ"), None);
+        assert_eq!(store.update_count("ZZZ"), 0);
+    }
+
     #[test]
     fn movers_returns_sorted_advancers_decliners() {
         let mut store = TickStore::new(8);
@@ -187,4 +617,88 @@ mod tests {
         assert_eq!(decliners.first().unwrap().0, "BBB");
         assert!(decliners.first().unwrap().1 < 0.0);
     }
+
+    #[test]
+    fn movers_break_ties_by_symbol() {
+        let mut store = TickStore::new(8);
+        // insertion order deliberately differs from alphabetical order
+        for (symbol, from, to) in [
+            ("DDD", 10.0, 11.0),
+            ("AAA", 10.0, 11.0),
+            ("EEE", 20.0, 18.0),
+            ("BBB", 20.0, 18.0),
+            ("ZZZ", 5.0, 5.0),
+            ("CCC", 5.0, 5.0),
+        ] {
+            store.ingest(sample_tick(symbol, from, 1));
+            store.ingest(sample_tick(symbol, to, 2));
+        }
+
+        let (advancers, decliners) = store.movers(5);
+        let symbols = |movers: &Movers| movers.iter().map(|(s, _)| s.clone()).collect::<Vec<_>>();
+        assert_eq!(symbols(&advancers), vec!["AAA", "DDD"]);
+        assert_eq!(symbols(&decliners), vec!["BBB", "EEE"]);
+    }
+
+    #[test]
+    fn breadth_counts_symbols_by_direction() {
+        let mut store = TickStore::new(8);
+        for (symbol, from, to) in [("AAA", 10.0, 11.0), ("BBB", 20.0, 18.0), ("CCC", 5.0, 5.0)] {
+            store.ingest(sample_tick(symbol, from, 1));
+            store.ingest(sample_tick(symbol, to, 2));
+        }
+        store.ingest(sample_tick("DDD", 7.0, 3));
+
+        assert_eq!(
+            store.breadth(),
+            Breadth {
+                advancing: 1,
+                declining: 1,
+                unchanged: 2,
+            }
+        );
+    }
+
+    fn screener_store() -> TickStore {
+        let mut store = TickStore::new(8);
+        for (symbol, from, to) in [
+            ("AAA", 10.0, 11.0),
+            ("BBB", 20.0, 18.0),
+            ("CCC", 50.0, 50.0),
+            ("DDD", 100.0, 125.0),
+        ] {
+            store.ingest(sample_tick(symbol, from, 1));
+            store.ingest(sample_tick(symbol, to, 2));
+        }
+        store
+    }
+
+    #[test]
+    fn filter_by_price_includes_boundaries() {
+        let store = screener_store();
+        let symbols = |ticks: Vec<&Tick>| {
+            ticks
+                .into_iter()
+                .map(|t| t.symbol.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            symbols(store.filter_by_price(11.0, 50.0)),
+            vec!["AAA", "BBB", "CCC"]
+        );
+        assert_eq!(symbols(store.filter_by_price(18.0, 18.0)), vec!["BBB"]);
+        assert!(store.filter_by_price(51.0, 124.0).is_empty());
+        assert!(store.filter_by_price(50.0, 10.0).is_empty());
+    }
+
+    #[test]
+    fn filter_by_change_includes_boundaries() {
+        let store = screener_store();
+
+        assert_eq!(store.filter_by_change(0.0, 10.0), vec!["AAA", "CCC"]);
+        assert_eq!(store.filter_by_change(-10.0, -10.0), vec!["BBB"]);
+        assert_eq!(store.filter_by_change(25.0, f64::INFINITY), vec!["DDD"]);
+        assert!(store.filter_by_change(-5.0, -1.0).is_empty());
+    }
 }
@@ -1,21 +1,18 @@
 use std::{rc::Rc, time::Duration};
 
-use futures::StreamExt;
+use futures::{
+    StreamExt,
+    channel::mpsc::{self, UnboundedSender},
+    future::{Either, select},
+};
 use gloo_net::websocket::{Message, futures::WebSocket};
 use gloo_timers::future::sleep;
+use market_core::{GatewayMessage, TickValidator, WireMessage, next_backoff};
 use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::spawn_local;
 
 use super::types::Tick;
 
-#[derive(serde::Deserialize)]
-struct TickBatchPayload {
-    #[allow(dead_code)]
-    version: u32,
-    #[serde(default)]
-    ticks: Vec<Tick>,
-}
-
 /// Errors that can surface when managing the websocket connection.
 #[derive(Debug)]
 pub enum TickStreamError {
@@ -36,11 +33,76 @@ pub enum StreamStatus {
 
 pub type StatusCallback = Rc<dyn Fn(StreamStatus)>;
 
+/// Consecutive failed connection attempts once `status` has been reported.
+pub fn failed_attempts_after(failed: u32, status: StreamStatus) -> u32 {
+    match status {
+        StreamStatus::Connected => 0,
+        StreamStatus::Failed => failed.saturating_add(1),
+        _ => failed,
+    }
+}
+
+/// Whether `failed` consecutive failures warrant the persistent "unable to reach
+/// market data" banner. A `threshold` of 0 never shows it.
+pub fn show_unreachable_banner(failed: u32, threshold: u32) -> bool {
+    threshold > 0 && failed >= threshold
+}
+
+/// First reconnect delay, doubled after each failed attempt.
+pub const RECONNECT_BASE: Duration = Duration::from_millis(500);
+
+/// Longest delay between reconnect attempts.
+pub const RECONNECT_MAX: Duration = Duration::from_secs(10);
+
+/// How long a connection must stay up, after delivering ticks, before the
+/// reconnect backoff starts over from its shortest delay.
+pub const BACKOFF_RESET_AFTER: Duration = Duration::from_secs(5);
+
+/// Whether a connection that lasted `uptime` earns a backoff reset. Only one
+/// that delivered at least one batch and stayed up for `stable_after` counts,
+/// so a server that accepts and immediately drops connections keeps backing off.
+pub fn should_reset_backoff(
+    uptime: Duration,
+    received_batch: bool,
+    stable_after: Duration,
+) -> bool {
+    received_batch && uptime >= stable_after
+}
+
+/// Receives every frame's raw text before it is parsed.
+pub type RawFrameCallback = Rc<dyn Fn(&str)>;
+
+/// Manual retry trigger for a connection started by [`connect_with_retry`].
+#[derive(Clone)]
+pub struct RetryHandle(UnboundedSender<()>);
+
+impl RetryHandle {
+    /// Skip the pending backoff, reset the attempt counter, and reconnect now.
+    /// Has no effect while the connection is open.
+    pub fn retry_now(&self) {
+        let _ = self.0.unbounded_send(());
+    }
+}
+
 /// Connect to the tick stream with automatic reconnection and status updates.
-pub fn connect_with_retry(url: String, on_tick: TickCallback, on_status: StatusCallback) {
+///
+/// The backoff only starts over once a connection has been stable for
+/// `stable_after` (see [`should_reset_backoff`]). The returned handle lets the
+/// UI cut a reconnect backoff short. With `accept_late_ticks`, ticks older
+/// than their symbol's previous one are delivered rather than dropped, for a
+/// store whose out-of-order policy places them.
+pub fn connect_with_retry(
+    url: String,
+    on_tick: TickCallback,
+    on_status: StatusCallback,
+    on_raw: Option<RawFrameCallback>,
+    stable_after: Duration,
+    accept_late_ticks: bool,
+) -> RetryHandle {
+    let (retry_tx, mut retry_rx) = mpsc::unbounded::<()>();
     spawn_local(async move {
         let mut attempt: u32 = 0;
-        let mut backoff_ms: u64 = 500;
+        let mut delay = RECONNECT_BASE;
         let mut ever_connected = false;
 
         loop {
@@ -52,30 +114,57 @@ pub fn connect_with_retry(url: String, on_tick: TickCallback, on_status: StatusC
 
             match WebSocket::open(&url) {
                 Ok(ws) => {
-                    attempt = 0;
-                    backoff_ms = 500;
-
+                    let opened_ms = js_sys::Date::now();
                     let (_, mut read) = ws.split();
+                    let mut validator = TickValidator::default();
+                    if accept_late_ticks {
+                        validator = validator.allowing_out_of_order();
+                    }
                     let mut announced_connected = false;
+                    let mut received_batch = false;
 
                     while let Some(message) = read.next().await {
                         match message {
                             Ok(Message::Bytes(bytes)) => {
-                                if let Err(err) = dispatch_message(&bytes, &on_tick) {
-                                    log::warn!("dropping malformed tick: {err:?}");
-                                } else if !announced_connected {
-                                    announced_connected = true;
-                                    ever_connected = true;
-                                    on_status(StreamStatus::Connected);
+                                if let Some(on_raw) = &on_raw {
+                                    on_raw(&String::from_utf8_lossy(&bytes));
+                                }
+                                match dispatch_message(
+                                    &bytes,
+                                    &on_tick,
+                                    &mut validator,
+                                    js_sys::Date::now() as u64,
+                                ) {
+                                    Err(err) => log::warn!("dropping malformed tick: {err:?}"),
+                                    Ok(delivered) => {
+                                        received_batch |= delivered;
+                                        if !announced_connected {
+                                            announced_connected = true;
+                                            ever_connected = true;
+                                            on_status(StreamStatus::Connected);
+                                        }
+                                    }
                                 }
                             }
                             Ok(Message::Text(text)) => {
-                                if let Err(err) = dispatch_message(text.as_bytes(), &on_tick) {
-                                    log::warn!("dropping malformed tick: {err:?}");
-                                } else if !announced_connected {
-                                    announced_connected = true;
-                                    ever_connected = true;
-                                    on_status(StreamStatus::Connected);
+                                if let Some(on_raw) = &on_raw {
+                                    on_raw(&text);
+                                }
+                                match dispatch_message(
+                                    text.as_bytes(),
+                                    &on_tick,
+                                    &mut validator,
+                                    js_sys::Date::now() as u64,
+                                ) {
+                                    Err(err) => log::warn!("dropping malformed tick: {err:?}"),
+                                    Ok(delivered) => {
+                                        received_batch |= delivered;
+                                        if !announced_connected {
+                                            announced_connected = true;
+                                            ever_connected = true;
+                                            on_status(StreamStatus::Connected);
+                                        }
+                                    }
                                 }
                             }
                             Err(err) => {
@@ -85,6 +174,12 @@ pub fn connect_with_retry(url: String, on_tick: TickCallback, on_status: StatusC
                         }
                     }
 
+                    let uptime =
+                        Duration::from_millis((js_sys::Date::now() - opened_ms).max(0.0) as u64);
+                    if should_reset_backoff(uptime, received_batch, stable_after) {
+                        attempt = 0;
+                        delay = RECONNECT_BASE;
+                    }
                     on_status(StreamStatus::Failed);
                 }
                 Err(err) => {
@@ -93,21 +188,78 @@ pub fn connect_with_retry(url: String, on_tick: TickCallback, on_status: StatusC
                 }
             }
 
+            // drop retries requested while the connection was still open
+            while let Ok(Some(())) = retry_rx.try_next() {}
+
             attempt = attempt.saturating_add(1);
-            sleep(Duration::from_millis(backoff_ms)).await;
-            backoff_ms = (backoff_ms * 2).min(10_000);
+            let backoff = sleep(delay);
+            match select(backoff, retry_rx.next()).await {
+                Either::Right((Some(()), _)) => {
+                    attempt = 0;
+                    delay = RECONNECT_BASE;
+                    continue;
+                }
+                // every handle was dropped; keep the regular schedule
+                Either::Right((None, backoff)) => backoff.await,
+                Either::Left(_) => {}
+            }
+            delay = next_backoff(delay, RECONNECT_BASE, RECONNECT_MAX);
         }
     });
+    RetryHandle(retry_tx)
 }
 
-fn dispatch_message(bytes: &[u8], on_tick: &TickCallback) -> Result<(), TickStreamError> {
-    let payload: TickBatchPayload = serde_json::from_slice(bytes)
+/// Parse one frame and hand its valid ticks to `on_tick`, returning whether any
+/// were delivered. `validator` tracks the connection's ticks, received at `now_ms`.
+fn dispatch_message(
+    bytes: &[u8],
+    on_tick: &TickCallback,
+    validator: &mut TickValidator,
+    now_ms: u64,
+) -> Result<bool, TickStreamError> {
+    let message: WireMessage = serde_json::from_slice(bytes)
         .map_err(|err| TickStreamError::Deserialize(err.to_string()))?;
 
-    if !payload.ticks.is_empty() {
-        on_tick(payload.ticks);
+    let ticks = match GatewayMessage::from(message) {
+        GatewayMessage::Batch(batch) | GatewayMessage::Delta(batch) => batch.ticks,
+        GatewayMessage::Meta(meta) => {
+            log::debug!(
+                "received gateway meta frame for {} symbols",
+                meta.symbols.len()
+            );
+            return Ok(false);
+        }
+        GatewayMessage::Heartbeat(heartbeat) => {
+            log::debug!(
+                "gateway heartbeat: up {:.0}s, regime {:?}",
+                heartbeat.uptime_secs,
+                heartbeat.regime
+            );
+            return Ok(false);
+        }
+        GatewayMessage::Notice(notice) => {
+            log::warn!("gateway notice ({:?}): {}", notice.code, notice.message);
+            return Ok(false);
+        }
+        GatewayMessage::Debug(_) | GatewayMessage::Trades(_) => return Ok(false),
+    };
+
+    let ticks: Vec<Tick> = ticks
+        .into_iter()
+        .filter(|tick| match validator.check(tick, now_ms) {
+            Ok(()) => true,
+            Err(err) => {
+                log::warn!("dropping malformed tick {:?}: {err}", tick.symbol.as_str());
+                false
+            }
+        })
+        .collect();
+
+    if ticks.is_empty() {
+        return Ok(false);
     }
-    Ok(())
+    on_tick(ticks);
+    Ok(true)
 }
 
 impl From<TickStreamError> for JsValue {
@@ -124,22 +276,230 @@ impl From<TickStreamError> for JsValue {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ticks::store::{OutOfOrder, TickStore};
     use std::cell::RefCell;
 
+    const NOW_MS: u64 = 1_716_400_010_000;
+
     #[test]
     fn dispatch_message_parses_tick_batches() {
         let captured: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
         let sink = captured.clone();
         let callback: TickCallback = Rc::new(move |ticks: Vec<Tick>| {
             sink.borrow_mut()
-                .extend(ticks.into_iter().map(|tick| tick.symbol));
+                .extend(ticks.into_iter().map(|tick| tick.symbol.into_string()));
         });
 
         let payload = r#"{"version":1,"ticks":[{"symbol":"AAA","price":10.0,"timestamp_ms":1,"region":"north_america","sector":"technology"}]}"#;
-        dispatch_message(payload.as_bytes(), &callback).expect("valid payload");
+        dispatch_message(
+            payload.as_bytes(),
+            &callback,
+            &mut TickValidator::default(),
+            NOW_MS,
+        )
+        .expect("valid payload");
 
         let captured = captured.borrow();
         assert_eq!(captured.len(), 1);
         assert_eq!(captured[0], "AAA");
     }
+
+    #[test]
+    fn dispatch_message_accepts_backend_batch_sample() {
+        let captured: Rc<RefCell<Vec<Tick>>> = Rc::new(RefCell::new(Vec::new()));
+        let sink = captured.clone();
+        let callback: TickCallback = Rc::new(move |ticks: Vec<Tick>| {
+            sink.borrow_mut().extend(ticks);
+        });
+
+        let payload = include_str!("../../../schemas/tick_batch.sample.json");
+        dispatch_message(
+            payload.as_bytes(),
+            &callback,
+            &mut TickValidator::default(),
+            NOW_MS,
+        )
+        .expect("valid sample batch");
+        let compact = include_str!("../../../schemas/tick_batch_compact.sample.json");
+        dispatch_message(
+            compact.as_bytes(),
+            &callback,
+            &mut TickValidator::default(),
+            NOW_MS,
+        )
+        .expect("valid compact sample batch");
+
+        let captured = captured.borrow();
+        assert_eq!(captured.len(), 3);
+        assert_eq!(captured[0].timestamp_ms, 1_716_400_005_123u64);
+        assert_eq!(captured[2].symbol, "NA_TECH007");
+    }
+
+    #[test]
+    fn dispatch_message_ignores_meta_frames() {
+        let calls = Rc::new(RefCell::new(0usize));
+        let sink = calls.clone();
+        let callback: TickCallback = Rc::new(move |_ticks: Vec<Tick>| {
+            *sink.borrow_mut() += 1;
+        });
+
+        let payload = r#"{"type":"meta","version":1,"throttle_ms":1000,"symbols":[],"regions":[],"sectors":[]}"#;
+        let delivered = dispatch_message(
+            payload.as_bytes(),
+            &callback,
+            &mut TickValidator::default(),
+            NOW_MS,
+        )
+        .expect("valid meta frame");
+        assert!(!delivered, "meta frames carry no batch");
+
+        assert_eq!(*calls.borrow(), 0);
+    }
+
+    #[test]
+    fn dispatch_message_routes_by_type() {
+        let captured: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+        let sink = captured.clone();
+        let callback: TickCallback = Rc::new(move |ticks: Vec<Tick>| {
+            sink.borrow_mut()
+                .extend(ticks.into_iter().map(|tick| tick.symbol.into_string()));
+        });
+
+        let frames = [
+            include_str!("../../../schemas/gateway_heartbeat.sample.json"),
+            include_str!("../../../schemas/gateway_debug.sample.json"),
+            include_str!("../../../schemas/gateway_notice.sample.json"),
+            r#"{"type":"delta","version":1,"ticks":[{"symbol":"DDD","price":4.0,"timestamp_ms":3,"region":"europe","sector":"energy"}]}"#,
+        ];
+        for frame in frames {
+            dispatch_message(
+                frame.as_bytes(),
+                &callback,
+                &mut TickValidator::default(),
+                NOW_MS,
+            )
+            .expect("known frame");
+        }
+
+        assert_eq!(*captured.borrow(), vec!["DDD".to_string()]);
+        assert!(
+            dispatch_message(
+                br#"{"type":"quote"}"#,
+                &callback,
+                &mut TickValidator::default(),
+                NOW_MS
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn unreachable_banner_waits_for_the_threshold() {
+        let failed = [
+            StreamStatus::Connecting,
+            StreamStatus::Failed,
+            StreamStatus::Connecting,
+            StreamStatus::Failed,
+            StreamStatus::Reconnecting { attempt: 2 },
+            StreamStatus::Failed,
+        ]
+        .into_iter()
+        .fold(0, failed_attempts_after);
+        assert_eq!(failed, 3);
+        assert!(!show_unreachable_banner(failed, 4));
+        assert!(show_unreachable_banner(failed, 3));
+        assert!(!show_unreachable_banner(failed, 0), "0 disables the banner");
+        assert_eq!(failed_attempts_after(failed, StreamStatus::Connected), 0);
+    }
+
+    #[test]
+    fn backoff_resets_only_after_a_stable_connection() {
+        let stable = Duration::from_secs(5);
+        assert!(should_reset_backoff(Duration::from_secs(5), true, stable));
+        assert!(should_reset_backoff(Duration::from_secs(60), true, stable));
+        // a flapping server drops the connection right after the first batch
+        assert!(!should_reset_backoff(
+            Duration::from_millis(200),
+            true,
+            stable
+        ));
+        // up for a while, but never delivered any ticks
+        assert!(!should_reset_backoff(
+            Duration::from_secs(60),
+            false,
+            stable
+        ));
+        assert!(should_reset_backoff(Duration::ZERO, true, Duration::ZERO));
+    }
+
+    #[test]
+    fn dispatch_message_skips_invalid_ticks() {
+        let captured: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+        let sink = captured.clone();
+        let callback: TickCallback = Rc::new(move |ticks: Vec<Tick>| {
+            sink.borrow_mut()
+                .extend(ticks.into_iter().map(|tick| tick.symbol.into_string()));
+        });
+
+        // symbols are checked while decoding, so a bad one rejects the frame
+        let payload = r#"{"version":1,"ticks":[{"symbol":"","price":10.0,"timestamp_ms":1,"region":"north_america","sector":"technology"}]}"#;
+        assert!(matches!(
+            dispatch_message(
+                payload.as_bytes(),
+                &callback,
+                &mut TickValidator::default(),
+                NOW_MS
+            ),
+            Err(TickStreamError::Deserialize(_))
+        ));
+
+        let payload = r#"{"version":1,"ticks":[{"symbol":"BBB","price":-1.0,"timestamp_ms":1,"region":"europe","sector":"energy"},{"symbol":"CCC","price":5.0,"timestamp_ms":2,"region":"europe","sector":"energy"}]}"#;
+        let mut validator = TickValidator::default();
+        dispatch_message(payload.as_bytes(), &callback, &mut validator, NOW_MS)
+            .expect("valid payload");
+
+        // stamped far past the clock, then older than CCC's last tick
+        let payload = r#"{"version":1,"ticks":[{"symbol":"DDD","price":5.0,"timestamp_ms":1716500000000,"region":"europe","sector":"energy"},{"symbol":"CCC","price":5.0,"timestamp_ms":1,"region":"europe","sector":"energy"}]}"#;
+        let delivered = dispatch_message(payload.as_bytes(), &callback, &mut validator, NOW_MS)
+            .expect("valid payload");
+        assert!(!delivered);
+
+        assert_eq!(*captured.borrow(), vec!["CCC".to_string()]);
+    }
+
+    #[test]
+    fn late_ticks_reach_an_ordered_store_through_a_permissive_validator() {
+        let store = Rc::new(RefCell::new(
+            TickStore::new(8)
+                .with_min_history_interval(10)
+                .with_out_of_order(OutOfOrder::Insert),
+        ));
+        let sink = store.clone();
+        let callback: TickCallback =
+            Rc::new(move |ticks: Vec<Tick>| sink.borrow_mut().ingest_batch(ticks));
+        let frame = |ts: u64| {
+            format!(
+                r#"{{"version":1,"ticks":[{{"symbol":"AAA","price":5.0,"timestamp_ms":{ts},"region":"europe","sector":"energy"}}]}}"#
+            )
+        };
+
+        let mut validator = TickValidator::default();
+        if store.borrow().orders_late_ticks() {
+            validator = validator.allowing_out_of_order();
+        }
+        for ts in [100, 120, 105] {
+            dispatch_message(frame(ts).as_bytes(), &callback, &mut validator, NOW_MS)
+                .expect("valid payload");
+        }
+
+        let store = store.borrow();
+        let stamps: Vec<u64> = store
+            .history_for("AAA")
+            .unwrap()
+            .iter()
+            .map(|point| point.timestamp_ms)
+            .collect();
+        assert_eq!(stamps, vec![100, 105, 120]);
+        assert_eq!(store.latest()["AAA"].timestamp_ms, 120);
+    }
 }
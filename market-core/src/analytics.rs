@@ -0,0 +1,277 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::symbol::Symbol;
+use crate::tick::Tick;
+
+/// `(symbol, percent change)` pairs, best first for advancers and worst first for decliners.
+pub type Movers = Vec<(String, f64)>;
+
+/// Split per-symbol changes into the top `count` advancers and decliners.
+///
+/// Ties (e.g. every symbol flat at startup) fall back to symbol order so output is stable.
+pub fn rank_movers<I>(changes: I, count: usize) -> (Movers, Movers)
+where
+    I: IntoIterator<Item = (String, f64)>,
+{
+    if count == 0 {
+        return (Vec::new(), Vec::new());
+    }
+
+    let mut changes: Movers = changes.into_iter().collect();
+    changes.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.0.cmp(&b.0))
+    });
+
+    let advancers = changes
+        .iter()
+        .filter(|(_, change)| *change > 0.0)
+        .take(count)
+        .cloned()
+        .collect();
+
+    changes.sort_by(|a, b| {
+        a.1.partial_cmp(&b.1)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.0.cmp(&b.0))
+    });
+
+    let decliners = changes
+        .into_iter()
+        .filter(|(_, change)| *change < 0.0)
+        .take(count)
+        .collect();
+
+    (advancers, decliners)
+}
+
+/// Count of symbols moving up, down, or not at all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Breadth {
+    pub advancing: usize,
+    pub declining: usize,
+    pub unchanged: usize,
+}
+
+/// Market breadth over a set of percentage changes.
+pub fn breadth<I>(changes: I) -> Breadth
+where
+    I: IntoIterator<Item = f64>,
+{
+    changes
+        .into_iter()
+        .fold(Breadth::default(), |mut breadth, change| {
+            match change.partial_cmp(&0.0) {
+                Some(Ordering::Greater) => breadth.advancing += 1,
+                Some(Ordering::Less) => breadth.declining += 1,
+                _ => breadth.unchanged += 1,
+            }
+            breadth
+        })
+}
+
+/// Mean of `(value, weight)` pairs, or `None` when there are no values.
+///
+/// Falls back to equal weighting when any weight is missing, zero, negative,
+/// or not finite, so a partially configured weight map never skews the result.
+pub fn weighted_average<I>(entries: I) -> Option<f64>
+where
+    I: IntoIterator<Item = (f64, Option<f64>)>,
+{
+    let entries: Vec<(f64, Option<f64>)> = entries.into_iter().collect();
+    if entries.is_empty() {
+        return None;
+    }
+    let weights: Option<Vec<f64>> = entries
+        .iter()
+        .map(|(_, weight)| weight.filter(|weight| weight.is_finite() && *weight > 0.0))
+        .collect();
+    let weights = weights.unwrap_or_else(|| vec![1.0; entries.len()]);
+    let total: f64 = weights.iter().sum();
+    let weighted: f64 = entries
+        .iter()
+        .zip(&weights)
+        .map(|((value, _), weight)| value * weight)
+        .sum();
+    Some(weighted / total)
+}
+
+/// Synthetic index level per group (e.g. sector), averaging the latest price of
+/// each constituent with per-symbol `weights` via [`weighted_average`].
+pub fn group_indices<'a, K, I, F>(
+    ticks: I,
+    group: F,
+    weights: &HashMap<Symbol, f64>,
+) -> HashMap<K, f64>
+where
+    K: Eq + Hash,
+    I: IntoIterator<Item = &'a Tick>,
+    F: Fn(&Tick) -> K,
+{
+    let mut members: HashMap<K, Vec<(f64, Option<f64>)>> = HashMap::new();
+    for tick in ticks {
+        members
+            .entry(group(tick))
+            .or_default()
+            .push((tick.price, weights.get(&tick.symbol).copied()));
+    }
+    members
+        .into_iter()
+        .filter_map(|(key, entries)| Some((key, weighted_average(entries)?)))
+        .collect()
+}
+
+/// Open/high/low/close summary of a price path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ohlc {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl Ohlc {
+    pub fn new(price: f64) -> Self {
+        Self {
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+        }
+    }
+
+    pub fn update(&mut self, price: f64) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+    }
+
+    /// Summarise prices in order, or `None` when there are none.
+    pub fn from_prices<I>(prices: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        let mut prices = prices.into_iter();
+        let mut ohlc = Self::new(prices.next()?);
+        for price in prices {
+            ohlc.update(price);
+        }
+        Some(ohlc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(pairs: &[(&str, f64)]) -> Movers {
+        pairs
+            .iter()
+            .map(|(symbol, change)| (symbol.to_string(), *change))
+            .collect()
+    }
+
+    #[test]
+    fn movers_are_ranked_and_capped() {
+        let (advancers, decliners) = rank_movers(
+            changes(&[("A", 5.0), ("B", -2.0), ("C", 1.0), ("D", -7.0), ("E", 0.0)]),
+            1,
+        );
+        assert_eq!(advancers, changes(&[("A", 5.0)]));
+        assert_eq!(decliners, changes(&[("D", -7.0)]));
+        assert_eq!(rank_movers(changes(&[("A", 1.0)]), 0), (vec![], vec![]));
+    }
+
+    #[test]
+    fn movers_break_ties_by_symbol() {
+        let (advancers, decliners) = rank_movers(
+            changes(&[("C", 1.0), ("A", 1.0), ("B", -1.0), ("D", -1.0)]),
+            5,
+        );
+        assert_eq!(advancers, changes(&[("A", 1.0), ("C", 1.0)]));
+        assert_eq!(decliners, changes(&[("B", -1.0), ("D", -1.0)]));
+    }
+
+    #[test]
+    fn breadth_counts_direction() {
+        assert_eq!(
+            breadth([1.0, -0.5, 0.0, 2.0, f64::NAN]),
+            Breadth {
+                advancing: 2,
+                declining: 1,
+                unchanged: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn ohlc_tracks_range_and_close() {
+        let ohlc = Ohlc::from_prices([10.0, 12.0, 9.0, 11.0]).expect("prices");
+        assert_eq!(
+            ohlc,
+            Ohlc {
+                open: 10.0,
+                high: 12.0,
+                low: 9.0,
+                close: 11.0,
+            }
+        );
+        assert_eq!(Ohlc::from_prices(std::iter::empty()), None);
+    }
+
+    #[test]
+    fn weighted_average_uses_weights_when_all_are_valid() {
+        assert_eq!(weighted_average([]), None);
+        assert_eq!(
+            weighted_average([(10.0, Some(3.0)), (20.0, Some(1.0))]),
+            Some(12.5)
+        );
+        assert_eq!(weighted_average([(10.0, None), (20.0, None)]), Some(15.0));
+    }
+
+    #[test]
+    fn weighted_average_falls_back_to_equal_weight() {
+        for weight in [None, Some(0.0), Some(-1.0), Some(f64::NAN)] {
+            assert_eq!(
+                weighted_average([(10.0, Some(3.0)), (20.0, weight)]),
+                Some(15.0),
+                "weight {weight:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn group_indices_compare_equal_and_weighted() {
+        use crate::model::{Region, Sector};
+
+        let tick = |symbol: &str, price: f64, sector: Sector| Tick {
+            symbol: Symbol::new(symbol).unwrap(),
+            price,
+            timestamp_ms: 1,
+            region: Region::Europe,
+            sector,
+            prev_close: None,
+        };
+        let ticks = [
+            tick("BIG", 200.0, Sector::Technology),
+            tick("SMALL", 2.0, Sector::Technology),
+            tick("OIL", 50.0, Sector::Energy),
+        ];
+
+        let equal = group_indices(&ticks, |tick| tick.sector, &HashMap::new());
+        assert_eq!(equal[&Sector::Technology], 101.0);
+        assert_eq!(equal[&Sector::Energy], 50.0);
+
+        let weights = HashMap::from([
+            (Symbol::new("BIG").unwrap(), 99.0),
+            (Symbol::new("SMALL").unwrap(), 1.0),
+        ]);
+        let weighted = group_indices(&ticks, |tick| tick.sector, &weights);
+        assert_eq!(weighted[&Sector::Technology], 198.02);
+        // OIL has no weight, but it is alone in its sector so equal weight applies
+        assert_eq!(weighted[&Sector::Energy], 50.0);
+    }
+}
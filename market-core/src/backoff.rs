@@ -0,0 +1,41 @@
+//! Reconnect backoff shared by the CLI socket clients and the dashboard's
+//! websocket, so every client retries on the same schedule.
+
+use std::time::Duration;
+
+/// Delay to wait after `current`: doubled, kept within `base..=max`.
+///
+/// Callers validate that `base` does not exceed `max`.
+pub fn next_backoff(current: Duration, base: Duration, max: Duration) -> Duration {
+    current.saturating_mul(2).clamp(base, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_until_capped() {
+        let (base, max) = (Duration::from_millis(100), Duration::from_millis(750));
+        let mut delay = base;
+        let mut schedule = vec![delay.as_millis()];
+        for _ in 0..5 {
+            delay = next_backoff(delay, base, max);
+            schedule.push(delay.as_millis());
+        }
+        assert_eq!(schedule, vec![100, 200, 400, 750, 750, 750]);
+    }
+
+    #[test]
+    fn backoff_with_equal_base_and_max_is_constant() {
+        let delay = Duration::from_millis(300);
+        assert_eq!(next_backoff(delay, delay, delay), delay);
+    }
+
+    #[test]
+    fn backoff_never_drops_below_base() {
+        let (base, max) = (Duration::from_millis(500), Duration::from_secs(10));
+        assert_eq!(next_backoff(Duration::ZERO, base, max), base);
+        assert_eq!(next_backoff(Duration::MAX, base, max), max);
+    }
+}
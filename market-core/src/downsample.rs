@@ -0,0 +1,94 @@
+/// Reduce `points` to at most `threshold` while keeping the visual shape, using
+/// largest-triangle-three-buckets (LTTB).
+///
+/// The first and last points are always kept. `coords` maps a point to its
+/// `(x, y)`; points must already be ordered by `x`. A `threshold` of zero, or
+/// one at least as large as the input, returns every point unchanged.
+pub fn lttb<T, F>(points: &[T], threshold: usize, coords: F) -> Vec<T>
+where
+    T: Clone,
+    F: Fn(&T) -> (f64, f64),
+{
+    let len = points.len();
+    if threshold == 0 || threshold >= len {
+        return points.to_vec();
+    }
+    if threshold < 3 {
+        return vec![points[0].clone(), points[len - 1].clone()];
+    }
+
+    // the endpoints sit in their own buckets; the rest share threshold - 2 buckets
+    let bucket_size = (len - 2) as f64 / (threshold - 2) as f64;
+    let bucket_start = |bucket: usize| ((bucket as f64 * bucket_size) as usize + 1).min(len - 1);
+
+    let mut sampled = Vec::with_capacity(threshold);
+    sampled.push(points[0].clone());
+    let mut anchor = 0;
+    for bucket in 0..threshold - 2 {
+        // average of the next bucket, or the last point for the final bucket
+        let next = &points
+            [bucket_start(bucket + 1)..bucket_start(bucket + 2).max(bucket_start(bucket + 1) + 1)];
+        let (sum_x, sum_y) = next
+            .iter()
+            .map(&coords)
+            .fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+        let (avg_x, avg_y) = (sum_x / next.len() as f64, sum_y / next.len() as f64);
+
+        let (anchor_x, anchor_y) = coords(&points[anchor]);
+        let range = bucket_start(bucket)..bucket_start(bucket + 1);
+        let mut chosen = range.start;
+        let mut max_area = f64::NEG_INFINITY;
+        for idx in range {
+            let (x, y) = coords(&points[idx]);
+            let area =
+                ((anchor_x - avg_x) * (y - anchor_y) - (anchor_x - x) * (avg_y - anchor_y)).abs();
+            if area > max_area {
+                max_area = area;
+                chosen = idx;
+            }
+        }
+        sampled.push(points[chosen].clone());
+        anchor = chosen;
+    }
+    sampled.push(points[len - 1].clone());
+    sampled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xy(point: &(f64, f64)) -> (f64, f64) {
+        *point
+    }
+
+    #[test]
+    fn keeps_endpoints_and_caps_the_count() {
+        let points: Vec<(f64, f64)> = (0..1_000)
+            .map(|i| (i as f64, (i as f64 / 25.0).sin()))
+            .collect();
+        let sampled = lttb(&points, 100, xy);
+
+        assert_eq!(sampled.len(), 100);
+        assert_eq!(sampled.first(), points.first());
+        assert_eq!(sampled.last(), points.last());
+        assert!(sampled.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn preserves_an_isolated_spike() {
+        let mut points: Vec<(f64, f64)> = (0..500).map(|i| (i as f64, 1.0)).collect();
+        points[233].1 = 50.0;
+        let sampled = lttb(&points, 20, xy);
+        assert!(sampled.contains(&(233.0, 50.0)));
+    }
+
+    #[test]
+    fn small_inputs_pass_through() {
+        let points = vec![(0.0, 1.0), (1.0, 2.0), (2.0, 0.5)];
+        assert_eq!(lttb(&points, 3, xy), points);
+        assert_eq!(lttb(&points, 0, xy), points);
+        assert_eq!(lttb(&points, 2, xy), vec![(0.0, 1.0), (2.0, 0.5)]);
+        assert!(lttb(&[] as &[(f64, f64)], 10, xy).is_empty());
+    }
+}
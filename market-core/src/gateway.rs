@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Equity, Regime, Region, Sector};
+use crate::tick::Tick;
+use crate::trade::Trade;
+
+/// Every frame the websocket gateway sends, tagged by `type` so clients can route them.
+///
+/// `T` is the per-symbol entry carried by batches; the simulator sends candles
+/// that flatten a [`Tick`] (or its compact form) next to open/high/low, which
+/// clients may read back as plain ticks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GatewayMessage<T = Tick> {
+    /// One-time handshake describing the served universe.
+    Meta(MetaMessage),
+    /// Latest entry for every symbol that ticked during the throttle window.
+    Batch(BatchMessage<T>),
+    /// Liveness and market context, sent on a fixed cadence.
+    Heartbeat(HeartbeatMessage),
+    /// Entries for symbols whose price changed since the previous frame.
+    /// Reserved for incremental streams; the simulator does not send it yet.
+    Delta(BatchMessage<T>),
+    /// Server-side batching stats for clients connecting with `?debug=1`.
+    Debug(DebugMessage),
+    /// Time-and-sales prints, sent on the `/ws/trades` route.
+    Trades(TradesMessage),
+    /// Tells the client how the gateway adjusted its request, e.g. symbols
+    /// dropped from a `?symbols=` subscription.
+    Notice(NoticeMessage),
+}
+
+/// Any frame a client may receive, including the untagged `{"version","ticks"}`
+/// batches older gateways sent. Convert into a [`GatewayMessage`] to dispatch.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum WireMessage<T = Tick> {
+    Tagged(GatewayMessage<T>),
+    Bare(BatchMessage<T>),
+}
+
+impl<T> From<WireMessage<T>> for GatewayMessage<T> {
+    fn from(message: WireMessage<T>) -> Self {
+        match message {
+            WireMessage::Tagged(message) => message,
+            WireMessage::Bare(batch) => GatewayMessage::Batch(batch),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetaMessage {
+    pub version: u32,
+    #[serde(default)]
+    pub build_version: String,
+    pub throttle_ms: u64,
+    #[serde(default)]
+    pub compact_keys: bool,
+    pub symbols: Vec<Equity>,
+    pub regions: Vec<Region>,
+    pub sectors: Vec<Sector>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchMessage<T = Tick> {
+    pub version: u32,
+    pub ticks: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeartbeatMessage {
+    pub version: u32,
+    pub regime: Regime,
+    /// Regions inside their trading session, or `None` when no calendars are configured.
+    pub open_regions: Option<usize>,
+    pub uptime_secs: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DebugMessage {
+    pub batch_count: u64,
+    pub avg_symbols: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradesMessage {
+    pub version: u32,
+    pub trades: Vec<Trade>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoticeMessage {
+    pub code: NoticeCode,
+    pub message: String,
+    /// Symbols the notice refers to, as the client sent them.
+    #[serde(default)]
+    pub symbols: Vec<String>,
+}
+
+/// Why a [`NoticeMessage`] was sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoticeCode {
+    /// Requested symbols that are not in the universe were ignored.
+    UnknownSymbols,
+    /// The subscription exceeded the per-client symbol limit and was cut short.
+    SubscriptionTruncated,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol::Symbol;
+
+    fn tick() -> Tick {
+        Tick {
+            symbol: Symbol::new("NATECH007").unwrap(),
+            price: 101.25,
+            timestamp_ms: 1_716_400_005_123,
+            region: Region::NorthAmerica,
+            sector: Sector::Technology,
+            prev_close: None,
+        }
+    }
+
+    fn round_trip(message: GatewayMessage, tag: &str) {
+        let encoded = serde_json::to_value(&message).unwrap();
+        assert_eq!(encoded["type"], tag);
+        let decoded: WireMessage = serde_json::from_value(encoded).unwrap();
+        assert_eq!(GatewayMessage::from(decoded), message);
+    }
+
+    #[test]
+    fn every_variant_round_trips_with_its_tag() {
+        round_trip(
+            GatewayMessage::Meta(MetaMessage {
+                version: 1,
+                build_version: "0.1.0".to_string(),
+                throttle_ms: 250,
+                compact_keys: false,
+                symbols: vec![Equity {
+                    symbol: Symbol::new("EUENG001").unwrap(),
+                    region: Region::Europe,
+                    sector: Sector::Energy,
+                }],
+                regions: Region::ALL.to_vec(),
+                sectors: Sector::ALL.to_vec(),
+            }),
+            "meta",
+        );
+        round_trip(
+            GatewayMessage::Batch(BatchMessage {
+                version: 1,
+                ticks: vec![tick()],
+            }),
+            "batch",
+        );
+        round_trip(
+            GatewayMessage::Heartbeat(HeartbeatMessage {
+                version: 1,
+                regime: Regime::Stressed,
+                open_regions: Some(3),
+                uptime_secs: 12.5,
+            }),
+            "heartbeat",
+        );
+        round_trip(
+            GatewayMessage::Delta(BatchMessage {
+                version: 1,
+                ticks: vec![tick()],
+            }),
+            "delta",
+        );
+        round_trip(
+            GatewayMessage::Debug(DebugMessage {
+                batch_count: 4,
+                avg_symbols: 2.5,
+            }),
+            "debug",
+        );
+        round_trip(
+            GatewayMessage::Trades(TradesMessage {
+                version: 1,
+                trades: vec![Trade {
+                    symbol: Symbol::new("NATECH007").unwrap(),
+                    price: 101.26,
+                    size: 200,
+                    timestamp_ms: 1_716_400_005_123,
+                    aggressor_side: crate::trade::Side::Sell,
+                    bid: 101.23,
+                    ask: 101.27,
+                }],
+            }),
+            "trades",
+        );
+        round_trip(
+            GatewayMessage::Notice(NoticeMessage {
+                code: NoticeCode::SubscriptionTruncated,
+                message: "subscription is limited to 1 symbols".to_string(),
+                symbols: vec!["EUENG001".to_string()],
+            }),
+            "notice",
+        );
+    }
+
+    #[test]
+    fn bare_batches_decode_as_batch_messages() {
+        let bare = serde_json::json!({ "version": 1, "ticks": [tick()] });
+        let decoded: WireMessage = serde_json::from_value(bare).unwrap();
+        assert_eq!(
+            GatewayMessage::from(decoded),
+            GatewayMessage::Batch(BatchMessage {
+                version: 1,
+                ticks: vec![tick()],
+            })
+        );
+    }
+
+    #[test]
+    fn candle_fields_are_ignored_when_reading_ticks() {
+        let mut candle = serde_json::to_value(tick()).unwrap();
+        candle["open"] = 100.0.into();
+        candle["high"] = 102.0.into();
+        candle["low"] = 99.5.into();
+        let frame = serde_json::json!({ "type": "batch", "version": 1, "ticks": [candle] });
+
+        let decoded: WireMessage = serde_json::from_value(frame).unwrap();
+        let GatewayMessage::Batch(batch) = decoded.into() else {
+            panic!("expected a batch");
+        };
+        assert_eq!(batch.ticks, vec![tick()]);
+    }
+}
@@ -0,0 +1,25 @@
+// Tick types and pure analytics shared by the simulator backend and the wasm
+// frontend, so both sides agree on the wire contract.
+
+pub mod analytics;
+pub mod backoff;
+pub mod downsample;
+pub mod gateway;
+pub mod model;
+pub mod returns;
+pub mod symbol;
+pub mod tick;
+pub mod trade;
+
+pub use analytics::{breadth, group_indices, rank_movers, weighted_average, Breadth, Movers, Ohlc};
+pub use backoff::next_backoff;
+pub use downsample::lttb;
+pub use gateway::{
+    BatchMessage, DebugMessage, GatewayMessage, HeartbeatMessage, MetaMessage, NoticeCode,
+    NoticeMessage, TradesMessage, WireMessage,
+};
+pub use model::{format_symbol, parse_symbol, Equity, Regime, Region, Sector};
+pub use returns::{log_return, pct_change, simple_return};
+pub use symbol::{Symbol, SymbolError};
+pub use tick::{CompactTick, Tick, TickError, TickValidator, MAX_CLOCK_SKEW_MS};
+pub use trade::{Side, Trade};
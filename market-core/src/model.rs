@@ -0,0 +1,275 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::symbol::{Symbol, SymbolError};
+
+/// Geographical region of the issuer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Region {
+    NorthAmerica,
+    SouthAmerica,
+    Europe,
+    AsiaPacific,
+    MiddleEastAfrica,
+}
+
+impl Region {
+    pub const ALL: [Region; 5] = [
+        Region::NorthAmerica,
+        Region::SouthAmerica,
+        Region::Europe,
+        Region::AsiaPacific,
+        Region::MiddleEastAfrica,
+    ];
+
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Region::NorthAmerica => "NA",
+            Region::SouthAmerica => "SA",
+            Region::Europe => "EU",
+            Region::AsiaPacific => "AP",
+            Region::MiddleEastAfrica => "ME",
+        }
+    }
+
+    pub fn from_prefix(prefix: &str) -> Option<Region> {
+        Region::ALL
+            .into_iter()
+            .find(|region| region.prefix() == prefix)
+    }
+
+    pub fn index(self) -> usize {
+        match self {
+            Region::NorthAmerica => 0,
+            Region::SouthAmerica => 1,
+            Region::Europe => 2,
+            Region::AsiaPacific => 3,
+            Region::MiddleEastAfrica => 4,
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Region::NorthAmerica => "North America",
+            Region::SouthAmerica => "South America",
+            Region::Europe => "Europe",
+            Region::AsiaPacific => "Asia Pacific",
+            Region::MiddleEastAfrica => "Middle East & Africa",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Activity sector classification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sector {
+    Technology,
+    Financials,
+    Industrials,
+    Healthcare,
+    ConsumerDiscretionary,
+    ConsumerStaples,
+    Energy,
+    Utilities,
+    Materials,
+    RealEstate,
+}
+
+impl Sector {
+    pub const ALL: [Sector; 10] = [
+        Sector::Technology,
+        Sector::Financials,
+        Sector::Industrials,
+        Sector::Healthcare,
+        Sector::ConsumerDiscretionary,
+        Sector::ConsumerStaples,
+        Sector::Energy,
+        Sector::Utilities,
+        Sector::Materials,
+        Sector::RealEstate,
+    ];
+
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Sector::Technology => "TECH",
+            Sector::Financials => "FIN",
+            Sector::Industrials => "IND",
+            Sector::Healthcare => "HLT",
+            Sector::ConsumerDiscretionary => "CND",
+            Sector::ConsumerStaples => "CNS",
+            Sector::Energy => "ENG",
+            Sector::Utilities => "UTL",
+            Sector::Materials => "MAT",
+            Sector::RealEstate => "REA",
+        }
+    }
+
+    pub fn from_prefix(prefix: &str) -> Option<Sector> {
+        Sector::ALL
+            .into_iter()
+            .find(|sector| sector.prefix() == prefix)
+    }
+
+    pub fn index(self) -> usize {
+        match self {
+            Sector::Technology => 0,
+            Sector::Financials => 1,
+            Sector::Industrials => 2,
+            Sector::Healthcare => 3,
+            Sector::ConsumerDiscretionary => 4,
+            Sector::ConsumerStaples => 5,
+            Sector::Energy => 6,
+            Sector::Utilities => 7,
+            Sector::Materials => 8,
+            Sector::RealEstate => 9,
+        }
+    }
+}
+
+impl fmt::Display for Sector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Sector::Technology => "Technology",
+            Sector::Financials => "Financials",
+            Sector::Industrials => "Industrials",
+            Sector::Healthcare => "Healthcare",
+            Sector::ConsumerDiscretionary => "Consumer Discretionary",
+            Sector::ConsumerStaples => "Consumer Staples",
+            Sector::Energy => "Energy",
+            Sector::Utilities => "Utilities",
+            Sector::Materials => "Materials",
+            Sector::RealEstate => "Real Estate",
+        };
+        f.write_str(label)
+    }
+}
+
+/// One listed instrument: its ticker plus the buckets it belongs to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Equity {
+    pub symbol: Symbol,
+    pub region: Region,
+    pub sector: Sector,
+}
+
+/// Market conditions that shape how wide quotes are around the mid price.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Regime {
+    #[default]
+    Calm,
+    Stressed,
+}
+
+/// Build a symbol such as `NATECH007`, or `NA_TECH_007` when a separator is given.
+///
+/// Fails when the separator is whitespace, or alphanumeric, which
+/// [`parse_symbol`] could not tell apart from the prefixes and number.
+pub fn format_symbol(
+    region: Region,
+    sector: Sector,
+    replica: u32,
+    separator: Option<char>,
+) -> Result<Symbol, SymbolError> {
+    if let Some(sep) = separator.filter(char::is_ascii_alphanumeric) {
+        return Err(SymbolError::AlphanumericSeparator(sep));
+    }
+    let symbol = match separator {
+        Some(sep) => format!(
+            "{}{sep}{}{sep}{replica:03}",
+            region.prefix(),
+            sector.prefix()
+        ),
+        None => format!("{}{}{replica:03}", region.prefix(), sector.prefix()),
+    };
+    Symbol::new(symbol)
+}
+
+/// Recover the region, sector, and replica from a symbol built by [`format_symbol`].
+///
+/// Accepts both the compact form and any single non-alphanumeric separator.
+pub fn parse_symbol(symbol: &str) -> Option<(Region, Sector, u32)> {
+    fn skip_separator(rest: &str) -> &str {
+        rest.strip_prefix(|ch: char| !ch.is_ascii_alphanumeric())
+            .unwrap_or(rest)
+    }
+
+    let region = Region::from_prefix(symbol.get(..2)?)?;
+    let rest = skip_separator(&symbol[2..]);
+    let sector = Sector::ALL
+        .into_iter()
+        .find(|sector| rest.starts_with(sector.prefix()))?;
+    let digits = skip_separator(&rest[sector.prefix().len()..]);
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    Some((region, sector, digits.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefixes_and_indices_are_unique() {
+        for (idx, region) in Region::ALL.into_iter().enumerate() {
+            assert_eq!(region.index(), idx);
+            assert_eq!(Region::from_prefix(region.prefix()), Some(region));
+        }
+        for (idx, sector) in Sector::ALL.into_iter().enumerate() {
+            assert_eq!(sector.index(), idx);
+            assert_eq!(Sector::from_prefix(sector.prefix()), Some(sector));
+        }
+        assert_eq!(Region::from_prefix("XX"), None);
+    }
+
+    #[test]
+    fn wire_names_are_snake_case() {
+        assert_eq!(
+            serde_json::to_string(&Region::MiddleEastAfrica).unwrap(),
+            "\"middle_east_africa\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Sector::ConsumerStaples).unwrap(),
+            "\"consumer_staples\""
+        );
+    }
+
+    #[test]
+    fn symbols_round_trip_for_every_bucket() {
+        for separator in [None, Some('_'), Some('-')] {
+            for region in Region::ALL {
+                for sector in Sector::ALL {
+                    for replica in [0, 7, 123] {
+                        let symbol = format_symbol(region, sector, replica, separator).unwrap();
+                        assert_eq!(
+                            parse_symbol(&symbol),
+                            Some((region, sector, replica)),
+                            "{symbol}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parse_symbol_rejects_foreign_symbols() {
+        for symbol in [
+            "",
+            "AAPL",
+            "XXTECH001",
+            "NATECH",
+            "NA_TECH_",
+            "NATECH01a",
+            "N",
+        ] {
+            assert_eq!(parse_symbol(symbol), None, "{symbol}");
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Return computations shared by every view of price change.
+//!
+//! All functions take a `first` and `last` price and return `None` unless both
+//! are finite, `first` is positive, and `last` is non-negative, so an empty or
+//! corrupt history never shows up as a spurious move.
+
+fn valid(first: f64, last: f64) -> bool {
+    first.is_finite() && last.is_finite() && first > 0.0 && last >= 0.0
+}
+
+/// Fractional return from `first` to `last` (`0.1` for a 10% rise).
+pub fn simple_return(first: f64, last: f64) -> Option<f64> {
+    valid(first, last).then(|| (last - first) / first)
+}
+
+/// Percentage change from `first` to `last` (`10.0` for a 10% rise).
+pub fn pct_change(first: f64, last: f64) -> Option<f64> {
+    simple_return(first, last).map(|ret| ret * 100.0)
+}
+
+/// Continuously compounded return `ln(last / first)`; also `None` when `last`
+/// is zero, where the log return is unbounded.
+pub fn log_return(first: f64, last: f64) -> Option<f64> {
+    (valid(first, last) && last > 0.0).then(|| (last / first).ln())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INVALID: [(f64, f64); 9] = [
+        (0.0, 110.0),
+        (-100.0, 110.0),
+        (100.0, -1.0),
+        (f64::NAN, 110.0),
+        (100.0, f64::NAN),
+        (f64::INFINITY, 110.0),
+        (100.0, f64::INFINITY),
+        (f64::NEG_INFINITY, 110.0),
+        (0.0, 0.0),
+    ];
+
+    #[test]
+    fn returns_agree_on_valid_prices() {
+        assert!((pct_change(100.0, 110.0).unwrap() - 10.0).abs() < 1e-9);
+        assert!((simple_return(100.0, 110.0).unwrap() - 0.1).abs() < 1e-12);
+        assert!((log_return(100.0, 110.0).unwrap() - 1.1f64.ln()).abs() < 1e-12);
+
+        assert_eq!(pct_change(50.0, 50.0), Some(0.0));
+        assert_eq!(simple_return(50.0, 50.0), Some(0.0));
+        assert_eq!(log_return(50.0, 50.0), Some(0.0));
+
+        assert!((pct_change(200.0, 150.0).unwrap() + 25.0).abs() < 1e-12);
+        assert!(log_return(200.0, 150.0).unwrap() < 0.0);
+    }
+
+    #[test]
+    fn a_price_falling_to_zero_has_no_log_return() {
+        assert_eq!(pct_change(100.0, 0.0), Some(-100.0));
+        assert_eq!(simple_return(100.0, 0.0), Some(-1.0));
+        assert_eq!(log_return(100.0, 0.0), None);
+    }
+
+    #[test]
+    fn zero_negative_and_non_finite_inputs_are_rejected() {
+        for (first, last) in INVALID {
+            assert_eq!(simple_return(first, last), None, "{first} -> {last}");
+            assert_eq!(pct_change(first, last), None, "{first} -> {last}");
+            assert_eq!(log_return(first, last), None, "{first} -> {last}");
+        }
+    }
+}
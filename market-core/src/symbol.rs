@@ -0,0 +1,192 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Ticker identifying one equity, e.g. `NATECH007`.
+///
+/// Serializes as the bare string so the wire format matches a plain `String`.
+/// Construction rejects empty symbols and symbols containing whitespace, and
+/// deserialization goes through the same check.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Symbol(String);
+
+/// Reasons a string is not a usable symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolError {
+    Empty,
+    ContainsWhitespace,
+    /// A separator passed to [`crate::format_symbol`] that is a letter or digit.
+    AlphanumericSeparator(char),
+}
+
+impl Symbol {
+    pub fn new(symbol: impl Into<String>) -> Result<Self, SymbolError> {
+        let symbol = symbol.into();
+        Self::check(&symbol)?;
+        Ok(Self(symbol))
+    }
+
+    /// Re-check the invariant [`Symbol::new`] and deserialization enforce.
+    pub fn validate(&self) -> Result<(), SymbolError> {
+        Self::check(&self.0)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    fn check(symbol: &str) -> Result<(), SymbolError> {
+        if symbol.is_empty() {
+            return Err(SymbolError::Empty);
+        }
+        if symbol.chars().any(char::is_whitespace) {
+            return Err(SymbolError::ContainsWhitespace);
+        }
+        Ok(())
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Symbol {
+    type Err = SymbolError;
+
+    fn from_str(symbol: &str) -> Result<Self, Self::Err> {
+        Self::new(symbol)
+    }
+}
+
+impl TryFrom<String> for Symbol {
+    type Error = SymbolError;
+
+    fn try_from(symbol: String) -> Result<Self, Self::Error> {
+        Self::new(symbol)
+    }
+}
+
+impl TryFrom<&str> for Symbol {
+    type Error = SymbolError;
+
+    fn try_from(symbol: &str) -> Result<Self, Self::Error> {
+        Self::new(symbol)
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(symbol: Symbol) -> Self {
+        symbol.0
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Symbol {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+impl fmt::Display for SymbolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SymbolError::Empty => f.write_str("symbol is empty"),
+            SymbolError::ContainsWhitespace => f.write_str("symbol contains whitespace"),
+            SymbolError::AlphanumericSeparator(sep) => {
+                write!(f, "symbol separator {sep:?} must not be a letter or digit")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SymbolError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_symbols_are_rejected() {
+        assert_eq!(Symbol::new(""), Err(SymbolError::Empty));
+        for symbol in [" ", "NA TECH", "NATECH007\n", "\tEU"] {
+            assert_eq!(
+                Symbol::new(symbol),
+                Err(SymbolError::ContainsWhitespace),
+                "{symbol:?}"
+            );
+        }
+        assert!("NA_TECH_007".parse::<Symbol>().is_ok());
+    }
+
+    #[test]
+    fn serde_matches_the_plain_string_form() {
+        let symbol = Symbol::new("NATECH007").unwrap();
+        let encoded = serde_json::to_string(&symbol).unwrap();
+        assert_eq!(encoded, serde_json::to_string("NATECH007").unwrap());
+        assert_eq!(serde_json::from_str::<Symbol>(&encoded).unwrap(), symbol);
+    }
+
+    #[test]
+    fn deserialization_rejects_invalid_symbols() {
+        let err = serde_json::from_str::<Symbol>(r#""""#).unwrap_err();
+        assert!(err.to_string().contains("symbol is empty"), "{err}");
+        let err = serde_json::from_str::<Symbol>(r#""NA TECH""#).unwrap_err();
+        assert!(err.to_string().contains("whitespace"), "{err}");
+    }
+
+    #[test]
+    fn derefs_and_borrows_as_str() {
+        let symbol = Symbol::new("EUFIN001").unwrap();
+        assert!(symbol.starts_with("EU"));
+        assert_eq!(symbol, "EUFIN001");
+        let prices = std::collections::HashMap::from([(symbol.clone(), 101.5)]);
+        assert_eq!(prices.get("EUFIN001"), Some(&101.5));
+    }
+}
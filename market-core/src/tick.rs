@@ -0,0 +1,411 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Region, Sector};
+use crate::symbol::{Symbol, SymbolError};
+
+/// Latest market data tick for one symbol, as published by the simulator.
+///
+/// Accepts both the full and the compact (`s`, `p`, `t`, `r`, `c`, `pc`) wire keys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tick {
+    #[serde(alias = "s")]
+    pub symbol: Symbol,
+    #[serde(alias = "p")]
+    pub price: f64,
+    #[serde(alias = "t")]
+    pub timestamp_ms: u64,
+    #[serde(alias = "r")]
+    pub region: Region,
+    #[serde(alias = "c")]
+    pub sector: Sector,
+    /// Close of the symbol's previous session (or its first price when no
+    /// calendars are configured); absent unless the simulator is asked to send it.
+    #[serde(alias = "pc", default, skip_serializing_if = "Option::is_none")]
+    pub prev_close: Option<f64>,
+}
+
+/// Bandwidth-friendly wire form of [`Tick`] using single-letter keys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompactTick {
+    #[serde(rename = "s")]
+    pub symbol: Symbol,
+    #[serde(rename = "p")]
+    pub price: f64,
+    #[serde(rename = "t")]
+    pub timestamp_ms: u64,
+    #[serde(rename = "r")]
+    pub region: Region,
+    #[serde(rename = "c")]
+    pub sector: Sector,
+    #[serde(rename = "pc", default, skip_serializing_if = "Option::is_none")]
+    pub prev_close: Option<f64>,
+}
+
+impl From<Tick> for CompactTick {
+    fn from(tick: Tick) -> Self {
+        Self {
+            symbol: tick.symbol,
+            price: tick.price,
+            timestamp_ms: tick.timestamp_ms,
+            region: tick.region,
+            sector: tick.sector,
+            prev_close: tick.prev_close,
+        }
+    }
+}
+
+impl From<CompactTick> for Tick {
+    fn from(tick: CompactTick) -> Self {
+        Self {
+            symbol: tick.symbol,
+            price: tick.price,
+            timestamp_ms: tick.timestamp_ms,
+            region: tick.region,
+            sector: tick.sector,
+            prev_close: tick.prev_close,
+        }
+    }
+}
+
+impl Tick {
+    /// Convenience accessor for sorting keys.
+    pub fn symbol_key(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Check that the tick carries a usable symbol, price, and timestamp.
+    pub fn validate(&self) -> Result<(), TickError> {
+        self.symbol.validate().map_err(TickError::InvalidSymbol)?;
+        if !self.price.is_finite() {
+            return Err(TickError::NonFinitePrice(self.price));
+        }
+        if self.price <= 0.0 {
+            return Err(TickError::NonPositivePrice(self.price));
+        }
+        if self.timestamp_ms == 0 {
+            return Err(TickError::MissingTimestamp);
+        }
+        Ok(())
+    }
+}
+
+/// How far past the receiver's clock a tick may be stamped, to absorb clock
+/// skew between the simulator and its clients.
+pub const MAX_CLOCK_SKEW_MS: u64 = 60_000;
+
+/// Validates a stream of ticks: each must pass [`Tick::validate`], must not be
+/// stamped more than [`MAX_CLOCK_SKEW_MS`] ahead of the receiver's clock, and
+/// must not be older than the previous accepted tick for its symbol unless
+/// [`TickValidator::allowing_out_of_order`] lets late ticks through.
+///
+/// Keep one per connection; a reconnect legitimately replays older ticks.
+#[derive(Debug, Default)]
+pub struct TickValidator {
+    last_ms: HashMap<Symbol, u64>,
+    allow_out_of_order: bool,
+}
+
+impl TickValidator {
+    /// Accept ticks older than their symbol's previous one, for consumers
+    /// that place or drop late ticks themselves.
+    pub fn allowing_out_of_order(mut self) -> Self {
+        self.allow_out_of_order = true;
+        self
+    }
+
+    /// Check `tick` as received at `now_ms` and remember it when accepted.
+    pub fn check(&mut self, tick: &Tick, now_ms: u64) -> Result<(), TickError> {
+        tick.validate()?;
+        if tick.timestamp_ms > now_ms.saturating_add(MAX_CLOCK_SKEW_MS) {
+            return Err(TickError::FutureTimestamp {
+                timestamp_ms: tick.timestamp_ms,
+                now_ms,
+            });
+        }
+        match self.last_ms.get_mut(&tick.symbol) {
+            Some(last_ms) if tick.timestamp_ms < *last_ms => {
+                if self.allow_out_of_order {
+                    Ok(())
+                } else {
+                    Err(TickError::OutOfOrder {
+                        timestamp_ms: tick.timestamp_ms,
+                        previous_ms: *last_ms,
+                    })
+                }
+            }
+            Some(last_ms) => {
+                *last_ms = tick.timestamp_ms;
+                Ok(())
+            }
+            None => {
+                self.last_ms.insert(tick.symbol.clone(), tick.timestamp_ms);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Reasons a tick is rejected at the ingest boundary.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TickError {
+    InvalidSymbol(SymbolError),
+    NonFinitePrice(f64),
+    NonPositivePrice(f64),
+    MissingTimestamp,
+    /// Stamped further ahead of the receiver's clock than [`MAX_CLOCK_SKEW_MS`].
+    FutureTimestamp {
+        timestamp_ms: u64,
+        now_ms: u64,
+    },
+    /// Older than the previous accepted tick for the same symbol.
+    OutOfOrder {
+        timestamp_ms: u64,
+        previous_ms: u64,
+    },
+}
+
+impl fmt::Display for TickError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TickError::InvalidSymbol(err) => write!(f, "tick {err}"),
+            TickError::NonFinitePrice(price) => write!(f, "tick price {price} is not finite"),
+            TickError::NonPositivePrice(price) => write!(f, "tick price {price} is not positive"),
+            TickError::MissingTimestamp => f.write_str("tick timestamp is zero"),
+            TickError::FutureTimestamp {
+                timestamp_ms,
+                now_ms,
+            } => write!(
+                f,
+                "tick timestamp {timestamp_ms} is more than {MAX_CLOCK_SKEW_MS}ms past the clock ({now_ms})"
+            ),
+            TickError::OutOfOrder {
+                timestamp_ms,
+                previous_ms,
+            } => write!(
+                f,
+                "tick timestamp {timestamp_ms} precedes the symbol's previous tick ({previous_ms})"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TickError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_tick() -> Tick {
+        Tick {
+            symbol: Symbol::new("NATECH000").unwrap(),
+            price: 101.5,
+            timestamp_ms: 1_716_400_000_000,
+            region: Region::NorthAmerica,
+            sector: Sector::Technology,
+            prev_close: None,
+        }
+    }
+
+    #[test]
+    fn tick_deserializes_from_sample() {
+        let json = r#"{
+            "symbol": "NA_TECH007",
+            "price": 134.2875,
+            "timestamp_ms": 1716400005123,
+            "region": "north_america",
+            "sector": "technology"
+        }"#;
+
+        let tick: Tick = serde_json::from_str(json).expect("valid tick");
+        assert_eq!(tick.symbol, "NA_TECH007");
+        assert_eq!(tick.region, Region::NorthAmerica);
+        assert_eq!(tick.sector, Sector::Technology);
+        assert_eq!(tick.validate(), Ok(()));
+    }
+
+    #[test]
+    fn serialized_ticks_deserialize_in_either_key_form() {
+        let tick = valid_tick();
+        let full = serde_json::to_string(&tick).unwrap();
+        let compact = serde_json::to_string(&CompactTick::from(tick.clone())).unwrap();
+        assert_eq!(serde_json::from_str::<Tick>(&full).unwrap(), tick);
+        assert_eq!(serde_json::from_str::<Tick>(&compact).unwrap(), tick);
+    }
+
+    #[test]
+    fn validate_accepts_well_formed_tick() {
+        assert_eq!(valid_tick().validate(), Ok(()));
+    }
+
+    #[test]
+    fn deserialization_rejects_invalid_symbols() {
+        for json in [
+            r#"{"s":" ","p":1.0,"t":1,"r":"europe","c":"energy"}"#,
+            r#"{"s":"","p":1.0,"t":1,"r":"europe","c":"energy"}"#,
+        ] {
+            assert!(serde_json::from_str::<Tick>(json).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn validate_rejects_nan_price() {
+        let tick = Tick {
+            price: f64::NAN,
+            ..valid_tick()
+        };
+        assert!(matches!(tick.validate(), Err(TickError::NonFinitePrice(_))));
+    }
+
+    #[test]
+    fn validate_rejects_non_positive_price() {
+        let tick = Tick {
+            price: 0.0,
+            ..valid_tick()
+        };
+        assert_eq!(tick.validate(), Err(TickError::NonPositivePrice(0.0)));
+    }
+
+    #[test]
+    fn validate_rejects_zero_timestamp() {
+        let tick = Tick {
+            timestamp_ms: 0,
+            ..valid_tick()
+        };
+        assert_eq!(tick.validate(), Err(TickError::MissingTimestamp));
+    }
+
+    #[test]
+    fn validator_rejects_ticks_stamped_in_the_future() {
+        let now_ms = valid_tick().timestamp_ms;
+        let mut validator = TickValidator::default();
+        let skewed = Tick {
+            timestamp_ms: now_ms + MAX_CLOCK_SKEW_MS,
+            ..valid_tick()
+        };
+        assert_eq!(validator.check(&skewed, now_ms), Ok(()));
+
+        let future = Tick {
+            timestamp_ms: now_ms + MAX_CLOCK_SKEW_MS + 1,
+            ..valid_tick()
+        };
+        assert_eq!(
+            validator.check(&future, now_ms),
+            Err(TickError::FutureTimestamp {
+                timestamp_ms: future.timestamp_ms,
+                now_ms
+            })
+        );
+    }
+
+    #[test]
+    fn validator_rejects_ticks_older_than_the_previous_one() {
+        let tick = valid_tick();
+        let now_ms = tick.timestamp_ms;
+        let mut validator = TickValidator::default();
+        assert_eq!(validator.check(&tick, now_ms), Ok(()));
+        // batches repeat a symbol's latest tick until it trades again
+        assert_eq!(validator.check(&tick, now_ms), Ok(()));
+
+        let older = Tick {
+            timestamp_ms: tick.timestamp_ms - 1,
+            ..valid_tick()
+        };
+        assert_eq!(
+            validator.check(&older, now_ms),
+            Err(TickError::OutOfOrder {
+                timestamp_ms: older.timestamp_ms,
+                previous_ms: tick.timestamp_ms
+            })
+        );
+        let other_symbol = Tick {
+            symbol: Symbol::new("EUENG000").unwrap(),
+            ..older.clone()
+        };
+        assert_eq!(validator.check(&other_symbol, now_ms), Ok(()));
+        assert!(matches!(
+            validator.check(&Tick { price: 0.0, ..tick }, now_ms),
+            Err(TickError::NonPositivePrice(_))
+        ));
+    }
+
+    #[test]
+    fn permissive_validator_accepts_late_ticks() {
+        let tick = valid_tick();
+        let now_ms = tick.timestamp_ms;
+        let mut validator = TickValidator::default().allowing_out_of_order();
+        assert_eq!(validator.check(&tick, now_ms), Ok(()));
+        let older = Tick {
+            timestamp_ms: tick.timestamp_ms - 1,
+            ..valid_tick()
+        };
+        assert_eq!(validator.check(&older, now_ms), Ok(()));
+        // the other checks still apply
+        assert!(matches!(
+            validator.check(
+                &Tick {
+                    price: 0.0,
+                    ..older
+                },
+                now_ms
+            ),
+            Err(TickError::NonPositivePrice(_))
+        ));
+    }
+
+    #[test]
+    fn compact_tick_round_trips() {
+        let tick = valid_tick();
+        let compact = CompactTick::from(tick.clone());
+        let encoded = serde_json::to_string(&compact).expect("serialize compact");
+        assert!(encoded.contains("\"s\":\"NATECH000\""));
+
+        let decoded: CompactTick = serde_json::from_str(&encoded).expect("deserialize compact");
+        assert_eq!(decoded, compact);
+
+        let back: Tick = decoded.into();
+        assert_eq!(back.symbol, tick.symbol);
+        assert_eq!(back.price, tick.price);
+        assert_eq!(back.timestamp_ms, tick.timestamp_ms);
+        assert_eq!(back.region, tick.region);
+        assert_eq!(back.sector, tick.sector);
+
+        let via_alias: Tick = serde_json::from_str(&encoded).expect("tick accepts short keys");
+        assert_eq!(via_alias.symbol, tick.symbol);
+    }
+
+    #[test]
+    fn prev_close_is_optional_on_the_wire() {
+        let encoded = serde_json::to_value(valid_tick()).unwrap();
+        assert!(encoded.get("prev_close").is_none());
+
+        let tick = Tick {
+            prev_close: Some(99.75),
+            ..valid_tick()
+        };
+        let compact = serde_json::to_value(CompactTick::from(tick.clone())).unwrap();
+        assert_eq!(compact["pc"], 99.75);
+        let decoded: Tick = serde_json::from_value(compact).unwrap();
+        assert_eq!(decoded, tick);
+    }
+
+    #[test]
+    fn compact_batch_is_smaller() {
+        let batch: Vec<Tick> = (0..50)
+            .map(|idx| Tick {
+                symbol: Symbol::new(format!("NATECH{idx:03}")).unwrap(),
+                ..valid_tick()
+            })
+            .collect();
+        let compact: Vec<CompactTick> = batch.iter().cloned().map(CompactTick::from).collect();
+
+        let full_len = serde_json::to_vec(&batch).unwrap().len();
+        let compact_len = serde_json::to_vec(&compact).unwrap().len();
+        assert!(
+            compact_len * 4 < full_len * 3,
+            "expected at least 25% reduction ({compact_len} vs {full_len})"
+        );
+    }
+}
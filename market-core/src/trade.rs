@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::symbol::Symbol;
+
+/// Side that crossed the spread to make a trade happen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+/// One time-and-sales print, with the quote prevailing when it happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trade {
+    pub symbol: Symbol,
+    pub price: f64,
+    /// Shares exchanged.
+    pub size: u32,
+    pub timestamp_ms: u64,
+    pub aggressor_side: Side,
+    pub bid: f64,
+    pub ask: f64,
+}
+
+impl Trade {
+    /// Whether the print lies at or between the prevailing bid and ask.
+    pub fn within_spread(&self) -> bool {
+        self.bid <= self.price && self.price <= self.ask
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trades_use_snake_case_sides() {
+        let trade = Trade {
+            symbol: Symbol::new("EUENG001").unwrap(),
+            price: 100.01,
+            size: 300,
+            timestamp_ms: 1_716_400_005_123,
+            aggressor_side: Side::Buy,
+            bid: 99.98,
+            ask: 100.02,
+        };
+        let encoded = serde_json::to_value(&trade).unwrap();
+        assert_eq!(encoded["aggressor_side"], "buy");
+        assert!(trade.within_spread());
+        assert!(!Trade {
+            price: 100.03,
+            ..trade
+        }
+        .within_spread());
+    }
+}